@@ -0,0 +1,341 @@
+//!
+//!  # Contrast requirements
+//!
+//! A requirements file lists the color pairs of a palette that must stay readable, one per line:
+//!
+//! ```text
+//! # Aliases name hex colors or shades of the palette
+//! surface = #fafafa
+//!
+//! primary-600 on primary-50 >= AA text
+//! primary-a-700 on surface >= AAA large
+//! error-500 on surface >= 3.0
+//! neutral-900 on surface >= Lc 75
+//! ```
+//!
+//! Colors are shades of the palette, e.g. `primary-600` or the alpha shade `primary-a-600`,
+//! aliases or hex colors. The minimum is a WCAG level, a WCAG 2 contrast ratio or an APCA
+//! lightness contrast. Lines starting with `# ` are comments.
+//!
+//! Translucent backgrounds are drawn on the alpha background of the palette before checking
+//! them, the same way its alpha shades are generated.
+//!
+
+use anyhow::{anyhow, bail, Context};
+use color::contrast::{ContrastAlgorithm, RGBA};
+use color::conversion::Srgb8;
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// The minimum contrast of a requirement
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Threshold {
+    pub algorithm: ContrastAlgorithm,
+    pub minimum: f32,
+    /// The WCAG level the minimum was given as, if any
+    pub level: Option<&'static str>,
+}
+
+impl Threshold {
+    /// The contrast of the foreground on the background, as compared against the minimum
+    pub fn contrast(&self, foreground: RGBA, background: RGBA) -> f32 {
+        // Light text on dark backgrounds has a negative APCA contrast
+        self.algorithm.contrast(foreground, background).abs()
+    }
+}
+
+impl Display for Threshold {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match (self.algorithm, self.level) {
+            (_, Some(level)) => write!(f, "{level} ({})", self.minimum),
+            (ContrastAlgorithm::Apca, None) => write!(f, "Lc {}", self.minimum),
+            (ContrastAlgorithm::Wcag2, None) => write!(f, "{}", self.minimum),
+        }
+    }
+}
+
+impl FromStr for Threshold {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let words = s.split_whitespace().collect::<Vec<_>>();
+
+        let wcag_level = |level, minimum| Threshold {
+            algorithm: ContrastAlgorithm::Wcag2,
+            minimum,
+            level: Some(level),
+        };
+
+        match words.as_slice() {
+            ["AA"] | ["AA", "text"] => Ok(wcag_level("AA text", 4.5)),
+            ["AA", "large"] => Ok(wcag_level("AA large", 3.0)),
+            ["AAA"] | ["AAA", "text"] => Ok(wcag_level("AAA text", 7.0)),
+            ["AAA", "large"] => Ok(wcag_level("AAA large", 4.5)),
+            ["Lc", minimum] => Ok(Threshold {
+                algorithm: ContrastAlgorithm::Apca,
+                minimum: minimum.parse().map_err(|_| ())?,
+                level: None,
+            }),
+            [minimum] => Ok(Threshold {
+                algorithm: ContrastAlgorithm::Wcag2,
+                minimum: minimum.trim_end_matches(":1").parse().map_err(|_| ())?,
+                level: None,
+            }),
+            _ => Err(()),
+        }
+    }
+}
+
+/// A single `foreground on background >= minimum` line
+#[derive(Debug, Clone, PartialEq)]
+pub struct Requirement {
+    /// Line number in the requirements file, starting at 1
+    pub line: usize,
+    pub foreground: String,
+    pub background: String,
+    pub threshold: Threshold,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Requirements {
+    pub aliases: BTreeMap<String, String>,
+    pub requirements: Vec<Requirement>,
+}
+
+impl FromStr for Requirements {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut requirements = Requirements::default();
+
+        for (idx, line) in s.lines().enumerate() {
+            let line_number = idx + 1;
+            let line = line.trim();
+
+            if line.is_empty() || line == "#" || line.starts_with("# ") {
+                continue;
+            }
+
+            if let Some((alias, color)) = line.split_once('=').filter(|_| !line.contains(">=")) {
+                requirements
+                    .aliases
+                    .insert(alias.trim().to_string(), color.trim().to_string());
+                continue;
+            }
+
+            let parse_requirement = || {
+                let (pair, threshold) = line.split_once(">=")?;
+                let (foreground, background) = pair.split_once(" on ")?;
+
+                Some(Requirement {
+                    line: line_number,
+                    foreground: foreground.trim().to_string(),
+                    background: background.trim().to_string(),
+                    threshold: threshold.parse().ok()?,
+                })
+            };
+
+            let requirement = parse_requirement().ok_or_else(|| {
+                anyhow!(
+                    "line {line_number}: expected `<color> on <color> >= <minimum>`, got `{line}`"
+                )
+            })?;
+
+            requirements.requirements.push(requirement);
+        }
+
+        Ok(requirements)
+    }
+}
+
+/// The outcome of checking one requirement
+#[derive(Debug, Clone, PartialEq)]
+pub struct CheckResult {
+    pub requirement: Requirement,
+    pub contrast: f32,
+}
+
+impl CheckResult {
+    pub fn passed(&self) -> bool {
+        self.contrast >= self.requirement.threshold.minimum
+    }
+}
+
+impl Display for CheckResult {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let requirement = &self.requirement;
+        let (status, comparison) = if self.passed() {
+            ("PASS", ">=")
+        } else {
+            ("FAIL", "< ")
+        };
+
+        write!(
+            f,
+            "{status}  line {:<3} {} on {}: {:.2} {comparison} {}",
+            requirement.line,
+            requirement.foreground,
+            requirement.background,
+            self.contrast,
+            requirement.threshold
+        )
+    }
+}
+
+impl Requirements {
+    /// Check every requirement against the named shades of a palette, see
+    /// [palette::palette::Palette::to_named_shades]. Translucent backgrounds are composited over
+    /// the alpha background of the palette
+    pub fn check(
+        &self,
+        shades: &[(String, RGBA)],
+        alpha_background: Srgb8,
+    ) -> anyhow::Result<Vec<CheckResult>> {
+        let shades = shades.iter().cloned().collect::<BTreeMap<_, _>>();
+
+        self.requirements
+            .iter()
+            .map(|requirement| {
+                let foreground = self
+                    .resolve(&requirement.foreground, &shades)
+                    .with_context(|| format!("line {}", requirement.line))?;
+                let background = self
+                    .resolve(&requirement.background, &shades)
+                    .with_context(|| format!("line {}", requirement.line))?
+                    .over(alpha_background)
+                    .into();
+
+                Ok(CheckResult {
+                    requirement: requirement.clone(),
+                    contrast: requirement.threshold.contrast(foreground, background),
+                })
+            })
+            .collect()
+    }
+
+    fn resolve(&self, name: &str, shades: &BTreeMap<String, RGBA>) -> anyhow::Result<RGBA> {
+        let color = self.aliases.get(name).map(String::as_str).unwrap_or(name);
+
+        if let Some(shade) = shades.get(color) {
+            return Ok(*shade);
+        }
+
+        if color.starts_with('#') {
+            return color
+                .parse()
+                .map_err(|_| anyhow!("`{color}` is not a valid hex color"));
+        }
+
+        bail!("the palette has no color named `{color}`")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const REQUIREMENTS: &str = "
+# Brand colors
+surface = #ffffff
+
+primary-600 on primary-50 >= AA text
+error-500 on surface >= 3.0
+primary-50 on #000 >= Lc 90
+";
+
+    fn shades() -> Vec<(String, RGBA)> {
+        vec![
+            ("primary-50".to_string(), RGBA::opaque(0xef, 0xf6, 0xff)),
+            ("primary-600".to_string(), RGBA::opaque(0x25, 0x63, 0xeb)),
+            ("error-500".to_string(), RGBA::opaque(0xf8, 0x71, 0x71)),
+        ]
+    }
+
+    #[test]
+    fn test_parse_requirements() {
+        let requirements = REQUIREMENTS.parse::<Requirements>().unwrap();
+
+        assert_eq!(requirements.aliases["surface"], "#ffffff");
+        assert_eq!(requirements.requirements.len(), 3);
+
+        let first = &requirements.requirements[0];
+
+        assert_eq!(first.line, 5);
+        assert_eq!(first.foreground, "primary-600");
+        assert_eq!(first.background, "primary-50");
+        assert_eq!(first.threshold.minimum, 4.5);
+        assert_eq!(
+            requirements.requirements[2].threshold.algorithm,
+            ContrastAlgorithm::Apca
+        );
+
+        assert!("primary-600 primary-50 >= AA"
+            .parse::<Requirements>()
+            .is_err());
+        assert!("primary-600 on primary-50 >= A"
+            .parse::<Requirements>()
+            .is_err());
+    }
+
+    #[test]
+    fn test_thresholds() {
+        assert_eq!("AAA large".parse::<Threshold>().unwrap().minimum, 4.5);
+        assert_eq!("4.5:1".parse::<Threshold>().unwrap().minimum, 4.5);
+        assert_eq!(
+            "AA".parse::<Threshold>().unwrap(),
+            "AA text".parse::<Threshold>().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_check() {
+        let requirements = REQUIREMENTS.parse::<Requirements>().unwrap();
+        let results = requirements
+            .check(&shades(), Srgb8::new(255, 255, 255))
+            .unwrap();
+
+        // #2563eb on #eff6ff is ~4.8:1, #f87171 on white is ~2.8:1
+        assert!(results[0].passed());
+        assert!(!results[1].passed());
+        assert!(results[2].passed());
+        assert!(results[1].to_string().starts_with("FAIL  line 6"));
+    }
+
+    #[test]
+    fn test_unknown_color() {
+        let requirements = "primary-700 on primary-50 >= AA"
+            .parse::<Requirements>()
+            .unwrap();
+
+        let error = requirements
+            .check(&shades(), Srgb8::new(255, 255, 255))
+            .unwrap_err();
+
+        assert!(format!("{error:#}").contains("no color named `primary-700`"));
+    }
+
+    #[test]
+    fn test_translucent_background() {
+        let requirements = "#ffffff on primary-a-500 >= AA"
+            .parse::<Requirements>()
+            .unwrap();
+        let shades = [(
+            "primary-a-500".to_string(),
+            RGBA::new(0x00, 0x00, 0x00, 0x80),
+        )];
+
+        // Half transparent black is drawn as #7f7f7f on white, and #000000 on black
+        let on_white = &requirements
+            .check(&shades, Srgb8::new(255, 255, 255))
+            .unwrap()[0];
+        let on_black = &requirements.check(&shades, Srgb8::new(0, 0, 0)).unwrap()[0];
+
+        assert_eq!(
+            on_white.contrast,
+            color::contrast::contrast(RGBA::opaque(255, 255, 255), RGBA::opaque(127, 127, 127))
+        );
+        assert!(!on_white.passed());
+        assert!((on_black.contrast - 21.).abs() < 1e-3);
+    }
+}
@@ -0,0 +1,311 @@
+use clap::{Args, ValueEnum};
+use color::contrast::RGBA;
+use color::conversion::Srgb8;
+use color::css::CssColorSyntax;
+use palette::base16::{Base16Options, Base16System};
+use palette::editor_theme::EditorFormat;
+use palette::palette::{Palette, TAILWIND_NUMBERS};
+use palette::swatch_book::{PageSize, SwatchBookOptions};
+use palette::swatch_sheet::{SheetOrientation, SwatchLabels, SwatchSheetOptions};
+use palette::terminal_scheme::{TerminalFormat, TerminalTheme};
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum ExportFormat {
+    /// DWIND color file JSON, including the alpha scales
+    Dwind,
+    /// W3C design tokens JSON
+    DesignTokens,
+    /// JASC-PAL palette
+    Jasc,
+    /// GIMP palette, also read by Inkscape, Krita and Aseprite
+    Gpl,
+    /// Lospec hex list, one color per line
+    Hex,
+    /// paint.net palette
+    PaintNet,
+    /// Adobe swatch exchange, with a group per color
+    Ase,
+    /// Photoshop color swatches
+    Aco,
+    /// CSS custom properties
+    Css,
+    /// SCSS map of the colors and their shades
+    Scss,
+    /// LESS variables
+    Less,
+    /// Tailwind v3 `tailwind.config.js`
+    TailwindConfig,
+    /// Tailwind v4 `@theme` CSS
+    TailwindTheme,
+    /// PNG swatch sheet, see the swatch sheet options
+    Png,
+    /// Indexed PNG with the shades in its palette, e.g. for Aseprite. Only uses `--scale`
+    IndexedPng,
+    /// Printable SVG swatch book with every shade's values
+    SwatchBookSvg,
+    /// Printable PDF swatch book, with as many colors per page as fit
+    SwatchBookPdf,
+    /// Alacritty terminal colors TOML
+    Alacritty,
+    /// kitty terminal colors conf
+    Kitty,
+    /// WezTerm terminal colors Lua module, for `config.colors`
+    Wezterm,
+    /// Windows Terminal color scheme JSON
+    WindowsTerminal,
+    /// iTerm2 `.itermcolors` color preset
+    Iterm2,
+    /// Xresources terminal colors
+    Xresources,
+    /// Base16 scheme YAML, for base16 templates
+    Base16,
+    /// Base24 scheme YAML, with darker backgrounds and bright accents
+    Base24,
+    /// VS Code `color-theme.json`
+    VscodeTheme,
+    /// Neovim Lua colorscheme
+    NeovimTheme,
+}
+
+/// The syntax of color values in stylesheet exports, see [CssColorSyntax]
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum ValueSyntax {
+    Hex,
+    Rgb,
+    Hsl,
+    Oklch,
+}
+
+impl From<ValueSyntax> for CssColorSyntax {
+    fn from(value: ValueSyntax) -> Self {
+        match value {
+            ValueSyntax::Hex => CssColorSyntax::Hex,
+            ValueSyntax::Rgb => CssColorSyntax::Rgb,
+            ValueSyntax::Hsl => CssColorSyntax::Hsl,
+            ValueSyntax::Oklch => CssColorSyntax::Oklch,
+        }
+    }
+}
+
+/// The labels of PNG swatch sheets, see [SwatchLabels]
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum Labels {
+    None,
+    Names,
+    HexCodes,
+    NamesAndHexCodes,
+}
+
+impl From<Labels> for SwatchLabels {
+    fn from(value: Labels) -> Self {
+        match value {
+            Labels::None => SwatchLabels::None,
+            Labels::Names => SwatchLabels::Names,
+            Labels::HexCodes => SwatchLabels::HexCodes,
+            Labels::NamesAndHexCodes => SwatchLabels::NamesAndHexCodes,
+        }
+    }
+}
+
+/// The layout of PNG swatch sheets, see [SheetOrientation]
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum Orientation {
+    /// One row of shades per color
+    Rows,
+    /// One column of shades per color
+    Columns,
+}
+
+impl From<Orientation> for SheetOrientation {
+    fn from(value: Orientation) -> Self {
+        match value {
+            Orientation::Rows => SheetOrientation::Rows,
+            Orientation::Columns => SheetOrientation::Columns,
+        }
+    }
+}
+
+#[derive(Args)]
+#[command(next_help_heading = "Swatch sheet options")]
+pub struct SheetArgs {
+    /// Size in pixels of each swatch in PNG exports
+    #[arg(long, default_value_t = 1)]
+    scale: u32,
+    /// Space in pixels between the swatches, and around the sheet
+    #[arg(long, default_value_t = 0)]
+    gap: u32,
+    #[arg(long, value_enum, default_value_t = Labels::None)]
+    labels: Labels,
+    #[arg(long, value_enum, default_value_t = Orientation::Rows)]
+    orientation: Orientation,
+    /// Background color as hex, e.g. `#ffffff`. Transparent if omitted
+    #[arg(long, value_parser = parse_background)]
+    background: Option<Srgb8>,
+}
+
+impl From<&SheetArgs> for SwatchSheetOptions {
+    fn from(args: &SheetArgs) -> Self {
+        SwatchSheetOptions {
+            swatch_size: args.scale,
+            gap: args.gap,
+            labels: args.labels.into(),
+            orientation: args.orientation.into(),
+            background: args.background,
+        }
+    }
+}
+
+/// The paper size of swatch books, see [PageSize]
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum Paper {
+    A4,
+    A3,
+    Letter,
+}
+
+impl From<Paper> for PageSize {
+    fn from(value: Paper) -> Self {
+        match value {
+            Paper::A4 => PageSize::A4,
+            Paper::A3 => PageSize::A3,
+            Paper::Letter => PageSize::Letter,
+        }
+    }
+}
+
+#[derive(Args)]
+#[command(next_help_heading = "Swatch book options")]
+pub struct BookArgs {
+    /// Paper size of swatch books, in landscape
+    #[arg(long, value_enum, default_value_t = Paper::A4)]
+    page_size: Paper,
+    /// Badge every shade of swatch books with the WCAG level of white or black text on it
+    #[arg(long)]
+    contrast_badges: bool,
+}
+
+impl From<&BookArgs> for SwatchBookOptions {
+    fn from(args: &BookArgs) -> Self {
+        SwatchBookOptions {
+            page_size: args.page_size.into(),
+            contrast_badges: args.contrast_badges,
+        }
+    }
+}
+
+#[derive(Args)]
+#[command(next_help_heading = "Terminal color options")]
+pub struct TerminalArgs {
+    /// Map the palette to a light terminal background instead of a dark one. Also makes base16
+    /// and base24 schemes and editor themes light
+    #[arg(long)]
+    light: bool,
+    /// Also set colors 16 to 255, blended from the ANSI colors. Not supported by Windows
+    /// Terminal and iTerm2
+    #[arg(long)]
+    colors_256: bool,
+}
+
+impl TerminalArgs {
+    fn theme(&self) -> TerminalTheme {
+        match self.light {
+            true => TerminalTheme::Light,
+            false => TerminalTheme::Dark,
+        }
+    }
+
+    fn export(&self, palette: &Palette, format: TerminalFormat) -> Vec<u8> {
+        palette
+            .to_terminal_scheme(self.theme())
+            .export(format, self.colors_256)
+            .into_bytes()
+    }
+}
+
+#[derive(Args)]
+#[command(next_help_heading = "Base16 options")]
+pub struct Base16Args {
+    /// Name of the color to take base00 to base07 from. Defaults to the least colorful color
+    #[arg(long)]
+    neutral: Option<String>,
+    /// Shade of the accent colors base08 to base0F
+    #[arg(long, value_parser = parse_shade, default_value = "500")]
+    accent_shade: usize,
+}
+
+impl Base16Args {
+    fn export(&self, palette: &Palette, system: Base16System, theme: TerminalTheme) -> Vec<u8> {
+        let options = Base16Options {
+            system,
+            variant: theme,
+            neutral: self.neutral.clone(),
+            accent_shade: self.accent_shade,
+        };
+
+        palette.to_base16_scheme(&options).to_string().into_bytes()
+    }
+}
+
+fn parse_background(value: &str) -> Result<Srgb8, String> {
+    value
+        .parse::<RGBA>()
+        .map(|color| color.rgb())
+        .map_err(|_| format!("{value} is not a hex color"))
+}
+
+/// The index of a shade number, e.g. 1 for `100`
+fn parse_shade(value: &str) -> Result<usize, String> {
+    TAILWIND_NUMBERS
+        .iter()
+        .position(|number| number.to_string() == value)
+        .ok_or_else(|| format!("{value} is not a shade, e.g. 50, 100 or 950"))
+}
+
+pub fn export(
+    palette: &Palette,
+    format: ExportFormat,
+    sheet: &SheetArgs,
+    book: &BookArgs,
+    terminal: &TerminalArgs,
+    base16: &Base16Args,
+) -> anyhow::Result<Vec<u8>> {
+    Ok(match format {
+        ExportFormat::Dwind => {
+            serde_json::to_string_pretty(&palette.to_dwind_color_file())?.into_bytes()
+        }
+        ExportFormat::DesignTokens => {
+            serde_json::to_string_pretty(&palette.to_design_tokens())?.into_bytes()
+        }
+        ExportFormat::Jasc => palette.to_jasc_pal().into_bytes(),
+        ExportFormat::Gpl => palette.to_gimp_palette().into_bytes(),
+        ExportFormat::Hex => palette.to_hex_list().into_bytes(),
+        ExportFormat::PaintNet => palette.to_paint_net_palette().into_bytes(),
+        ExportFormat::Ase => palette.to_ase(),
+        ExportFormat::Aco => palette.to_aco(),
+        ExportFormat::Css => palette.to_css_variables().into_bytes(),
+        ExportFormat::Scss => palette.to_scss_map().into_bytes(),
+        ExportFormat::Less => palette.to_less_variables().into_bytes(),
+        ExportFormat::TailwindConfig => palette.to_tailwind_config().into_bytes(),
+        ExportFormat::TailwindTheme => palette.to_tailwind_theme().into_bytes(),
+        ExportFormat::Png => palette.to_swatch_sheet(&sheet.into())?.to_png()?,
+        ExportFormat::IndexedPng => palette.to_indexed_png(sheet.scale)?,
+        ExportFormat::SwatchBookSvg => palette.to_swatch_book_svg(&book.into()).into_bytes(),
+        ExportFormat::SwatchBookPdf => palette.to_swatch_book_pdf(&book.into()),
+        ExportFormat::Alacritty => terminal.export(palette, TerminalFormat::Alacritty),
+        ExportFormat::Kitty => terminal.export(palette, TerminalFormat::Kitty),
+        ExportFormat::Wezterm => terminal.export(palette, TerminalFormat::WezTerm),
+        ExportFormat::WindowsTerminal => terminal.export(palette, TerminalFormat::WindowsTerminal),
+        ExportFormat::Iterm2 => terminal.export(palette, TerminalFormat::ITerm2),
+        ExportFormat::Xresources => terminal.export(palette, TerminalFormat::Xresources),
+        ExportFormat::Base16 => base16.export(palette, Base16System::Base16, terminal.theme()),
+        ExportFormat::Base24 => base16.export(palette, Base16System::Base24, terminal.theme()),
+        ExportFormat::VscodeTheme => palette
+            .to_editor_theme(terminal.theme())
+            .export(EditorFormat::VsCode)
+            .into_bytes(),
+        ExportFormat::NeovimTheme => palette
+            .to_editor_theme(terminal.theme())
+            .export(EditorFormat::Neovim)
+            .into_bytes(),
+    })
+}
@@ -0,0 +1,147 @@
+//!
+//!  # palettepal
+//!
+//! Command line tool for working with `.palettepal` files outside of the browser, e.g. to
+//! regenerate exports in CI.
+//!
+
+mod check;
+mod export;
+
+use crate::check::Requirements;
+use crate::export::{Base16Args, BookArgs, ExportFormat, SheetArgs, TerminalArgs, ValueSyntax};
+use anyhow::Context;
+use clap::{Args, Parser, Subcommand};
+use palette::palette::Palette;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+#[derive(Parser)]
+#[command(name = "palettepal", version, about)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Render a palette file to one of the export formats
+    Export(ExportArgs),
+    /// Check the contrast requirements of a palette. Exits with an error if any of them fail
+    Check(CheckArgs),
+}
+
+#[derive(Args)]
+struct ExportArgs {
+    /// The `.palettepal` file to export
+    palette: PathBuf,
+    #[arg(short, long, value_enum)]
+    format: ExportFormat,
+    /// Where to write the export. Written to stdout if omitted
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+    /// Syntax of the color values in CSS, SCSS and LESS exports. Defaults to the one saved in
+    /// the palette
+    #[arg(long, value_enum)]
+    syntax: Option<ValueSyntax>,
+    #[command(flatten)]
+    sheet: SheetArgs,
+    #[command(flatten)]
+    book: BookArgs,
+    #[command(flatten)]
+    terminal: TerminalArgs,
+    #[command(flatten)]
+    base16: Base16Args,
+}
+
+#[derive(Args)]
+struct CheckArgs {
+    /// The `.palettepal` file to check
+    palette: PathBuf,
+    /// File listing the requirements, e.g. `primary-600 on primary-50 >= AA text`
+    #[arg(short, long)]
+    requirements: PathBuf,
+}
+
+fn main() -> anyhow::Result<ExitCode> {
+    match Cli::parse().command {
+        Command::Export(args) => {
+            let palette = load_palette(&args.palette)?;
+
+            if let Some(syntax) = args.syntax {
+                palette.css_syntax.set(syntax.into());
+            }
+
+            let content = export::export(
+                &palette,
+                args.format,
+                &args.sheet,
+                &args.book,
+                &args.terminal,
+                &args.base16,
+            )?;
+
+            write_output(args.output.as_deref(), &content)?;
+
+            Ok(ExitCode::SUCCESS)
+        }
+        Command::Check(args) => check(&args),
+    }
+}
+
+fn check(args: &CheckArgs) -> anyhow::Result<ExitCode> {
+    let palette = load_palette(&args.palette)?;
+    let requirements = std::fs::read_to_string(&args.requirements)
+        .with_context(|| format!("Failed to read {}", args.requirements.display()))?
+        .parse::<Requirements>()
+        .with_context(|| format!("Invalid requirements in {}", args.requirements.display()))?;
+
+    let results = requirements
+        .check(&palette.to_named_shades(), palette.alpha_background.get())
+        .with_context(|| format!("Invalid requirements in {}", args.requirements.display()))?;
+
+    for result in &results {
+        println!("{result}");
+    }
+
+    let failed = results.iter().filter(|result| !result.passed()).count();
+
+    println!();
+    println!(
+        "{} of {} contrast requirements passed",
+        results.len() - failed,
+        results.len()
+    );
+
+    Ok(if failed == 0 {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    })
+}
+
+/// Load a palette saved by the website. The open tools saved along with it are ignored
+fn load_palette(path: &Path) -> anyhow::Result<Palette> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+
+    let palette: Palette = serde_json::from_str(&content)
+        .with_context(|| format!("{} is not a palettepal file", path.display()))?;
+
+    palette
+        .validate()
+        .with_context(|| format!("{} is not a valid palettepal file", path.display()))?;
+
+    Ok(palette)
+}
+
+fn write_output(output: Option<&Path>, content: &[u8]) -> anyhow::Result<()> {
+    match output {
+        Some(path) => std::fs::write(path, content)
+            .with_context(|| format!("Failed to write {}", path.display())),
+        None => std::io::stdout()
+            .write_all(content)
+            .context("Failed to write to stdout"),
+    }
+}
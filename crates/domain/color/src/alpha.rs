@@ -0,0 +1,135 @@
+//!
+//!  # Alpha
+//!
+//! Alpha scales, translucent versions of solid colors that look the same when drawn on a given
+//! background. Useful for overlays such as hover and pressed states, which then work on top of
+//! any surface close to the background.
+//!
+//! https://www.radix-ui.com/colors/docs/palette-composition/understanding-the-scale
+//!
+
+use crate::contrast::RGBA;
+use crate::conversion::Srgb8;
+
+/// Return the most transparent color that composites to exactly `color` when drawn over
+/// `background`.
+///
+/// Returns a fully transparent color if `color` is the background itself.
+pub fn minimum_alpha(color: Srgb8, background: Srgb8) -> RGBA {
+    let channels = [
+        (color.r, background.r),
+        (color.g, background.g),
+        (color.b, background.b),
+    ];
+
+    // The foreground channels must stay in 0..255, which limits how far a channel can be moved
+    // from the background at a given alpha
+    let alpha = channels
+        .iter()
+        .map(|&(channel, background)| {
+            let (channel, background) = (channel as f32, background as f32);
+
+            if channel > background {
+                (channel - background) / (255. - background)
+            } else if channel < background {
+                (background - channel) / background
+            } else {
+                0.
+            }
+        })
+        .fold(0., f32::max);
+
+    if alpha == 0. {
+        return RGBA::from(color).with_alpha(0);
+    }
+
+    let unmix = |a: u8, channel: u8, background: u8| {
+        let a = a as f32 / 255.;
+        let (channel, background) = (channel as f32, background as f32);
+
+        (background + (channel - background) / a)
+            .round()
+            .clamp(0., 255.) as u8
+    };
+
+    // Rounding to 8 bits may move the composited color, so pick the first alpha that reproduces
+    // it exactly. An opaque color always does.
+    ((alpha * 255.).ceil() as u8..=255)
+        .map(|a| {
+            RGBA::new(
+                unmix(a, color.r, background.r),
+                unmix(a, color.g, background.g),
+                unmix(a, color.b, background.b),
+                a,
+            )
+        })
+        .find(|candidate| candidate.over(background) == color)
+        .unwrap_or(RGBA::from(color))
+}
+
+/// The alpha version of every color of the swatch, see [minimum_alpha]. Translucent colors of
+/// the swatch are composited over the background first
+pub fn alpha_scale(swatch: impl IntoIterator<Item = RGBA>, background: Srgb8) -> Vec<RGBA> {
+    swatch
+        .into_iter()
+        .map(|color| minimum_alpha(color.over(background), background))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const WHITE: Srgb8 = Srgb8::new(255, 255, 255);
+    const BLACK: Srgb8 = Srgb8::new(0, 0, 0);
+
+    #[test]
+    fn test_alpha_composites_to_color() {
+        for background in [
+            WHITE,
+            BLACK,
+            Srgb8::new(30, 40, 50),
+            Srgb8::new(250, 240, 200),
+        ] {
+            for r in (0..=255u8).step_by(17) {
+                for g in (0..=255u8).step_by(51) {
+                    for b in (0..=255u8).step_by(85) {
+                        let color = Srgb8::new(r, g, b);
+                        let alpha = minimum_alpha(color, background);
+
+                        assert_eq!(alpha.over(background), color, "{alpha} over {background}");
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_greys_on_white_are_translucent_black() {
+        assert_eq!(
+            minimum_alpha(Srgb8::new(128, 128, 128), WHITE),
+            RGBA::new(0, 0, 0, 127)
+        );
+        assert_eq!(minimum_alpha(BLACK, WHITE), RGBA::new(0, 0, 0, 255));
+        assert_eq!(minimum_alpha(WHITE, WHITE).a, 0);
+        assert_eq!(minimum_alpha(WHITE, BLACK), RGBA::new(255, 255, 255, 255));
+    }
+
+    /// Light shades are more transparent than dark shades on a light background
+    #[test]
+    fn test_alpha_scale() {
+        let swatch = [
+            RGBA::opaque(0xef, 0xf6, 0xff),
+            RGBA::opaque(0x93, 0xc5, 0xfd),
+            RGBA::opaque(0x1d, 0x4e, 0xd8),
+        ];
+
+        let scale = alpha_scale(swatch, WHITE);
+
+        assert!(scale[0].a < scale[1].a && scale[1].a < scale[2].a);
+
+        for (alpha, color) in scale.iter().zip(swatch) {
+            assert_eq!(alpha.over(WHITE), color.rgb());
+        }
+    }
+}
@@ -0,0 +1,225 @@
+//!
+//!  # APCA
+//!
+//! This module implements the Accessible Perceptual Contrast Algorithm (APCA), the contrast
+//! method proposed for WCAG 3. Unlike the WCAG 2 ratio, the APCA lightness contrast (Lc) is
+//! polarity aware: dark text on a light background yields a positive Lc, light text on a dark
+//! background yields a negative Lc, and the magnitudes differ between the two.
+//!
+//! Constants are from APCA-W3 0.0.98G-4g
+//! https://github.com/Myndex/apca-w3
+//!
+
+use crate::contrast::RGBA;
+use crate::conversion::Srgb8;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+const MAIN_TRC: f32 = 2.4;
+
+const R_COEFFICIENT: f32 = 0.2126729;
+const G_COEFFICIENT: f32 = 0.7151522;
+const B_COEFFICIENT: f32 = 0.0721750;
+
+const NORMAL_BG: f32 = 0.56;
+const NORMAL_TEXT: f32 = 0.57;
+const REVERSE_TEXT: f32 = 0.62;
+const REVERSE_BG: f32 = 0.65;
+
+const BLACK_THRESHOLD: f32 = 0.022;
+const BLACK_CLAMP: f32 = 1.414;
+const SCALE_BOW: f32 = 1.14;
+const SCALE_WOB: f32 = 1.14;
+const LOW_BOW_OFFSET: f32 = 0.027;
+const LOW_WOB_OFFSET: f32 = 0.027;
+const DELTA_Y_MIN: f32 = 0.0005;
+const LOW_CLIP: f32 = 0.1;
+
+/// The screen luminance estimate used by APCA.
+///
+/// Note that this deliberately differs from the WCAG relative luminance, it uses a simple
+/// power curve rather than the piecewise sRGB transfer function.
+pub fn screen_luminance(color: Srgb8) -> f32 {
+    let Srgb8 { r, g, b } = color;
+    let linearize = |c: u8| (c as f32 / 255.).powf(MAIN_TRC);
+
+    R_COEFFICIENT * linearize(r) + G_COEFFICIENT * linearize(g) + B_COEFFICIENT * linearize(b)
+}
+
+/// Soft clamp of luminances near black, to model flare
+fn soft_clamp_black(y: f32) -> f32 {
+    if y > BLACK_THRESHOLD {
+        y
+    } else {
+        y + (BLACK_THRESHOLD - y).powf(BLACK_CLAMP)
+    }
+}
+
+/// Calculate the APCA lightness contrast (Lc) of text on a background.
+///
+/// The result is roughly in the range -108..106. Positive values mean dark text on a light
+/// background, negative values light text on a dark background.
+///
+/// Translucent text is composited over the background first, while the alpha of the background
+/// is ignored.
+pub fn lightness_contrast(text_color: RGBA, background_color: RGBA) -> f32 {
+    let background_color = background_color.rgb();
+    let text_y = soft_clamp_black(screen_luminance(text_color.over(background_color)));
+    let background_y = soft_clamp_black(screen_luminance(background_color));
+
+    if (background_y - text_y).abs() < DELTA_Y_MIN {
+        return 0.;
+    }
+
+    let contrast = if background_y > text_y {
+        let sapc = (background_y.powf(NORMAL_BG) - text_y.powf(NORMAL_TEXT)) * SCALE_BOW;
+
+        if sapc < LOW_CLIP {
+            0.
+        } else {
+            sapc - LOW_BOW_OFFSET
+        }
+    } else {
+        let sapc = (background_y.powf(REVERSE_BG) - text_y.powf(REVERSE_TEXT)) * SCALE_WOB;
+
+        if sapc > -LOW_CLIP {
+            0.
+        } else {
+            sapc + LOW_WOB_OFFSET
+        }
+    };
+
+    contrast * 100.
+}
+
+/// Usage levels for text and non-text elements, according to the APCA "bronze" conformance
+/// level. Each level lists the minimum absolute Lc needed.
+///
+/// https://readtech.org/ARC/tests/bronze-simple-mode/
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub enum ApcaUsage {
+    /// Lc 90, preferred for body text and columns of text
+    FluentText,
+    /// Lc 75, minimum for body text (18px/400 or 14px/700)
+    BodyText,
+    /// Lc 60, minimum for other content text (24px/400 or 16px/700)
+    ContentText,
+    /// Lc 45, minimum for large text and headlines (36px/400 or 24px/700)
+    LargeText,
+    /// Lc 30, minimum for spot text such as placeholders and disabled elements
+    SpotText,
+    /// Lc 15, minimum for non-text elements such as dividers and focus rings
+    NonText,
+}
+
+impl ApcaUsage {
+    pub const ALL: [ApcaUsage; 6] = [
+        ApcaUsage::FluentText,
+        ApcaUsage::BodyText,
+        ApcaUsage::ContentText,
+        ApcaUsage::LargeText,
+        ApcaUsage::SpotText,
+        ApcaUsage::NonText,
+    ];
+
+    /// The minimum absolute Lc value for this usage
+    pub fn minimum_lc(&self) -> f32 {
+        match self {
+            ApcaUsage::FluentText => 90.,
+            ApcaUsage::BodyText => 75.,
+            ApcaUsage::ContentText => 60.,
+            ApcaUsage::LargeText => 45.,
+            ApcaUsage::SpotText => 30.,
+            ApcaUsage::NonText => 15.,
+        }
+    }
+
+    /// The most demanding usage the lightness contrast is sufficient for, if any.
+    /// Polarity is ignored.
+    pub fn from_lc(lc: f32) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|usage| lc.abs() >= usage.minimum_lc())
+    }
+
+    /// Human readable font size and weight requirements for this usage
+    pub fn font_requirement(&self) -> &'static str {
+        match self {
+            ApcaUsage::FluentText => "Any text, 14px/400 and up",
+            ApcaUsage::BodyText => "Body text, 18px/400 or 14px/700",
+            ApcaUsage::ContentText => "Content text, 24px/400 or 16px/700",
+            ApcaUsage::LargeText => "Headlines, 36px/400 or 24px/700",
+            ApcaUsage::SpotText => "Spot text and placeholders",
+            ApcaUsage::NonText => "Non-text elements",
+        }
+    }
+}
+
+impl Display for ApcaUsage {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Lc {}", self.minimum_lc())
+    }
+}
+
+impl FromStr for ApcaUsage {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|usage| usage.to_string() == s)
+            .ok_or(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{lightness_contrast, ApcaUsage};
+    use crate::contrast::RGBA;
+
+    fn assert_lc(text: u32, background: u32, expected: f32) {
+        let to_rgb = |c: u32| RGBA::opaque((c >> 16) as u8, (c >> 8) as u8, c as u8);
+        let actual = lightness_contrast(to_rgb(text), to_rgb(background));
+
+        assert!(
+            (actual - expected).abs() < 0.1,
+            "Lc of #{text:06x} on #{background:06x} was {actual}, expected {expected}"
+        );
+    }
+
+    /// Reference values from the apca-w3 test suite
+    #[test]
+    fn test_reference_values() {
+        assert_lc(0x000000, 0xffffff, 106.04);
+        assert_lc(0xffffff, 0x000000, -107.88);
+        assert_lc(0x888888, 0xffffff, 63.06);
+        assert_lc(0xffffff, 0x888888, -68.54);
+        assert_lc(0x000000, 0xaaaaaa, 58.15);
+        assert_lc(0xaaaaaa, 0x000000, -56.24);
+        assert_lc(0x123456, 0xdef00f, 83.08);
+        assert_lc(0xdef00f, 0x123456, -84.93);
+    }
+
+    #[test]
+    fn test_similar_colors_have_no_contrast() {
+        assert_eq!(
+            lightness_contrast(
+                RGBA::opaque(0x11, 0x22, 0x33),
+                RGBA::opaque(0x22, 0x22, 0x22)
+            ),
+            0.
+        );
+        assert_eq!(
+            lightness_contrast(RGBA::opaque(200, 200, 200), RGBA::opaque(200, 200, 200)),
+            0.
+        );
+    }
+
+    #[test]
+    fn test_usage_from_lc() {
+        assert_eq!(ApcaUsage::from_lc(106.), Some(ApcaUsage::FluentText));
+        assert_eq!(ApcaUsage::from_lc(-80.), Some(ApcaUsage::BodyText));
+        assert_eq!(ApcaUsage::from_lc(45.), Some(ApcaUsage::LargeText));
+        assert_eq!(ApcaUsage::from_lc(10.), None);
+    }
+}
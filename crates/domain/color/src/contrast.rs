@@ -5,31 +5,50 @@
 //! the contrast fulfills various WCAG requirements
 //!
 
+use crate::apca;
+use crate::conversion::{srgb_to_linear, Hsl, Srgb8, Srgba8};
 use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
 
-pub type RGBA = (u8, u8, u8);
+/// 8 bit sRGB color with straight alpha
+pub type RGBA = Srgba8;
 
-pub fn brightness(color: RGBA) -> f32 {
-    let (r, g, b) = color;
-    // https://stackoverflow.com/questions/596216/formula-to-determine-perceived-brightness-of-rgb-color
-    //((r as f32).powf(2.) * 0.299 + (g as f32).powf(2.) * 0.587 + (b as f32).powf(2.) * 0.114).sqrt()
-    // 0.2126 * r as f32 + 0.7152 * g as f32 + 0.0722 * b as f32
-    let hsl = hsl::HSL::from_rgb(&[r, g, b]);
-    hsl.l as f32
+/// Calculate the WCAG relative luminance of the color, in the range 0..1
+///
+/// https://www.w3.org/TR/WCAG/#dfn-relative-luminance
+pub fn relative_luminance(color: Srgb8) -> f32 {
+    let linear = |channel: u8| srgb_to_linear(channel as f32 / 255.);
+    let Srgb8 { r, g, b } = color;
+
+    0.2126 * linear(r) + 0.7152 * linear(g) + 0.0722 * linear(b)
 }
 
-/// Calculate the WCAG contrast ratio between the two colors
+/// The HSL lightness of the color, in the range 0..1
+///
+/// Note: this is *not* the luminance used by WCAG, and must not be used for contrast calculations.
+/// Saturated colors of any hue share the same perceived brightness, even though their
+/// luminance differs greatly.
+pub fn perceived_brightness(color: Srgb8) -> f32 {
+    Hsl::from(color).l
+}
+
+/// Calculate the WCAG contrast ratio between a foreground and a background color.
+///
+/// A translucent foreground is composited over the background first, while the alpha of the
+/// background is ignored, as it is assumed to be drawn on an opaque surface.
 ///
 /// https://www.w3.org/TR/WCAG/#dfn-contrast-ratio
-pub fn contrast(color_a: RGBA, color_b: RGBA) -> f32 {
-    let mut brightness_a = brightness(color_a) + 0.05;
-    let mut brightness_b = brightness(color_b) + 0.05;
+pub fn contrast(foreground: RGBA, background: RGBA) -> f32 {
+    let background = background.rgb();
+    let mut luminance_a = relative_luminance(foreground.over(background)) + 0.05;
+    let mut luminance_b = relative_luminance(background) + 0.05;
 
-    if brightness_a < brightness_b {
-        std::mem::swap(&mut brightness_a, &mut brightness_b);
+    if luminance_a < luminance_b {
+        std::mem::swap(&mut luminance_a, &mut luminance_b);
     }
 
-    brightness_a / brightness_b
+    luminance_a / luminance_b
 }
 
 /// Determine if a contrast fulfills the requirement for normal text
@@ -56,19 +75,70 @@ pub fn is_enhanced_large_text_contrast(text_color: RGBA, background_color: RGBA)
     contrast(text_color, background_color) >= 4.5
 }
 
+/// The method used to calculate the contrast between two colors
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ContrastAlgorithm {
+    /// The WCAG 2 contrast ratio, in the range 1..21
+    #[default]
+    Wcag2,
+    /// The APCA lightness contrast (Lc), see [apca::lightness_contrast]
+    Apca,
+}
+
+impl ContrastAlgorithm {
+    /// Calculate the contrast of `color_a` (the text) on `color_b` (the background).
+    ///
+    /// A translucent `color_a` is composited over `color_b` first, see [contrast]
+    pub fn contrast(&self, color_a: RGBA, color_b: RGBA) -> f32 {
+        match self {
+            ContrastAlgorithm::Wcag2 => contrast(color_a, color_b),
+            ContrastAlgorithm::Apca => apca::lightness_contrast(color_a, color_b),
+        }
+    }
+}
+
+impl Display for ContrastAlgorithm {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ContrastAlgorithm::Wcag2 => write!(f, "WCAG 2"),
+            ContrastAlgorithm::Apca => write!(f, "APCA"),
+        }
+    }
+}
+
+impl FromStr for ContrastAlgorithm {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "WCAG 2" => Ok(Self::Wcag2),
+            "APCA" => Ok(Self::Apca),
+            _ => Err(()),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct SwatchColorContrast {
     pub swatch_a_idx: usize,
     pub swatch_b_idx: usize,
+    /// The foreground (text) color
     pub color_a: RGBA,
+    /// The background color
     pub color_b: RGBA,
+    pub algorithm: ContrastAlgorithm,
+    /// The contrast of `color_a` on `color_b`, as calculated by `algorithm`
     pub contrast: f32,
 }
 
-/// Return the contrast between all colors in both swatches
+/// Return the contrast between all colors in both swatches, with the colors of `swatch_a` used
+/// as the foreground and `swatch_b` as the background.
+///
+/// The result is sorted by the magnitude of the contrast, lowest first
 pub fn swatch_color_contrast(
     swatch_a: impl IntoIterator<Item = RGBA>,
     swatch_b: impl IntoIterator<Item = RGBA> + Clone,
+    algorithm: ContrastAlgorithm,
 ) -> Vec<SwatchColorContrast> {
     let mut out: Vec<_> = swatch_a
         .into_iter()
@@ -83,13 +153,14 @@ pub fn swatch_color_contrast(
                     swatch_b_idx,
                     color_a,
                     color_b,
-                    contrast: contrast(color_a, color_b),
+                    algorithm,
+                    contrast: algorithm.contrast(color_a, color_b),
                 })
         })
         .collect();
 
     out.sort_by(|lhs, rhs| {
-        if lhs.contrast < rhs.contrast {
+        if lhs.contrast.abs() < rhs.contrast.abs() {
             Ordering::Less
         } else {
             Ordering::Greater
@@ -101,15 +172,110 @@ pub fn swatch_color_contrast(
 
 #[cfg(test)]
 mod test {
-    use crate::contrast::SwatchColorContrast;
+    use crate::contrast::{
+        contrast, relative_luminance, ContrastAlgorithm, SwatchColorContrast, RGBA,
+    };
+    use crate::conversion::Srgb8;
+
+    const WHITE: RGBA = RGBA::opaque(255, 255, 255);
+    const BLACK: RGBA = RGBA::opaque(0, 0, 0);
+
+    fn assert_ratio(color_a: RGBA, color_b: RGBA, expected: f32) {
+        let actual = contrast(color_a, color_b);
+
+        assert!(
+            (actual - expected).abs() < 0.01,
+            "contrast between {color_a:?} and {color_b:?} was {actual}, expected {expected}"
+        );
+    }
+
+    #[test]
+    fn test_relative_luminance() {
+        assert_eq!(relative_luminance(BLACK.rgb()), 0.);
+        assert!((relative_luminance(WHITE.rgb()) - 1.).abs() < 0.0001);
+        assert!((relative_luminance(Srgb8::new(255, 0, 0)) - 0.2126).abs() < 0.0001);
+        assert!((relative_luminance(Srgb8::new(0, 255, 0)) - 0.7152).abs() < 0.0001);
+        assert!((relative_luminance(Srgb8::new(0, 0, 255)) - 0.0722).abs() < 0.0001);
+    }
+
+    /// Reference ratios, as reported by the WebAIM and W3C contrast checkers
+    #[test]
+    fn test_reference_ratios() {
+        assert_ratio(BLACK, WHITE, 21.);
+        assert_ratio(WHITE, WHITE, 1.);
+        assert_ratio(RGBA::opaque(0x76, 0x76, 0x76), WHITE, 4.54);
+        assert_ratio(RGBA::opaque(0x77, 0x77, 0x77), WHITE, 4.48);
+        assert_ratio(RGBA::opaque(0x59, 0x59, 0x59), WHITE, 7.0);
+        assert_ratio(RGBA::opaque(0xff, 0x00, 0x00), WHITE, 4.0);
+        assert_ratio(RGBA::opaque(0x00, 0x80, 0x00), WHITE, 5.14);
+        assert_ratio(RGBA::opaque(0x00, 0x00, 0xff), WHITE, 8.59);
+        assert_ratio(RGBA::opaque(0xff, 0xff, 0x00), WHITE, 1.07);
+        assert_ratio(RGBA::opaque(0xff, 0xff, 0x00), BLACK, 19.56);
+    }
+
+    #[test]
+    fn test_contrast_is_symmetric() {
+        assert_eq!(
+            contrast(
+                RGBA::opaque(0x12, 0x34, 0x56),
+                RGBA::opaque(0xfe, 0xdc, 0xba)
+            ),
+            contrast(
+                RGBA::opaque(0xfe, 0xdc, 0xba),
+                RGBA::opaque(0x12, 0x34, 0x56)
+            )
+        );
+    }
+
+    /// A translucent foreground is composited over the background before the ratio is
+    /// calculated, so 50% black on white has the contrast of a medium grey
+    #[test]
+    fn test_translucent_foreground() {
+        let half_black = BLACK.with_alpha(128);
+
+        assert_ratio(
+            half_black,
+            WHITE,
+            contrast(RGBA::opaque(127, 127, 127), WHITE),
+        );
+        assert_ratio(half_black, BLACK, 1.);
+        assert_ratio(BLACK.with_alpha(0), WHITE, 1.);
+        assert!(contrast(half_black, WHITE) < contrast(BLACK, WHITE));
+    }
+
+    /// Blue and yellow have the same HSL lightness, but very different luminance
+    #[test]
+    fn test_hue_affects_contrast() {
+        assert_eq!(
+            super::perceived_brightness(Srgb8::new(0, 0, 255)),
+            super::perceived_brightness(Srgb8::new(255, 255, 0))
+        );
+
+        assert!(super::is_minimum_text_contrast(
+            RGBA::opaque(0, 0, 255),
+            WHITE
+        ));
+        assert!(!super::is_minimum_large_text_contrast(
+            RGBA::opaque(255, 255, 0),
+            WHITE
+        ));
+    }
 
     #[test]
     fn test_swatch_contrasts() {
-        let swatch_a = (0..20).map(|c| (c, c, c)).collect::<Vec<_>>();
+        let swatch_a = (0..11)
+            .map(|c| RGBA::opaque(c * 25, c * 25, c * 25))
+            .collect::<Vec<_>>();
 
-        let swatch_b = (0..20).map(|c| (c, c, c)).collect::<Vec<_>>();
+        let swatch_b = (0..11)
+            .map(|c| RGBA::opaque(c * 25, c * 25, c * 25))
+            .collect::<Vec<_>>();
 
-        let contrasts = super::swatch_color_contrast(swatch_a.clone(), swatch_b.clone());
+        let contrasts = super::swatch_color_contrast(
+            swatch_a.clone(),
+            swatch_b.clone(),
+            ContrastAlgorithm::Wcag2,
+        );
 
         let text_contrasts_minimum: Vec<&SwatchColorContrast> = contrasts
             .iter()
@@ -131,7 +297,25 @@ mod test {
             })
             .collect::<Vec<_>>();
 
-        assert_eq!(text_contrasts_minimum.len(), 51);
-        assert_eq!(text_contrasts_enhanced.len(), 36);
+        assert_eq!(text_contrasts_minimum.len(), 40);
+        assert_eq!(text_contrasts_enhanced.len(), 26);
+    }
+
+    #[test]
+    fn test_apca_swatch_contrasts_are_polarity_aware() {
+        let swatch_a = vec![BLACK];
+        let swatch_b = vec![WHITE];
+
+        let dark_on_light = super::swatch_color_contrast(
+            swatch_a.clone(),
+            swatch_b.clone(),
+            ContrastAlgorithm::Apca,
+        );
+        let light_on_dark =
+            super::swatch_color_contrast(swatch_b, swatch_a, ContrastAlgorithm::Apca);
+
+        assert!(dark_on_light[0].contrast > 0.);
+        assert!(light_on_dark[0].contrast < 0.);
+        assert_ne!(dark_on_light[0].contrast, -light_on_dark[0].contrast);
     }
 }
@@ -0,0 +1,1193 @@
+//!
+//!  # Conversion
+//!
+//! Typed colors in the color spaces used by palettepal, and the conversions between them.
+//!
+//! All conversions go through [Srgb], the gamma encoded sRGB color with f32 channels. Its
+//! channels are not clamped, so colors outside of the sRGB gamut survive the round trip between
+//! the other spaces. Clamping only happens when converting to [Srgb8].
+//!
+//! Hues are in degrees, in the range 0..360.
+//!
+
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// D65 reference white, in CIE XYZ. Matches the white of the sRGB matrices, as in CSS Color 4
+pub const D65_WHITE: Xyz = Xyz {
+    x: 0.9504559,
+    y: 1.,
+    z: 1.089058,
+};
+
+const LAB_EPSILON: f32 = 216. / 24389.;
+const LAB_KAPPA: f32 = 24389. / 27.;
+
+/// Chromaticity coordinates (u', v') of the D65 white point
+const LUV_WHITE_U: f32 = 0.19783;
+const LUV_WHITE_V: f32 = 0.46832;
+
+/// 8 bit sRGB color, as used in hex codes and CSS `rgb()`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Srgb8 {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+/// 8 bit sRGB color with a straight (not premultiplied) alpha channel, as used in `#rrggbbaa`
+/// hex codes and CSS `rgb()` with an alpha value
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Srgba8 {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    pub a: u8,
+}
+
+/// Gamma encoded sRGB, with channels in the range 0..1 when in gamut
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Srgb {
+    pub r: f32,
+    pub g: f32,
+    pub b: f32,
+}
+
+/// Gamma encoded Display P3, with channels in the range 0..1 when in gamut. Uses the sRGB
+/// transfer function and white point, with wider primaries
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct DisplayP3 {
+    pub r: f32,
+    pub g: f32,
+    pub b: f32,
+}
+
+/// Gamma encoded ITU-R BT.2020, with channels in the range 0..1 when in gamut
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Rec2020 {
+    pub r: f32,
+    pub g: f32,
+    pub b: f32,
+}
+
+/// Linear light sRGB, with channels in the range 0..1 when in gamut
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct LinearSrgb {
+    pub r: f32,
+    pub g: f32,
+    pub b: f32,
+}
+
+/// Hue, saturation and value. Saturation and value are in the range 0..1
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Hsv {
+    pub h: f32,
+    pub s: f32,
+    pub v: f32,
+}
+
+/// Hue, saturation and lightness. Saturation and lightness are in the range 0..1
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Hsl {
+    pub h: f32,
+    pub s: f32,
+    pub l: f32,
+}
+
+/// CIE 1931 XYZ, relative to the D65 white point with a white luminance (Y) of 1
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Xyz {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+}
+
+/// CIELAB, relative to the D65 white point. Lightness is in the range 0..100
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Lab {
+    pub l: f32,
+    pub a: f32,
+    pub b: f32,
+}
+
+/// The cylindrical form of [Lab], CIE LCh(ab)
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Lch {
+    pub l: f32,
+    pub c: f32,
+    pub h: f32,
+}
+
+/// OKLab, lightness is in the range 0..1
+///
+/// https://bottosson.github.io/posts/oklab/
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Oklab {
+    pub l: f32,
+    pub a: f32,
+    pub b: f32,
+}
+
+/// The cylindrical form of [Oklab]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Oklch {
+    pub l: f32,
+    pub c: f32,
+    pub h: f32,
+}
+
+/// CIELUV, relative to the D65 white point. Lightness is in the range 0..100
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Luv {
+    pub l: f32,
+    pub u: f32,
+    pub v: f32,
+}
+
+/// The cylindrical form of [Luv], CIE LCh(uv)
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Lchuv {
+    pub l: f32,
+    pub c: f32,
+    pub h: f32,
+}
+
+/// HSLuv, a version of [Lchuv] where the chroma is scaled to the maximum chroma inside the sRGB
+/// gamut for each lightness and hue. Saturation and lightness are in the range 0..100
+///
+/// https://www.hsluv.org/
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Hsluv {
+    pub h: f32,
+    pub s: f32,
+    pub l: f32,
+}
+
+impl Srgb8 {
+    pub const fn new(r: u8, g: u8, b: u8) -> Self {
+        Self { r, g, b }
+    }
+
+    /// Formats the color as a lowercase `#rrggbb` hex code
+    pub fn to_hex(&self) -> String {
+        format!("#{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+    }
+}
+
+impl Display for Srgb8 {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_hex())
+    }
+}
+
+/// Parses hex codes like [Srgba8], ignoring the alpha channel
+impl FromStr for Srgb8 {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse::<Srgba8>().map(|color| color.rgb())
+    }
+}
+
+impl Srgba8 {
+    pub const fn new(r: u8, g: u8, b: u8, a: u8) -> Self {
+        Self { r, g, b, a }
+    }
+
+    pub const fn opaque(r: u8, g: u8, b: u8) -> Self {
+        Self::new(r, g, b, 255)
+    }
+
+    /// The color channels, without alpha
+    pub const fn rgb(&self) -> Srgb8 {
+        Srgb8::new(self.r, self.g, self.b)
+    }
+
+    /// The alpha channel in the range 0..1
+    pub fn alpha(&self) -> f32 {
+        self.a as f32 / 255.
+    }
+
+    pub const fn is_opaque(&self) -> bool {
+        self.a == 255
+    }
+
+    pub const fn with_alpha(&self, a: u8) -> Self {
+        Self::new(self.r, self.g, self.b, a)
+    }
+
+    /// The visible color when this color is drawn on top of an opaque background.
+    ///
+    /// Channels are blended in gamma encoded sRGB, the same way browsers composite CSS colors.
+    pub fn over(&self, background: Srgb8) -> Srgb8 {
+        let alpha = self.alpha();
+        let blend = |fg: u8, bg: u8| (bg as f32 + (fg as f32 - bg as f32) * alpha).round() as u8;
+
+        Srgb8::new(
+            blend(self.r, background.r),
+            blend(self.g, background.g),
+            blend(self.b, background.b),
+        )
+    }
+
+    /// Formats the color as a lowercase `#rrggbb` hex code if it is opaque, and `#rrggbbaa`
+    /// otherwise
+    pub fn to_hex(&self) -> String {
+        if self.is_opaque() {
+            self.rgb().to_hex()
+        } else {
+            format!("{}{:02x}", self.rgb().to_hex(), self.a)
+        }
+    }
+
+    /// Formats the color as CSS `rgb()`, with the alpha value included if it is not opaque
+    pub fn to_css(&self) -> String {
+        let Self { r, g, b, .. } = self;
+
+        if self.is_opaque() {
+            format!("rgb({r}, {g}, {b})")
+        } else {
+            format!("rgb({r} {g} {b} / {:.3})", self.alpha())
+        }
+    }
+}
+
+impl Display for Srgba8 {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_hex())
+    }
+}
+
+/// Parses `#rgb`, `#rgba`, `#rrggbb` and `#rrggbbaa` hex codes. The leading `#` is optional, and
+/// colors without an alpha channel are opaque.
+impl FromStr for Srgba8 {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let hex = s.trim();
+        let hex = hex.strip_prefix('#').unwrap_or(hex);
+
+        // `from_str_radix` accepts a leading sign, so check the digits up front
+        if !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(());
+        }
+
+        let channel = |idx: usize, len: usize| {
+            let value = u8::from_str_radix(&hex[idx * len..(idx + 1) * len], 16).map_err(|_| ())?;
+
+            Ok(if len == 1 { value * 17 } else { value })
+        };
+
+        match hex.len() {
+            3 => Ok(Self::opaque(channel(0, 1)?, channel(1, 1)?, channel(2, 1)?)),
+            4 => Ok(Self::new(
+                channel(0, 1)?,
+                channel(1, 1)?,
+                channel(2, 1)?,
+                channel(3, 1)?,
+            )),
+            6 => Ok(Self::opaque(channel(0, 2)?, channel(1, 2)?, channel(2, 2)?)),
+            8 => Ok(Self::new(
+                channel(0, 2)?,
+                channel(1, 2)?,
+                channel(2, 2)?,
+                channel(3, 2)?,
+            )),
+            _ => Err(()),
+        }
+    }
+}
+
+impl From<Srgb8> for Srgba8 {
+    fn from(value: Srgb8) -> Self {
+        Self::opaque(value.r, value.g, value.b)
+    }
+}
+
+impl From<(u8, u8, u8)> for Srgba8 {
+    fn from((r, g, b): (u8, u8, u8)) -> Self {
+        Self::opaque(r, g, b)
+    }
+}
+
+impl From<(u8, u8, u8, u8)> for Srgba8 {
+    fn from((r, g, b, a): (u8, u8, u8, u8)) -> Self {
+        Self::new(r, g, b, a)
+    }
+}
+
+impl From<(u8, u8, u8)> for Srgb8 {
+    fn from((r, g, b): (u8, u8, u8)) -> Self {
+        Self { r, g, b }
+    }
+}
+
+impl From<Srgb8> for (u8, u8, u8) {
+    fn from(value: Srgb8) -> Self {
+        (value.r, value.g, value.b)
+    }
+}
+
+impl From<Srgb8> for Srgb {
+    fn from(value: Srgb8) -> Self {
+        Self {
+            r: value.r as f32 / 255.,
+            g: value.g as f32 / 255.,
+            b: value.b as f32 / 255.,
+        }
+    }
+}
+
+/// Channels are clamped to the sRGB gamut and rounded to the nearest value
+impl From<Srgb> for Srgb8 {
+    fn from(value: Srgb) -> Self {
+        let to_u8 = |c: f32| (c.clamp(0., 1.) * 255.).round() as u8;
+
+        Self {
+            r: to_u8(value.r),
+            g: to_u8(value.g),
+            b: to_u8(value.b),
+        }
+    }
+}
+
+/// Implements the constructor and gamut checks of gamma encoded RGB spaces
+macro_rules! rgb_gamut {
+    ($($space:ty),*) => {
+        $(
+            impl $space {
+                pub const fn new(r: f32, g: f32, b: f32) -> Self {
+                    Self { r, g, b }
+                }
+
+                /// True if all channels are within 0..1, allowing for a small rounding error
+                pub fn is_in_gamut(&self) -> bool {
+                    const EPSILON: f32 = 0.0001;
+
+                    [self.r, self.g, self.b]
+                        .iter()
+                        .all(|c| (-EPSILON..=1. + EPSILON).contains(c))
+                }
+
+                pub fn clamp(&self) -> Self {
+                    Self::new(
+                        self.r.clamp(0., 1.),
+                        self.g.clamp(0., 1.),
+                        self.b.clamp(0., 1.),
+                    )
+                }
+            }
+        )*
+    };
+}
+
+rgb_gamut!(Srgb, DisplayP3, Rec2020);
+
+/// The sRGB transfer function, extended to negative values by mirroring
+pub fn srgb_to_linear(c: f32) -> f32 {
+    let abs = c.abs();
+
+    let linear = if abs <= 0.04045 {
+        abs / 12.92
+    } else {
+        ((abs + 0.055) / 1.055).powf(2.4)
+    };
+
+    linear.copysign(c)
+}
+
+/// The inverse of [srgb_to_linear]
+pub fn linear_to_srgb(c: f32) -> f32 {
+    let abs = c.abs();
+
+    let encoded = if abs <= 0.0031308 {
+        abs * 12.92
+    } else {
+        1.055 * abs.powf(1. / 2.4) - 0.055
+    };
+
+    encoded.copysign(c)
+}
+
+impl From<Srgb> for LinearSrgb {
+    fn from(value: Srgb) -> Self {
+        Self {
+            r: srgb_to_linear(value.r),
+            g: srgb_to_linear(value.g),
+            b: srgb_to_linear(value.b),
+        }
+    }
+}
+
+impl From<LinearSrgb> for Srgb {
+    fn from(value: LinearSrgb) -> Self {
+        Self {
+            r: linear_to_srgb(value.r),
+            g: linear_to_srgb(value.g),
+            b: linear_to_srgb(value.b),
+        }
+    }
+}
+
+/// Calculate the hue in degrees of a color, given its max channel value and chroma
+fn rgb_hue(color: Srgb, max: f32, chroma: f32) -> f32 {
+    if chroma == 0. {
+        return 0.;
+    }
+
+    let Srgb { r, g, b } = color;
+
+    let hue = if max == r {
+        ((g - b) / chroma).rem_euclid(6.)
+    } else if max == g {
+        (b - r) / chroma + 2.
+    } else {
+        (r - g) / chroma + 4.
+    };
+
+    (hue * 60.).rem_euclid(360.)
+}
+
+/// Calculate the rgb color with the given hue and chroma, offset by `m` on all channels
+fn hue_chroma_to_rgb(hue: f32, chroma: f32, m: f32) -> Srgb {
+    let h = hue.rem_euclid(360.) / 60.;
+    let x = chroma * (1. - (h.rem_euclid(2.) - 1.).abs());
+
+    let (r, g, b) = match h as u32 {
+        0 => (chroma, x, 0.),
+        1 => (x, chroma, 0.),
+        2 => (0., chroma, x),
+        3 => (0., x, chroma),
+        4 => (x, 0., chroma),
+        _ => (chroma, 0., x),
+    };
+
+    Srgb::new(r + m, g + m, b + m)
+}
+
+impl From<Srgb> for Hsv {
+    fn from(value: Srgb) -> Self {
+        let max = value.r.max(value.g).max(value.b);
+        let min = value.r.min(value.g).min(value.b);
+        let chroma = max - min;
+
+        Self {
+            h: rgb_hue(value, max, chroma),
+            s: if max == 0. { 0. } else { chroma / max },
+            v: max,
+        }
+    }
+}
+
+impl From<Hsv> for Srgb {
+    fn from(value: Hsv) -> Self {
+        let chroma = value.v * value.s;
+
+        hue_chroma_to_rgb(value.h, chroma, value.v - chroma)
+    }
+}
+
+impl From<Srgb> for Hsl {
+    fn from(value: Srgb) -> Self {
+        let max = value.r.max(value.g).max(value.b);
+        let min = value.r.min(value.g).min(value.b);
+        let chroma = max - min;
+        let l = (max + min) / 2.;
+
+        let s = if chroma == 0. {
+            0.
+        } else {
+            chroma / (1. - (2. * l - 1.).abs())
+        };
+
+        Self {
+            h: rgb_hue(value, max, chroma),
+            s,
+            l,
+        }
+    }
+}
+
+impl From<Hsl> for Srgb {
+    fn from(value: Hsl) -> Self {
+        let chroma = (1. - (2. * value.l - 1.).abs()) * value.s;
+
+        hue_chroma_to_rgb(value.h, chroma, value.l - chroma / 2.)
+    }
+}
+
+impl From<LinearSrgb> for Xyz {
+    fn from(value: LinearSrgb) -> Self {
+        let LinearSrgb { r, g, b } = value;
+
+        Self {
+            x: 0.4123908 * r + 0.3575843 * g + 0.1804808 * b,
+            y: 0.212639 * r + 0.7151687 * g + 0.07219232 * b,
+            z: 0.01933082 * r + 0.1191948 * g + 0.9505322 * b,
+        }
+    }
+}
+
+impl From<Xyz> for LinearSrgb {
+    fn from(value: Xyz) -> Self {
+        let Xyz { x, y, z } = value;
+
+        Self {
+            r: 3.24097 * x - 1.537383 * y - 0.4986108 * z,
+            g: -0.9692436 * x + 1.875968 * y + 0.04155506 * z,
+            b: 0.05563008 * x - 0.203977 * y + 1.056972 * z,
+        }
+    }
+}
+
+impl From<Xyz> for DisplayP3 {
+    fn from(value: Xyz) -> Self {
+        let Xyz { x, y, z } = value;
+
+        Self {
+            r: linear_to_srgb(2.493497 * x - 0.9313836 * y - 0.4027108 * z),
+            g: linear_to_srgb(-0.829489 * x + 1.762664 * y + 0.02362469 * z),
+            b: linear_to_srgb(0.03584583 * x - 0.07617239 * y + 0.9568845 * z),
+        }
+    }
+}
+
+impl From<DisplayP3> for Xyz {
+    fn from(value: DisplayP3) -> Self {
+        let r = srgb_to_linear(value.r);
+        let g = srgb_to_linear(value.g);
+        let b = srgb_to_linear(value.b);
+
+        Self {
+            x: 0.4865709 * r + 0.2656677 * g + 0.1982173 * b,
+            y: 0.2289746 * r + 0.6917385 * g + 0.07928691 * b,
+            z: 0.04511338 * g + 1.043944 * b,
+        }
+    }
+}
+
+const REC2020_ALPHA: f32 = 1.0992968;
+const REC2020_BETA: f32 = 0.01805397;
+
+/// The BT.2020 transfer function, extended to negative values by mirroring
+fn rec2020_to_linear(c: f32) -> f32 {
+    let abs = c.abs();
+
+    let linear = if abs < REC2020_BETA * 4.5 {
+        abs / 4.5
+    } else {
+        ((abs + REC2020_ALPHA - 1.) / REC2020_ALPHA).powf(1. / 0.45)
+    };
+
+    linear.copysign(c)
+}
+
+/// The inverse of [rec2020_to_linear]
+fn linear_to_rec2020(c: f32) -> f32 {
+    let abs = c.abs();
+
+    let encoded = if abs < REC2020_BETA {
+        abs * 4.5
+    } else {
+        REC2020_ALPHA * abs.powf(0.45) - (REC2020_ALPHA - 1.)
+    };
+
+    encoded.copysign(c)
+}
+
+impl From<Xyz> for Rec2020 {
+    fn from(value: Xyz) -> Self {
+        let Xyz { x, y, z } = value;
+
+        Self {
+            r: linear_to_rec2020(1.716651 * x - 0.3556708 * y - 0.2533663 * z),
+            g: linear_to_rec2020(-0.6666844 * x + 1.616481 * y + 0.01576855 * z),
+            b: linear_to_rec2020(0.01763986 * x - 0.04277061 * y + 0.9421031 * z),
+        }
+    }
+}
+
+impl From<Rec2020> for Xyz {
+    fn from(value: Rec2020) -> Self {
+        let r = rec2020_to_linear(value.r);
+        let g = rec2020_to_linear(value.g);
+        let b = rec2020_to_linear(value.b);
+
+        Self {
+            x: 0.636958 * r + 0.1446169 * g + 0.168881 * b,
+            y: 0.2627002 * r + 0.6779981 * g + 0.05930172 * b,
+            z: 0.02807269 * g + 1.060985 * b,
+        }
+    }
+}
+
+impl From<Xyz> for Lab {
+    fn from(value: Xyz) -> Self {
+        let f = |t: f32| {
+            if t > LAB_EPSILON {
+                t.cbrt()
+            } else {
+                (LAB_KAPPA * t + 16.) / 116.
+            }
+        };
+
+        let fx = f(value.x / D65_WHITE.x);
+        let fy = f(value.y / D65_WHITE.y);
+        let fz = f(value.z / D65_WHITE.z);
+
+        Self {
+            l: 116. * fy - 16.,
+            a: 500. * (fx - fy),
+            b: 200. * (fy - fz),
+        }
+    }
+}
+
+impl From<Lab> for Xyz {
+    fn from(value: Lab) -> Self {
+        let fy = (value.l + 16.) / 116.;
+        let fx = fy + value.a / 500.;
+        let fz = fy - value.b / 200.;
+
+        let f_inv = |t: f32| {
+            if t.powi(3) > LAB_EPSILON {
+                t.powi(3)
+            } else {
+                (116. * t - 16.) / LAB_KAPPA
+            }
+        };
+
+        let y = if value.l > LAB_KAPPA * LAB_EPSILON {
+            fy.powi(3)
+        } else {
+            value.l / LAB_KAPPA
+        };
+
+        Self {
+            x: f_inv(fx) * D65_WHITE.x,
+            y: y * D65_WHITE.y,
+            z: f_inv(fz) * D65_WHITE.z,
+        }
+    }
+}
+
+/// Convert rectangular (a, b) coordinates to (chroma, hue in degrees)
+fn to_polar(a: f32, b: f32) -> (f32, f32) {
+    let chroma = (a * a + b * b).sqrt();
+    let hue = if chroma < 1e-6 {
+        0.
+    } else {
+        b.atan2(a).to_degrees().rem_euclid(360.)
+    };
+
+    (chroma, hue)
+}
+
+/// Convert (chroma, hue in degrees) to rectangular (a, b) coordinates
+fn to_rectangular(chroma: f32, hue: f32) -> (f32, f32) {
+    let (sin, cos) = hue.to_radians().sin_cos();
+
+    (chroma * cos, chroma * sin)
+}
+
+impl From<Lab> for Lch {
+    fn from(value: Lab) -> Self {
+        let (c, h) = to_polar(value.a, value.b);
+
+        Self { l: value.l, c, h }
+    }
+}
+
+impl From<Lch> for Lab {
+    fn from(value: Lch) -> Self {
+        let (a, b) = to_rectangular(value.c, value.h);
+
+        Self { l: value.l, a, b }
+    }
+}
+
+impl From<LinearSrgb> for Oklab {
+    fn from(value: LinearSrgb) -> Self {
+        let LinearSrgb { r, g, b } = value;
+
+        let l = (0.4122215 * r + 0.5363325 * g + 0.05144599 * b).cbrt();
+        let m = (0.2119035 * r + 0.6806996 * g + 0.107397 * b).cbrt();
+        let s = (0.08830246 * r + 0.2817188 * g + 0.6299787 * b).cbrt();
+
+        Self {
+            l: 0.2104543 * l + 0.7936178 * m - 0.00407205 * s,
+            a: 1.977998 * l - 2.428592 * m + 0.4505937 * s,
+            b: 0.02590404 * l + 0.7827718 * m - 0.8086758 * s,
+        }
+    }
+}
+
+impl From<Oklab> for LinearSrgb {
+    fn from(value: Oklab) -> Self {
+        let Oklab { l, a, b } = value;
+
+        let l_ = (l + 0.3963378 * a + 0.2158038 * b).powi(3);
+        let m_ = (l - 0.1055613 * a - 0.06385417 * b).powi(3);
+        let s_ = (l - 0.08948418 * a - 1.291486 * b).powi(3);
+
+        Self {
+            r: 4.076742 * l_ - 3.307712 * m_ + 0.2309699 * s_,
+            g: -1.268438 * l_ + 2.609757 * m_ - 0.3413194 * s_,
+            b: -0.00419609 * l_ - 0.7034186 * m_ + 1.707615 * s_,
+        }
+    }
+}
+
+impl From<Oklab> for Oklch {
+    fn from(value: Oklab) -> Self {
+        let (c, h) = to_polar(value.a, value.b);
+
+        Self { l: value.l, c, h }
+    }
+}
+
+impl From<Oklch> for Oklab {
+    fn from(value: Oklch) -> Self {
+        let (a, b) = to_rectangular(value.c, value.h);
+
+        Self { l: value.l, a, b }
+    }
+}
+
+impl From<Xyz> for Luv {
+    fn from(value: Xyz) -> Self {
+        let y = value.y / D65_WHITE.y;
+
+        let l = if y > LAB_EPSILON {
+            116. * y.cbrt() - 16.
+        } else {
+            LAB_KAPPA * y
+        };
+
+        let divisor = value.x + 15. * value.y + 3. * value.z;
+
+        if l == 0. || divisor == 0. {
+            return Self::default();
+        }
+
+        let u_prime = 4. * value.x / divisor;
+        let v_prime = 9. * value.y / divisor;
+
+        Self {
+            l,
+            u: 13. * l * (u_prime - LUV_WHITE_U),
+            v: 13. * l * (v_prime - LUV_WHITE_V),
+        }
+    }
+}
+
+impl From<Luv> for Xyz {
+    fn from(value: Luv) -> Self {
+        if value.l == 0. {
+            return Self::default();
+        }
+
+        let u_prime = value.u / (13. * value.l) + LUV_WHITE_U;
+        let v_prime = value.v / (13. * value.l) + LUV_WHITE_V;
+
+        let y = if value.l > LAB_KAPPA * LAB_EPSILON {
+            ((value.l + 16.) / 116.).powi(3)
+        } else {
+            value.l / LAB_KAPPA
+        } * D65_WHITE.y;
+
+        let x = y * 9. * u_prime / (4. * v_prime);
+        let z = y * (12. - 3. * u_prime - 20. * v_prime) / (4. * v_prime);
+
+        Self { x, y, z }
+    }
+}
+
+impl From<Luv> for Lchuv {
+    fn from(value: Luv) -> Self {
+        let (c, h) = to_polar(value.u, value.v);
+
+        Self { l: value.l, c, h }
+    }
+}
+
+impl From<Lchuv> for Luv {
+    fn from(value: Lchuv) -> Self {
+        let (u, v) = to_rectangular(value.c, value.h);
+
+        Self { l: value.l, u, v }
+    }
+}
+
+/// Lines bounding the sRGB gamut in the (u, v) plane at the given lightness,
+/// as (slope, intercept) pairs
+fn hsluv_gamut_bounds(l: f32) -> [(f32, f32); 6] {
+    // The XYZ to linear sRGB matrix, see `From<Xyz> for LinearSrgb`
+    const M: [[f32; 3]; 3] = [
+        [3.24097, -1.537383, -0.4986108],
+        [-0.9692436, 1.875968, 0.04155506],
+        [0.05563008, -0.203977, 1.056972],
+    ];
+
+    let sub1 = (l + 16.).powi(3) / 1560896.;
+    let sub2 = if sub1 > LAB_EPSILON {
+        sub1
+    } else {
+        l / LAB_KAPPA
+    };
+
+    let mut bounds = [(0., 0.); 6];
+
+    for (channel, [m1, m2, m3]) in M.into_iter().enumerate() {
+        for t in 0..2 {
+            let t = t as f32;
+            let top1 = (284517. * m1 - 94839. * m3) * sub2;
+            let top2 = (838422. * m3 + 769860. * m2 + 731718. * m1) * l * sub2 - 769860. * t * l;
+            let bottom = (632260. * m3 - 126452. * m2) * sub2 + 126452. * t;
+
+            bounds[channel * 2 + t as usize] = (top1 / bottom, top2 / bottom);
+        }
+    }
+
+    bounds
+}
+
+/// The largest chroma in the sRGB gamut for the given lightness and hue
+fn hsluv_max_chroma(l: f32, h: f32) -> f32 {
+    let (sin, cos) = h.to_radians().sin_cos();
+
+    hsluv_gamut_bounds(l)
+        .into_iter()
+        .map(|(slope, intercept)| intercept / (sin - slope * cos))
+        .filter(|length| *length >= 0.)
+        .fold(f32::INFINITY, f32::min)
+}
+
+impl From<Lchuv> for Hsluv {
+    fn from(value: Lchuv) -> Self {
+        let s = if value.l > 99.9999 || value.l < 0.0001 {
+            0.
+        } else {
+            value.c / hsluv_max_chroma(value.l, value.h) * 100.
+        };
+
+        Self {
+            h: value.h,
+            s,
+            l: value.l,
+        }
+    }
+}
+
+impl From<Hsluv> for Lchuv {
+    fn from(value: Hsluv) -> Self {
+        let c = if value.l > 99.9999 || value.l < 0.0001 {
+            0.
+        } else {
+            hsluv_max_chroma(value.l, value.h) * value.s / 100.
+        };
+
+        Self {
+            l: value.l,
+            c,
+            h: value.h,
+        }
+    }
+}
+
+/// Implements conversion to and from [Srgb] for a color space, by way of an intermediate space
+macro_rules! srgb_via {
+    ($space:ty, $via:ty) => {
+        impl From<Srgb> for $space {
+            fn from(value: Srgb) -> Self {
+                <$via>::from(value).into()
+            }
+        }
+
+        impl From<$space> for Srgb {
+            fn from(value: $space) -> Self {
+                <$via>::from(value).into()
+            }
+        }
+    };
+}
+
+srgb_via!(Xyz, LinearSrgb);
+srgb_via!(Lab, Xyz);
+srgb_via!(Lch, Lab);
+srgb_via!(Oklab, LinearSrgb);
+srgb_via!(Oklch, Oklab);
+srgb_via!(Luv, Xyz);
+srgb_via!(Lchuv, Luv);
+srgb_via!(Hsluv, Lchuv);
+srgb_via!(DisplayP3, Xyz);
+srgb_via!(Rec2020, Xyz);
+
+/// Implements conversion between [Srgb8] and a color space, by way of [Srgb]
+macro_rules! srgb8_via_srgb {
+    ($($space:ty),*) => {
+        $(
+            impl From<Srgb8> for $space {
+                fn from(value: Srgb8) -> Self {
+                    Srgb::from(value).into()
+                }
+            }
+
+            impl From<$space> for Srgb8 {
+                fn from(value: $space) -> Self {
+                    Srgb::from(value).into()
+                }
+            }
+        )*
+    };
+}
+
+srgb8_via_srgb!(
+    LinearSrgb, Hsv, Hsl, Xyz, Lab, Lch, Oklab, Oklch, Luv, Lchuv, Hsluv, DisplayP3, Rec2020
+);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EPSILON: f32 = 0.001;
+
+    fn assert_close(actual: [f32; 3], expected: [f32; 3], epsilon: f32) {
+        for (a, e) in actual.iter().zip(expected.iter()) {
+            assert!(
+                (a - e).abs() < epsilon,
+                "expected {expected:?}, got {actual:?}"
+            );
+        }
+    }
+
+    fn all_srgb8_samples() -> impl Iterator<Item = Srgb8> {
+        (0..=255u8)
+            .step_by(15)
+            .flat_map(|r| (0..=255u8).step_by(15).map(move |g| (r, g)))
+            .flat_map(|(r, g)| (0..=255u8).step_by(15).map(move |b| Srgb8::new(r, g, b)))
+    }
+
+    #[test]
+    fn test_hex() {
+        let color = Srgb8::new(0x0a, 0xbc, 0xff);
+
+        assert_eq!(color.to_hex(), "#0abcff");
+        assert_eq!("#0abcff".parse(), Ok(color));
+        assert_eq!("0ABCFF".parse(), Ok(color));
+        assert_eq!("#0abcff80".parse(), Ok(color));
+        assert_eq!("#fa0".parse(), Ok(Srgb8::new(0xff, 0xaa, 0x00)));
+        assert_eq!("#12345".parse::<Srgb8>(), Err(()));
+        assert_eq!("#zzzzzz".parse::<Srgb8>(), Err(()));
+        assert_eq!("+fffff".parse::<Srgb8>(), Err(()));
+        assert_eq!("#+f+f+f".parse::<Srgb8>(), Err(()));
+        assert_eq!("#aébbcc".parse::<Srgb8>(), Err(()));
+    }
+
+    #[test]
+    fn test_hex_alpha() {
+        let color = Srgba8::new(0x0a, 0xbc, 0xff, 0x80);
+
+        assert_eq!(color.to_hex(), "#0abcff80");
+        assert_eq!(color.to_css(), "rgb(10 188 255 / 0.502)");
+        assert_eq!("#0abcff80".parse(), Ok(color));
+        assert_eq!("#fa08".parse(), Ok(Srgba8::new(0xff, 0xaa, 0x00, 0x88)));
+        assert_eq!("#0abcff".parse(), Ok(color.with_alpha(255)));
+        assert_eq!("#+fff".parse::<Srgba8>(), Err(()));
+        assert_eq!("+fffffff".parse::<Srgba8>(), Err(()));
+        assert_eq!(color.with_alpha(255).to_hex(), "#0abcff");
+        assert_eq!(color.with_alpha(255).to_css(), "rgb(10, 188, 255)");
+    }
+
+    #[test]
+    fn test_alpha_compositing() {
+        let white = Srgb8::new(255, 255, 255);
+        let color = Srgba8::new(0, 100, 200, 255);
+
+        assert_eq!(color.over(white), color.rgb());
+        assert_eq!(color.with_alpha(0).over(white), white);
+        assert_eq!(
+            Srgba8::new(0, 0, 0, 128).over(white),
+            Srgb8::new(127, 127, 127)
+        );
+    }
+
+    #[test]
+    fn test_hsv_reference() {
+        let hsv = Hsv::from(Srgb8::new(255, 0, 0));
+        assert_close([hsv.h, hsv.s, hsv.v], [0., 1., 1.], EPSILON);
+
+        let hsv = Hsv::from(Srgb8::new(0, 0, 255));
+        assert_close([hsv.h, hsv.s, hsv.v], [240., 1., 1.], EPSILON);
+
+        assert_eq!(
+            Srgb8::from(Hsv {
+                h: 120.,
+                s: 1.,
+                v: 0.5
+            }),
+            Srgb8::new(0, 128, 0)
+        );
+    }
+
+    #[test]
+    fn test_hsl_reference() {
+        let hsl = Hsl::from(Srgb8::new(0, 0, 255));
+        assert_close([hsl.h, hsl.s, hsl.l], [240., 1., 0.5], EPSILON);
+
+        assert_eq!(
+            Srgb8::from(Hsl {
+                h: 60.,
+                s: 1.,
+                l: 0.5
+            }),
+            Srgb8::new(255, 255, 0)
+        );
+    }
+
+    #[test]
+    fn test_lab_reference() {
+        let lab = Lab::from(Srgb8::new(255, 0, 0));
+        assert_close([lab.l, lab.a, lab.b], [53.24, 80.09, 67.20], 0.05);
+
+        let lab = Lab::from(Srgb8::new(255, 255, 255));
+        assert_close([lab.l, lab.a, lab.b], [100., 0., 0.], 0.01);
+
+        let lch = Lch::from(Srgb8::new(0, 0, 255));
+        assert_close([lch.l, lch.c, lch.h], [32.30, 133.81, 306.29], 0.05);
+    }
+
+    #[test]
+    fn test_oklab_reference() {
+        let oklab = Oklab::from(Srgb8::new(255, 0, 0));
+        assert_close(
+            [oklab.l, oklab.a, oklab.b],
+            [0.62796, 0.22486, 0.12585],
+            EPSILON,
+        );
+
+        let oklab = Oklab::from(Srgb8::new(255, 255, 255));
+        assert_close([oklab.l, oklab.a, oklab.b], [1., 0., 0.], EPSILON);
+
+        let oklch = Oklch::from(Srgb8::new(0, 0, 255));
+        assert_close(
+            [oklch.l, oklch.c, oklch.h],
+            [0.45201, 0.31321, 264.05],
+            0.01,
+        );
+    }
+
+    #[test]
+    fn test_luv_reference() {
+        let luv = Luv::from(Srgb8::new(255, 0, 0));
+        assert_close([luv.l, luv.u, luv.v], [53.24, 175.01, 37.76], 0.05);
+    }
+
+    /// Reference values from the HSLuv test snapshot
+    #[test]
+    fn test_hsluv_reference() {
+        let hsluv = Hsluv::from(Srgb8::new(255, 0, 0));
+        assert_close([hsluv.h, hsluv.s, hsluv.l], [12.177, 100., 53.237], 0.05);
+
+        let hsluv = Hsluv::from(Srgb8::new(0, 0, 255));
+        assert_close([hsluv.h, hsluv.s, hsluv.l], [265.874, 100., 32.301], 0.05);
+
+        assert_eq!(
+            Srgb8::from(Hsluv {
+                h: 0.,
+                s: 0.,
+                l: 100.
+            }),
+            Srgb8::new(255, 255, 255)
+        );
+    }
+
+    #[test]
+    fn test_hsluv_is_always_in_gamut() {
+        for h in (0..360).step_by(10) {
+            for l in (1..100).step_by(7) {
+                let srgb = Srgb::from(Hsluv {
+                    h: h as f32,
+                    s: 100.,
+                    l: l as f32,
+                });
+
+                assert!(srgb.is_in_gamut(), "{srgb:?} is out of gamut");
+            }
+        }
+    }
+
+    /// Reference values from CSS Color 4, converting `color(srgb 1 0 0)`
+    #[test]
+    fn test_wide_gamut_reference() {
+        let red = Srgb::new(1., 0., 0.);
+
+        let p3 = DisplayP3::from(red);
+        assert_close([p3.r, p3.g, p3.b], [0.9175, 0.2003, 0.1386], EPSILON);
+        assert!(p3.is_in_gamut());
+
+        let rec2020 = Rec2020::from(red);
+        assert_close(
+            [rec2020.r, rec2020.g, rec2020.b],
+            [0.7919, 0.2307, 0.0739],
+            EPSILON,
+        );
+        assert!(rec2020.is_in_gamut());
+
+        let p3_red = Srgb::from(DisplayP3::new(1., 0., 0.));
+        assert!(!p3_red.is_in_gamut());
+
+        let white = DisplayP3::from(Srgb::new(1., 1., 1.));
+        assert_close([white.r, white.g, white.b], [1., 1., 1.], EPSILON);
+    }
+
+    #[test]
+    fn test_xyz_reference() {
+        let xyz = Xyz::from(Srgb::new(1., 1., 1.));
+        assert_close(
+            [xyz.x, xyz.y, xyz.z],
+            [D65_WHITE.x, D65_WHITE.y, D65_WHITE.z],
+            EPSILON,
+        );
+    }
+
+    #[test]
+    fn test_srgb8_round_trips() {
+        for color in all_srgb8_samples() {
+            assert_eq!(Srgb8::from(LinearSrgb::from(color)), color);
+            assert_eq!(Srgb8::from(Hsv::from(color)), color);
+            assert_eq!(Srgb8::from(Hsl::from(color)), color);
+            assert_eq!(Srgb8::from(Xyz::from(color)), color);
+            assert_eq!(Srgb8::from(Lab::from(color)), color);
+            assert_eq!(Srgb8::from(Lch::from(color)), color);
+            assert_eq!(Srgb8::from(Oklab::from(color)), color);
+            assert_eq!(Srgb8::from(Oklch::from(color)), color);
+            assert_eq!(Srgb8::from(Luv::from(color)), color);
+            assert_eq!(Srgb8::from(Lchuv::from(color)), color);
+            assert_eq!(Srgb8::from(Hsluv::from(color)), color);
+            assert_eq!(Srgb8::from(DisplayP3::from(color)), color);
+            assert_eq!(Srgb8::from(Rec2020::from(color)), color);
+        }
+    }
+
+    #[test]
+    fn test_out_of_gamut_survives_round_trip() {
+        let oklch = Oklch {
+            l: 0.7,
+            c: 0.35,
+            h: 150.,
+        };
+
+        let srgb = Srgb::from(oklch);
+        assert!(!srgb.is_in_gamut());
+
+        let back = Oklch::from(srgb);
+        assert_close(
+            [back.l, back.c, back.h],
+            [oklch.l, oklch.c, oklch.h],
+            EPSILON,
+        );
+    }
+}
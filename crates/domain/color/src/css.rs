@@ -0,0 +1,144 @@
+//!
+//!  # CSS
+//!
+//! Formatting of colors as CSS values, in the syntax of choice.
+//!
+//! https://www.w3.org/TR/css-color-4/
+//!
+
+use crate::contrast::RGBA;
+use crate::conversion::{Hsl, Oklch};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// The syntax colors are written in by stylesheet exports
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CssColorSyntax {
+    /// `#rrggbb`, or `#rrggbbaa` for translucent colors
+    #[default]
+    Hex,
+    /// `rgb(239 246 255)`
+    Rgb,
+    /// `hsl(214 100% 96.9%)`
+    Hsl,
+    /// `oklch(97.05% 0.0142 254.6)`
+    Oklch,
+}
+
+impl CssColorSyntax {
+    pub const ALL: [CssColorSyntax; 4] = [
+        CssColorSyntax::Hex,
+        CssColorSyntax::Rgb,
+        CssColorSyntax::Hsl,
+        CssColorSyntax::Oklch,
+    ];
+
+    /// Format the color in this syntax. The alpha is included for translucent colors
+    pub fn format(&self, color: RGBA) -> String {
+        let alpha = if color.is_opaque() {
+            String::new()
+        } else {
+            format!(" / {}", number(color.alpha(), 3))
+        };
+
+        match self {
+            CssColorSyntax::Hex => color.to_hex(),
+            CssColorSyntax::Rgb => format!("rgb({} {} {}{alpha})", color.r, color.g, color.b),
+            CssColorSyntax::Hsl => {
+                let Hsl { h, s, l } = color.rgb().into();
+
+                format!(
+                    "hsl({} {}% {}%{alpha})",
+                    number(h, 1),
+                    number(s * 100., 1),
+                    number(l * 100., 1)
+                )
+            }
+            CssColorSyntax::Oklch => {
+                let Oklch { l, c, h } = color.rgb().into();
+
+                format!(
+                    "oklch({}% {} {}{alpha})",
+                    number(l * 100., 2),
+                    number(c, 4),
+                    number(h, 1)
+                )
+            }
+        }
+    }
+}
+
+/// A number with at most `decimals` decimals, without trailing zeros
+fn number(value: f32, decimals: usize) -> String {
+    let formatted = format!("{value:.decimals$}");
+
+    let trimmed = if formatted.contains('.') {
+        formatted.trim_end_matches('0').trim_end_matches('.')
+    } else {
+        &formatted
+    };
+
+    // Tiny negative values, e.g. the lightness of black, round to -0
+    match trimmed {
+        "-0" => "0".to_string(),
+        trimmed => trimmed.to_string(),
+    }
+}
+
+impl Display for CssColorSyntax {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CssColorSyntax::Hex => write!(f, "Hex"),
+            CssColorSyntax::Rgb => write!(f, "rgb()"),
+            CssColorSyntax::Hsl => write!(f, "hsl()"),
+            CssColorSyntax::Oklch => write!(f, "oklch()"),
+        }
+    }
+}
+
+impl FromStr for CssColorSyntax {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|syntax| syntax.to_string() == s)
+            .ok_or(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_format() {
+        let color = RGBA::opaque(0xef, 0xf6, 0xff);
+
+        assert_eq!(CssColorSyntax::Hex.format(color), "#eff6ff");
+        assert_eq!(CssColorSyntax::Rgb.format(color), "rgb(239 246 255)");
+        assert_eq!(CssColorSyntax::Hsl.format(color), "hsl(213.8 100% 96.9%)");
+        assert_eq!(
+            CssColorSyntax::Oklch.format(color),
+            "oklch(97.05% 0.0142 254.6)"
+        );
+    }
+
+    #[test]
+    fn test_format_translucent() {
+        let color = RGBA::new(0, 0, 0, 128);
+
+        assert_eq!(CssColorSyntax::Hex.format(color), "#00000080");
+        assert_eq!(CssColorSyntax::Rgb.format(color), "rgb(0 0 0 / 0.502)");
+        assert_eq!(CssColorSyntax::Hsl.format(color), "hsl(0 0% 0% / 0.502)");
+        assert_eq!(CssColorSyntax::Oklch.format(color), "oklch(0% 0 0 / 0.502)");
+    }
+
+    #[test]
+    fn test_from_str() {
+        for syntax in CssColorSyntax::ALL {
+            assert_eq!(syntax.to_string().parse(), Ok(syntax));
+        }
+    }
+}
@@ -0,0 +1,202 @@
+//!
+//!  # CVD
+//!
+//! Simulation of color vision deficiencies (CVD), to check how a palette appears to people with
+//! color blindness.
+//!
+//! The dichromacies use the matrices of Machado, Oliveira and Fernandes (2009) at full severity,
+//! applied to linear sRGB. Lower severities, approximating the anomalous trichromacies,
+//! interpolate between normal vision and the full deficiency.
+//!
+//! https://www.inf.ufrgs.br/~oliveira/pubs_files/CVD_Simulation/CVD_Simulation.html
+//!
+
+use crate::contrast::RGBA;
+use crate::conversion::{LinearSrgb, Srgb, Srgb8, Srgba8};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+const PROTANOPIA: [[f32; 3]; 3] = [
+    [0.152286, 1.052583, -0.204868],
+    [0.114503, 0.786281, 0.099216],
+    [-0.003882, -0.048116, 1.051998],
+];
+
+const DEUTERANOPIA: [[f32; 3]; 3] = [
+    [0.367322, 0.860646, -0.227968],
+    [0.280085, 0.672501, 0.047413],
+    [-0.011820, 0.042940, 0.968881],
+];
+
+const TRITANOPIA: [[f32; 3]; 3] = [
+    [1.255528, -0.076749, -0.178779],
+    [-0.078411, 0.930809, 0.147602],
+    [0.004733, 0.691367, 0.303900],
+];
+
+/// Luminance weights of linear sRGB
+const ACHROMATOPSIA: [[f32; 3]; 3] = [[0.2126, 0.7152, 0.0722]; 3];
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ColorVisionDeficiency {
+    /// Missing or anomalous L (red) cones
+    Protanopia,
+    /// Missing or anomalous M (green) cones
+    Deuteranopia,
+    /// Missing or anomalous S (blue) cones
+    Tritanopia,
+    /// No color vision, only luminance
+    Achromatopsia,
+}
+
+impl ColorVisionDeficiency {
+    pub const ALL: [ColorVisionDeficiency; 4] = [
+        ColorVisionDeficiency::Protanopia,
+        ColorVisionDeficiency::Deuteranopia,
+        ColorVisionDeficiency::Tritanopia,
+        ColorVisionDeficiency::Achromatopsia,
+    ];
+
+    fn matrix(&self) -> [[f32; 3]; 3] {
+        match self {
+            ColorVisionDeficiency::Protanopia => PROTANOPIA,
+            ColorVisionDeficiency::Deuteranopia => DEUTERANOPIA,
+            ColorVisionDeficiency::Tritanopia => TRITANOPIA,
+            ColorVisionDeficiency::Achromatopsia => ACHROMATOPSIA,
+        }
+    }
+
+    /// Simulate how a linear sRGB color is perceived. `severity` is in the range 0..1, where 0
+    /// is normal vision and 1 is the full deficiency
+    pub fn simulate_linear(&self, color: LinearSrgb, severity: f32) -> LinearSrgb {
+        let severity = severity.clamp(0., 1.);
+        let [r, g, b] = self
+            .matrix()
+            .map(|[mr, mg, mb]| mr * color.r + mg * color.g + mb * color.b);
+
+        LinearSrgb {
+            r: color.r + (r - color.r) * severity,
+            g: color.g + (g - color.g) * severity,
+            b: color.b + (b - color.b) * severity,
+        }
+    }
+
+    /// Simulate how a color is perceived, see [ColorVisionDeficiency::simulate_linear]. The
+    /// alpha channel is kept as is
+    pub fn simulate(&self, color: RGBA, severity: f32) -> RGBA {
+        let linear = LinearSrgb::from(Srgb::from(color.rgb()));
+        let simulated = Srgb8::from(Srgb::from(self.simulate_linear(linear, severity)));
+
+        Srgba8::from(simulated).with_alpha(color.a)
+    }
+}
+
+impl Display for ColorVisionDeficiency {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ColorVisionDeficiency::Protanopia => write!(f, "Protanopia"),
+            ColorVisionDeficiency::Deuteranopia => write!(f, "Deuteranopia"),
+            ColorVisionDeficiency::Tritanopia => write!(f, "Tritanopia"),
+            ColorVisionDeficiency::Achromatopsia => write!(f, "Achromatopsia"),
+        }
+    }
+}
+
+impl FromStr for ColorVisionDeficiency {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|deficiency| deficiency.to_string() == s)
+            .ok_or(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::contrast::relative_luminance;
+    use crate::difference::DifferenceMetric;
+
+    #[test]
+    fn test_zero_severity_is_normal_vision() {
+        for deficiency in ColorVisionDeficiency::ALL {
+            for color in [
+                RGBA::opaque(255, 0, 0),
+                RGBA::opaque(12, 200, 99),
+                RGBA::new(0, 0, 255, 100),
+            ] {
+                assert_eq!(deficiency.simulate(color, 0.), color);
+            }
+        }
+    }
+
+    #[test]
+    fn test_neutral_colors_are_unchanged() {
+        for deficiency in ColorVisionDeficiency::ALL {
+            for grey in [0, 60, 128, 255] {
+                let simulated = deficiency.simulate(RGBA::opaque(grey, grey, grey), 1.);
+
+                assert!(
+                    simulated.r.abs_diff(grey) <= 1
+                        && simulated.g.abs_diff(grey) <= 1
+                        && simulated.b.abs_diff(grey) <= 1,
+                    "{deficiency} changed grey {grey} into {simulated:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_achromatopsia_keeps_luminance() {
+        let color = RGBA::opaque(200, 40, 120);
+        let simulated = ColorVisionDeficiency::Achromatopsia.simulate(color, 1.);
+
+        assert!(simulated.r == simulated.g && simulated.g == simulated.b);
+        assert!(
+            (relative_luminance(simulated.rgb()) - relative_luminance(color.rgb())).abs() < 0.01
+        );
+    }
+
+    /// Red and green are confused with protanopia and deuteranopia, blue and green with
+    /// tritanopia
+    #[test]
+    fn test_confusion_colors_become_similar() {
+        let metric = DifferenceMetric::Ciede2000;
+        let cases = [
+            (
+                ColorVisionDeficiency::Protanopia,
+                RGBA::opaque(200, 60, 40),
+                RGBA::opaque(110, 110, 30),
+            ),
+            (
+                ColorVisionDeficiency::Deuteranopia,
+                RGBA::opaque(200, 60, 40),
+                RGBA::opaque(110, 110, 30),
+            ),
+            (
+                ColorVisionDeficiency::Tritanopia,
+                RGBA::opaque(40, 120, 220),
+                RGBA::opaque(40, 160, 140),
+            ),
+        ];
+
+        for (deficiency, color_a, color_b) in cases {
+            let normal = metric.difference(color_a, color_b);
+            let partial = metric.difference(
+                deficiency.simulate(color_a, 0.5),
+                deficiency.simulate(color_b, 0.5),
+            );
+            let full = metric.difference(
+                deficiency.simulate(color_a, 1.),
+                deficiency.simulate(color_b, 1.),
+            );
+
+            assert!(
+                full < partial && partial < normal,
+                "{deficiency}: {normal} -> {partial} -> {full}"
+            );
+        }
+    }
+}
@@ -0,0 +1,418 @@
+//!
+//!  # Difference
+//!
+//! Color difference metrics, answering how distinguishable two colors are. Where contrast only
+//! looks at luminance, these also take hue and chroma into account.
+//!
+//! The CIE metrics (ΔE76, ΔE94 and ΔE2000) work on [Lab], ΔEok is the Euclidean distance in
+//! [Oklab]. Note that ΔEok is on a different scale, a difference of 1 is the distance between
+//! black and white.
+//!
+//! http://www2.ece.rochester.edu/~gsharma/ciede2000/
+//!
+
+use crate::contrast::RGBA;
+use crate::conversion::{Lab, Oklab};
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// CIE76, the Euclidean distance in CIELAB
+pub fn delta_e_76(lhs: Lab, rhs: Lab) -> f32 {
+    ((lhs.l - rhs.l).powi(2) + (lhs.a - rhs.a).powi(2) + (lhs.b - rhs.b).powi(2)).sqrt()
+}
+
+/// CIE94, with the graphic arts weights. Note that CIE94 is not symmetric, `reference` is the
+/// color the chroma weights are based on
+pub fn delta_e_94(reference: Lab, sample: Lab) -> f32 {
+    const K1: f32 = 0.045;
+    const K2: f32 = 0.015;
+
+    let c1 = reference.a.hypot(reference.b);
+    let c2 = sample.a.hypot(sample.b);
+
+    let delta_l = reference.l - sample.l;
+    let delta_c = c1 - c2;
+    let delta_h_squared = ((reference.a - sample.a).powi(2) + (reference.b - sample.b).powi(2)
+        - delta_c.powi(2))
+    .max(0.);
+
+    let s_c = 1. + K1 * c1;
+    let s_h = 1. + K2 * c1;
+
+    (delta_l.powi(2) + (delta_c / s_c).powi(2) + delta_h_squared / s_h.powi(2)).sqrt()
+}
+
+/// CIEDE2000, with unit weights. Calculated in f64, since the hue terms are sensitive to
+/// rounding for nearly neutral colors
+pub fn delta_e_2000(lhs: Lab, rhs: Lab) -> f32 {
+    let (l1, a1, b1) = (lhs.l as f64, lhs.a as f64, lhs.b as f64);
+    let (l2, a2, b2) = (rhs.l as f64, rhs.a as f64, rhs.b as f64);
+
+    let c_mean = (a1.hypot(b1) + a2.hypot(b2)) / 2.;
+    let c_mean_7 = c_mean.powi(7);
+    let g = 0.5 * (1. - (c_mean_7 / (c_mean_7 + 25f64.powi(7))).sqrt());
+
+    let a1 = a1 * (1. + g);
+    let a2 = a2 * (1. + g);
+
+    let c1 = a1.hypot(b1);
+    let c2 = a2.hypot(b2);
+
+    let hue = |a: f64, b: f64| {
+        if a == 0. && b == 0. {
+            0.
+        } else {
+            b.atan2(a).to_degrees().rem_euclid(360.)
+        }
+    };
+
+    let h1 = hue(a1, b1);
+    let h2 = hue(a2, b2);
+
+    let delta_l = l2 - l1;
+    let delta_c = c2 - c1;
+
+    let delta_h = if c1 * c2 == 0. {
+        0.
+    } else if (h2 - h1).abs() <= 180. {
+        h2 - h1
+    } else if h2 - h1 > 180. {
+        h2 - h1 - 360.
+    } else {
+        h2 - h1 + 360.
+    };
+
+    let delta_h = 2. * (c1 * c2).sqrt() * (delta_h / 2.).to_radians().sin();
+
+    let l_mean = (l1 + l2) / 2.;
+    let c_mean = (c1 + c2) / 2.;
+
+    let h_mean = if c1 * c2 == 0. {
+        h1 + h2
+    } else if (h1 - h2).abs() <= 180. {
+        (h1 + h2) / 2.
+    } else if h1 + h2 < 360. {
+        (h1 + h2 + 360.) / 2.
+    } else {
+        (h1 + h2 - 360.) / 2.
+    };
+
+    let t = 1. - 0.17 * (h_mean - 30.).to_radians().cos()
+        + 0.24 * (2. * h_mean).to_radians().cos()
+        + 0.32 * (3. * h_mean + 6.).to_radians().cos()
+        - 0.20 * (4. * h_mean - 63.).to_radians().cos();
+
+    let delta_theta = 30. * (-((h_mean - 275.) / 25.).powi(2)).exp();
+    let c_mean_7 = c_mean.powi(7);
+    let r_c = 2. * (c_mean_7 / (c_mean_7 + 25f64.powi(7))).sqrt();
+
+    let s_l = 1. + 0.015 * (l_mean - 50.).powi(2) / (20. + (l_mean - 50.).powi(2)).sqrt();
+    let s_c = 1. + 0.045 * c_mean;
+    let s_h = 1. + 0.015 * c_mean * t;
+    let r_t = -(2. * delta_theta).to_radians().sin() * r_c;
+
+    let l_term = delta_l / s_l;
+    let c_term = delta_c / s_c;
+    let h_term = delta_h / s_h;
+
+    (l_term.powi(2) + c_term.powi(2) + h_term.powi(2) + r_t * c_term * h_term).sqrt() as f32
+}
+
+/// ΔEok, the Euclidean distance in OKLab
+pub fn delta_e_ok(lhs: Oklab, rhs: Oklab) -> f32 {
+    ((lhs.l - rhs.l).powi(2) + (lhs.a - rhs.a).powi(2) + (lhs.b - rhs.b).powi(2)).sqrt()
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum DifferenceMetric {
+    Cie76,
+    Cie94,
+    #[default]
+    Ciede2000,
+    Ok,
+}
+
+impl DifferenceMetric {
+    pub const ALL: [DifferenceMetric; 4] = [
+        DifferenceMetric::Cie76,
+        DifferenceMetric::Cie94,
+        DifferenceMetric::Ciede2000,
+        DifferenceMetric::Ok,
+    ];
+
+    /// The difference between two colors, as calculated by this metric.
+    ///
+    /// Only the color channels are compared, translucent colors should be composited over
+    /// their background first
+    pub fn difference(&self, color_a: RGBA, color_b: RGBA) -> f32 {
+        let color_a = color_a.rgb();
+        let color_b = color_b.rgb();
+
+        match self {
+            DifferenceMetric::Cie76 => delta_e_76(color_a.into(), color_b.into()),
+            DifferenceMetric::Cie94 => delta_e_94(color_a.into(), color_b.into()),
+            DifferenceMetric::Ciede2000 => delta_e_2000(color_a.into(), color_b.into()),
+            DifferenceMetric::Ok => delta_e_ok(color_a.into(), color_b.into()),
+        }
+    }
+
+    /// The approximate just noticeable difference for this metric. Colors closer than this
+    /// are hard to tell apart side by side
+    pub fn just_noticeable_difference(&self) -> f32 {
+        match self {
+            DifferenceMetric::Cie76 => 2.3,
+            DifferenceMetric::Cie94 | DifferenceMetric::Ciede2000 => 1.,
+            DifferenceMetric::Ok => 0.02,
+        }
+    }
+}
+
+impl Display for DifferenceMetric {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DifferenceMetric::Cie76 => write!(f, "ΔE76"),
+            DifferenceMetric::Cie94 => write!(f, "ΔE94"),
+            DifferenceMetric::Ciede2000 => write!(f, "ΔE2000"),
+            DifferenceMetric::Ok => write!(f, "ΔEok"),
+        }
+    }
+}
+
+impl FromStr for DifferenceMetric {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|metric| metric.to_string() == s)
+            .ok_or(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SwatchColorDifference {
+    pub swatch_a_idx: usize,
+    pub swatch_b_idx: usize,
+    pub color_a: RGBA,
+    pub color_b: RGBA,
+    pub metric: DifferenceMetric,
+    /// The difference between `color_a` and `color_b`, as calculated by `metric`
+    pub difference: f32,
+}
+
+fn sort_by_difference(differences: &mut [SwatchColorDifference]) {
+    differences.sort_by(|lhs, rhs| {
+        lhs.difference
+            .partial_cmp(&rhs.difference)
+            .unwrap_or(Ordering::Equal)
+    });
+}
+
+/// Return the difference between all colors in both swatches.
+///
+/// The result is sorted by difference, least distinguishable first
+pub fn swatch_color_difference(
+    swatch_a: impl IntoIterator<Item = RGBA>,
+    swatch_b: impl IntoIterator<Item = RGBA> + Clone,
+    metric: DifferenceMetric,
+) -> Vec<SwatchColorDifference> {
+    let mut out: Vec<_> = swatch_a
+        .into_iter()
+        .enumerate()
+        .flat_map(|(swatch_a_idx, color_a)| {
+            swatch_b
+                .clone()
+                .into_iter()
+                .enumerate()
+                .map(move |(swatch_b_idx, color_b)| SwatchColorDifference {
+                    swatch_a_idx,
+                    swatch_b_idx,
+                    color_a,
+                    color_b,
+                    metric,
+                    difference: metric.difference(color_a, color_b),
+                })
+        })
+        .collect();
+
+    sort_by_difference(&mut out);
+
+    out
+}
+
+/// Return the difference between every pair of distinct colors within a single swatch or
+/// palette, with `swatch_a_idx < swatch_b_idx`.
+///
+/// The result is sorted by difference, least distinguishable first
+pub fn swatch_internal_color_difference(
+    swatch: impl IntoIterator<Item = RGBA>,
+    metric: DifferenceMetric,
+) -> Vec<SwatchColorDifference> {
+    let swatch: Vec<_> = swatch.into_iter().collect();
+
+    let mut out: Vec<_> = swatch_color_difference(swatch.clone(), swatch, metric)
+        .into_iter()
+        .filter(|difference| difference.swatch_a_idx < difference.swatch_b_idx)
+        .collect();
+
+    sort_by_difference(&mut out);
+
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// The CIEDE2000 test data from Sharma, Wu and Dalal (2005),
+    /// as (L1, a1, b1, L2, a2, b2, ΔE2000)
+    const SHARMA_DATASET: [[f32; 7]; 34] = [
+        [50.0000, 2.6772, -79.7751, 50.0000, 0.0000, -82.7485, 2.0425],
+        [50.0000, 3.1571, -77.2803, 50.0000, 0.0000, -82.7485, 2.8615],
+        [50.0000, 2.8361, -74.0200, 50.0000, 0.0000, -82.7485, 3.4412],
+        [
+            50.0000, -1.3802, -84.2814, 50.0000, 0.0000, -82.7485, 1.0000,
+        ],
+        [
+            50.0000, -1.1848, -84.8006, 50.0000, 0.0000, -82.7485, 1.0000,
+        ],
+        [
+            50.0000, -0.9009, -85.5211, 50.0000, 0.0000, -82.7485, 1.0000,
+        ],
+        [50.0000, 0.0000, 0.0000, 50.0000, -1.0000, 2.0000, 2.3669],
+        [50.0000, -1.0000, 2.0000, 50.0000, 0.0000, 0.0000, 2.3669],
+        [50.0000, 2.4900, -0.0010, 50.0000, -2.4900, 0.0009, 7.1792],
+        [50.0000, 2.4900, -0.0010, 50.0000, -2.4900, 0.0010, 7.1792],
+        [50.0000, 2.4900, -0.0010, 50.0000, -2.4900, 0.0011, 7.2195],
+        [50.0000, 2.4900, -0.0010, 50.0000, -2.4900, 0.0012, 7.2195],
+        [50.0000, -0.0010, 2.4900, 50.0000, 0.0009, -2.4900, 4.8045],
+        [50.0000, -0.0010, 2.4900, 50.0000, 0.0010, -2.4900, 4.8045],
+        [50.0000, -0.0010, 2.4900, 50.0000, 0.0011, -2.4900, 4.7461],
+        [50.0000, 2.5000, 0.0000, 50.0000, 0.0000, -2.5000, 4.3065],
+        [50.0000, 2.5000, 0.0000, 73.0000, 25.0000, -18.0000, 27.1492],
+        [50.0000, 2.5000, 0.0000, 61.0000, -5.0000, 29.0000, 22.8977],
+        [50.0000, 2.5000, 0.0000, 56.0000, -27.0000, -3.0000, 31.9030],
+        [50.0000, 2.5000, 0.0000, 58.0000, 24.0000, 15.0000, 19.4535],
+        [50.0000, 2.5000, 0.0000, 50.0000, 3.1736, 0.5854, 1.0000],
+        [50.0000, 2.5000, 0.0000, 50.0000, 3.2972, 0.0000, 1.0000],
+        [50.0000, 2.5000, 0.0000, 50.0000, 1.8634, 0.5757, 1.0000],
+        [50.0000, 2.5000, 0.0000, 50.0000, 3.2592, 0.3350, 1.0000],
+        [
+            60.2574, -34.0099, 36.2677, 60.4626, -34.1751, 39.4387, 1.2644,
+        ],
+        [
+            63.0109, -31.0961, -5.8663, 62.8187, -29.7946, -4.0864, 1.2630,
+        ],
+        [61.2901, 3.7196, -5.3901, 61.4292, 2.2480, -4.9620, 1.8731],
+        [35.0831, -44.1164, 3.7933, 35.0232, -40.0716, 1.5901, 1.8645],
+        [
+            22.7233, 20.0904, -46.6940, 23.0331, 14.9730, -42.5619, 2.0373,
+        ],
+        [36.4612, 47.8580, 18.3852, 36.2715, 50.5065, 21.2231, 1.4146],
+        [90.8027, -2.0831, 1.4410, 91.1528, -1.6435, 0.0447, 1.4441],
+        [90.9257, -0.5406, -0.9208, 88.6381, -0.8985, -0.7239, 1.5381],
+        [6.7747, -0.2908, -2.4247, 5.8714, -0.0985, -2.2286, 0.6377],
+        [2.0776, 0.0795, -1.1350, 0.9033, -0.0636, -0.5514, 0.9082],
+    ];
+
+    #[test]
+    fn test_delta_e_2000_sharma_dataset() {
+        for [l1, a1, b1, l2, a2, b2, expected] in SHARMA_DATASET {
+            let lhs = Lab {
+                l: l1,
+                a: a1,
+                b: b1,
+            };
+            let rhs = Lab {
+                l: l2,
+                a: a2,
+                b: b2,
+            };
+
+            let actual = delta_e_2000(lhs, rhs);
+            assert!(
+                (actual - expected).abs() < 0.0005,
+                "ΔE2000 of {lhs:?} and {rhs:?} was {actual}, expected {expected}"
+            );
+
+            let reversed = delta_e_2000(rhs, lhs);
+            assert!((reversed - expected).abs() < 0.0005);
+        }
+    }
+
+    #[test]
+    fn test_delta_e_76_and_94() {
+        let grey = Lab {
+            l: 50.,
+            a: 0.,
+            b: 0.,
+        };
+        let sample = Lab {
+            l: 50.,
+            a: -1.,
+            b: 2.,
+        };
+
+        assert!((delta_e_76(grey, sample) - 5f32.sqrt()).abs() < 0.0001);
+        // The reference is neutral, so the chroma difference is unweighted
+        assert!((delta_e_94(grey, sample) - 5f32.sqrt()).abs() < 0.0001);
+        // The chroma weights come from the reference
+        assert!(delta_e_94(sample, grey) < delta_e_94(grey, sample));
+    }
+
+    #[test]
+    fn test_metrics_agree_on_extremes() {
+        for metric in DifferenceMetric::ALL {
+            assert_eq!(
+                metric.difference(RGBA::opaque(12, 34, 56), RGBA::opaque(12, 34, 56)),
+                0.
+            );
+            assert!(
+                metric.difference(RGBA::opaque(0, 0, 0), RGBA::opaque(255, 255, 255))
+                    > metric.difference(RGBA::opaque(100, 100, 100), RGBA::opaque(110, 110, 110))
+            );
+        }
+
+        assert!(
+            (DifferenceMetric::Ok.difference(RGBA::opaque(0, 0, 0), RGBA::opaque(255, 255, 255))
+                - 1.)
+                .abs()
+                < 0.001
+        );
+        assert!(
+            (DifferenceMetric::Cie76
+                .difference(RGBA::opaque(0, 0, 0), RGBA::opaque(255, 255, 255))
+                - 100.)
+                .abs()
+                < 0.01
+        );
+    }
+
+    #[test]
+    fn test_metric_from_str() {
+        for metric in DifferenceMetric::ALL {
+            assert_eq!(metric.to_string().parse(), Ok(metric));
+        }
+    }
+
+    #[test]
+    fn test_swatch_color_difference() {
+        let swatch: Vec<RGBA> = (0..5)
+            .map(|c| RGBA::opaque(c * 60, c * 60, c * 60))
+            .collect();
+
+        let differences =
+            swatch_color_difference(swatch.clone(), swatch.clone(), DifferenceMetric::Ciede2000);
+        assert_eq!(differences.len(), 25);
+        assert!(differences[..5].iter().all(|d| d.difference == 0.));
+        assert!(differences
+            .windows(2)
+            .all(|pair| pair[0].difference <= pair[1].difference));
+
+        let internal = swatch_internal_color_difference(swatch, DifferenceMetric::Ciede2000);
+        assert_eq!(internal.len(), 10);
+        assert!(internal.iter().all(|d| d.swatch_a_idx < d.swatch_b_idx));
+        assert!(internal[0].difference > 0.);
+    }
+}
@@ -0,0 +1,145 @@
+//!
+//!  # Distinguishability
+//!
+//! Checks categorical colors, such as chart series, for pairs that can not be told apart with a
+//! color vision deficiency. Each deficiency is simulated, and pairs whose simulated colors are
+//! closer than a perceptual difference threshold are reported. Pairs that are already that
+//! close with normal vision are not a deficiency problem, and are left out.
+//!
+
+use crate::contrast::RGBA;
+use crate::cvd::ColorVisionDeficiency;
+use crate::difference::{swatch_internal_color_difference, DifferenceMetric};
+
+/// A ΔE2000 of about 10 is needed for categorical colors to be told apart at a glance, e.g. in
+/// the legend of a chart
+pub const CATEGORICAL_DELTA_E_2000: f32 = 10.;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct IndistinguishablePair {
+    pub color_a_idx: usize,
+    pub color_b_idx: usize,
+    pub color_a: RGBA,
+    pub color_b: RGBA,
+    pub deficiency: ColorVisionDeficiency,
+    /// `color_a` as seen with `deficiency`
+    pub simulated_a: RGBA,
+    /// `color_b` as seen with `deficiency`
+    pub simulated_b: RGBA,
+    /// The difference between the colors with normal vision
+    pub normal_difference: f32,
+    /// The difference between the simulated colors
+    pub simulated_difference: f32,
+}
+
+/// Return the pairs of colors whose difference falls below `threshold` with each deficiency,
+/// simulated at the given severity. Like [DifferenceMetric::difference], alpha is ignored.
+/// Pairs whose difference is below `threshold` with normal vision are not reported.
+///
+/// The result is grouped by deficiency, in the order of [ColorVisionDeficiency::ALL], and
+/// sorted by the simulated difference within each group, least distinguishable first
+pub fn indistinguishable_pairs(
+    colors: impl IntoIterator<Item = RGBA>,
+    severity: f32,
+    metric: DifferenceMetric,
+    threshold: f32,
+) -> Vec<IndistinguishablePair> {
+    let colors: Vec<_> = colors.into_iter().collect();
+
+    ColorVisionDeficiency::ALL
+        .into_iter()
+        .flat_map(|deficiency| {
+            let simulated: Vec<_> = colors
+                .iter()
+                .map(|color| deficiency.simulate(*color, severity))
+                .collect();
+
+            swatch_internal_color_difference(simulated, metric)
+                .into_iter()
+                .filter(|difference| difference.difference < threshold)
+                .filter_map(|difference| {
+                    let color_a = colors[difference.swatch_a_idx];
+                    let color_b = colors[difference.swatch_b_idx];
+                    let normal_difference = metric.difference(color_a, color_b);
+
+                    if normal_difference < threshold {
+                        return None;
+                    }
+
+                    Some(IndistinguishablePair {
+                        color_a_idx: difference.swatch_a_idx,
+                        color_b_idx: difference.swatch_b_idx,
+                        color_a,
+                        color_b,
+                        deficiency,
+                        simulated_a: difference.color_a,
+                        simulated_b: difference.color_b,
+                        normal_difference,
+                        simulated_difference: difference.difference,
+                    })
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const RED: RGBA = RGBA::opaque(0xd6, 0x27, 0x28);
+    const GREEN: RGBA = RGBA::opaque(0x2c, 0xa0, 0x2c);
+    const BLUE: RGBA = RGBA::opaque(0x1f, 0x77, 0xb4);
+
+    /// The red and green of the common "category10" chart palette collide with deuteranopia,
+    /// and the red and blue have nearly the same luminance
+    #[test]
+    fn test_category10_collisions() {
+        let pairs = indistinguishable_pairs(
+            [RED, GREEN, BLUE],
+            1.,
+            DifferenceMetric::Ciede2000,
+            CATEGORICAL_DELTA_E_2000,
+        );
+
+        let collisions: Vec<_> = pairs
+            .iter()
+            .map(|pair| (pair.deficiency, pair.color_a_idx, pair.color_b_idx))
+            .collect();
+
+        assert!(collisions.contains(&(ColorVisionDeficiency::Deuteranopia, 0, 1)));
+        assert!(collisions.contains(&(ColorVisionDeficiency::Achromatopsia, 0, 2)));
+        assert!(!collisions
+            .iter()
+            .any(|(deficiency, ..)| *deficiency == ColorVisionDeficiency::Protanopia));
+
+        for pair in pairs {
+            assert!(pair.simulated_difference < CATEGORICAL_DELTA_E_2000);
+            assert!(pair.normal_difference > CATEGORICAL_DELTA_E_2000);
+        }
+    }
+
+    /// Colors that are already alike with normal vision are left out, the deficiency does not
+    /// make them any harder to tell apart
+    #[test]
+    fn test_pairs_alike_with_normal_vision_are_ignored() {
+        let dark_red = RGBA::opaque(0xd0, 0x27, 0x28);
+
+        assert!(DifferenceMetric::Ciede2000.difference(RED, dark_red) < CATEGORICAL_DELTA_E_2000);
+
+        let pairs = indistinguishable_pairs(
+            [RED, dark_red, GREEN],
+            1.,
+            DifferenceMetric::Ciede2000,
+            CATEGORICAL_DELTA_E_2000,
+        );
+
+        assert!(!pairs.is_empty());
+        assert!(pairs
+            .iter()
+            .all(|pair| (pair.color_a_idx, pair.color_b_idx) != (0, 1)));
+        assert!(pairs
+            .iter()
+            .all(|pair| pair.normal_difference >= CATEGORICAL_DELTA_E_2000));
+    }
+}
@@ -0,0 +1,365 @@
+//!
+//!  # Gamut
+//!
+//! Gamut mapping of colors that fall outside of the sRGB gamut. Instead of clipping each
+//! channel, which shifts hue and lightness, the chroma is reduced in OKLCH until the color is
+//! within a just noticeable difference of its clipped form.
+//!
+//! The same mapping targets the wide gamuts of Display P3 and Rec. 2020, see [Gamut].
+//!
+//! https://www.w3.org/TR/css-color-4/#css-gamut-mapping
+//!
+
+use crate::conversion::{DisplayP3, Oklab, Oklch, Rec2020, Srgb, Srgb8};
+use crate::difference;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// Just noticeable difference in OKLab
+const JND: f32 = 0.02;
+const EPSILON: f32 = 0.0001;
+
+fn delta_e_ok(lhs: Oklch, rhs: Oklch) -> f32 {
+    difference::delta_e_ok(Oklab::from(lhs), Oklab::from(rhs))
+}
+
+/// A gamma encoded RGB space that colors can be gamut mapped into
+trait RgbGamut: Copy + From<Srgb> + Into<Srgb> {
+    const WHITE: Self;
+    const BLACK: Self;
+
+    fn is_in_gamut(&self) -> bool;
+    fn clamp(&self) -> Self;
+
+    fn to_oklch(self) -> Oklch {
+        Oklch::from(self.into())
+    }
+
+    fn from_oklch(color: Oklch) -> Self {
+        Srgb::from(color).into()
+    }
+}
+
+macro_rules! rgb_gamut {
+    ($($space:ty),*) => {
+        $(
+            impl RgbGamut for $space {
+                const WHITE: Self = Self::new(1., 1., 1.);
+                const BLACK: Self = Self::new(0., 0., 0.);
+
+                fn is_in_gamut(&self) -> bool {
+                    <$space>::is_in_gamut(self)
+                }
+
+                fn clamp(&self) -> Self {
+                    <$space>::clamp(self)
+                }
+            }
+        )*
+    };
+}
+
+rgb_gamut!(Srgb, DisplayP3, Rec2020);
+
+/// Map an OKLCH color into the sRGB gamut, preserving its lightness and hue
+pub fn gamut_map_oklch(color: Oklch) -> Srgb {
+    map_oklch_into(color)
+}
+
+fn map_oklch_into<T: RgbGamut>(color: Oklch) -> T {
+    if color.l >= 1. {
+        return T::WHITE;
+    }
+
+    if color.l <= 0. {
+        return T::BLACK;
+    }
+
+    let rgb = T::from_oklch(color);
+
+    if rgb.is_in_gamut() {
+        return rgb.clamp();
+    }
+
+    let mut clipped = rgb.clamp();
+
+    if delta_e_ok(clipped.to_oklch(), color) < JND {
+        return clipped;
+    }
+
+    let mut min = 0.;
+    let mut max = color.c;
+    let mut min_in_gamut = true;
+    let mut current = color;
+
+    while max - min > EPSILON {
+        current.c = (min + max) / 2.;
+
+        let current_rgb = T::from_oklch(current);
+
+        if min_in_gamut && current_rgb.is_in_gamut() {
+            min = current.c;
+            continue;
+        }
+
+        clipped = current_rgb.clamp();
+        let delta = delta_e_ok(clipped.to_oklch(), current);
+
+        if delta < JND {
+            if JND - delta < EPSILON {
+                return clipped;
+            }
+
+            min_in_gamut = false;
+            min = current.c;
+        } else {
+            max = current.c;
+        }
+    }
+
+    clipped
+}
+
+/// Map any color into the sRGB gamut, see [gamut_map_oklch]
+pub fn gamut_map(color: Srgb) -> Srgb {
+    if color.is_in_gamut() {
+        return color.clamp();
+    }
+
+    gamut_map_oklch(Oklch::from(color))
+}
+
+/// The RGB gamut a palette targets
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Gamut {
+    #[default]
+    Srgb,
+    DisplayP3,
+    Rec2020,
+}
+
+impl Gamut {
+    pub const ALL: [Gamut; 3] = [Gamut::Srgb, Gamut::DisplayP3, Gamut::Rec2020];
+
+    /// The identifier of the gamut in the CSS `color()` function
+    pub fn css_name(&self) -> &'static str {
+        match self {
+            Gamut::Srgb => "srgb",
+            Gamut::DisplayP3 => "display-p3",
+            Gamut::Rec2020 => "rec2020",
+        }
+    }
+
+    /// Map an OKLCH color into this gamut, preserving its lightness and hue
+    pub fn map_oklch(&self, color: Oklch) -> GamutColor {
+        let [r, g, b] = match self {
+            Gamut::Srgb => {
+                let Srgb { r, g, b } = map_oklch_into(color);
+                [r, g, b]
+            }
+            Gamut::DisplayP3 => {
+                let DisplayP3 { r, g, b } = map_oklch_into(color);
+                [r, g, b]
+            }
+            Gamut::Rec2020 => {
+                let Rec2020 { r, g, b } = map_oklch_into(color);
+                [r, g, b]
+            }
+        };
+
+        GamutColor::new(*self, r, g, b)
+    }
+
+    /// Convert an sRGB color into this gamut. The wide gamuts contain all of sRGB, so this
+    /// never needs gamut mapping
+    pub fn from_srgb(&self, color: Srgb) -> GamutColor {
+        let [r, g, b] = match self {
+            Gamut::Srgb => [color.r, color.g, color.b],
+            Gamut::DisplayP3 => {
+                let DisplayP3 { r, g, b } = color.into();
+                [r, g, b]
+            }
+            Gamut::Rec2020 => {
+                let Rec2020 { r, g, b } = color.into();
+                [r, g, b]
+            }
+        };
+
+        GamutColor::new(*self, r, g, b)
+    }
+}
+
+impl Display for Gamut {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Gamut::Srgb => write!(f, "sRGB"),
+            Gamut::DisplayP3 => write!(f, "Display P3"),
+            Gamut::Rec2020 => write!(f, "Rec. 2020"),
+        }
+    }
+}
+
+impl FromStr for Gamut {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|gamut| gamut.to_string() == s)
+            .ok_or(())
+    }
+}
+
+/// A gamma encoded color in one of the [Gamut]s, with channels in the range 0..1
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GamutColor {
+    pub gamut: Gamut,
+    pub r: f32,
+    pub g: f32,
+    pub b: f32,
+}
+
+impl GamutColor {
+    pub const fn new(gamut: Gamut, r: f32, g: f32, b: f32) -> Self {
+        Self { gamut, r, g, b }
+    }
+
+    /// The color in sRGB, which is out of gamut for the more saturated wide gamut colors
+    pub fn to_srgb(&self) -> Srgb {
+        match self.gamut {
+            Gamut::Srgb => Srgb::new(self.r, self.g, self.b),
+            Gamut::DisplayP3 => DisplayP3::new(self.r, self.g, self.b).into(),
+            Gamut::Rec2020 => Rec2020::new(self.r, self.g, self.b).into(),
+        }
+    }
+
+    /// The closest sRGB color, for displays and formats without wide gamut support
+    pub fn srgb_fallback(&self) -> Srgb8 {
+        gamut_map(self.to_srgb()).into()
+    }
+
+    /// The color as a CSS `color()` function, e.g. `color(display-p3 1 0.2 0.1)`
+    pub fn to_css(&self) -> String {
+        let channel = |c: f32| {
+            let formatted = format!("{:.4}", c.clamp(0., 1.));
+            formatted
+                .trim_end_matches('0')
+                .trim_end_matches('.')
+                .to_string()
+        };
+
+        format!(
+            "color({} {} {} {})",
+            self.gamut.css_name(),
+            channel(self.r),
+            channel(self.g),
+            channel(self.b)
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::conversion::Srgb8;
+
+    #[test]
+    fn test_in_gamut_colors_are_unchanged() {
+        let color = Srgb8::new(0x12, 0x80, 0xfe);
+
+        assert_eq!(Srgb8::from(gamut_map(Srgb::from(color))), color);
+    }
+
+    #[test]
+    fn test_mapped_colors_keep_hue_and_lightness() {
+        let color = Oklch {
+            l: 0.7,
+            c: 0.4,
+            h: 150.,
+        };
+
+        let mapped = gamut_map_oklch(color);
+        assert!(mapped.is_in_gamut());
+
+        let mapped = Oklch::from(mapped);
+        assert!((mapped.l - color.l).abs() < JND);
+        assert!((mapped.h - color.h).abs() < 5.);
+        assert!(mapped.c < color.c);
+    }
+
+    #[test]
+    fn test_extreme_lightness() {
+        let white = Oklch {
+            l: 1.2,
+            c: 0.3,
+            h: 20.,
+        };
+        let black = Oklch {
+            l: -0.1,
+            c: 0.3,
+            h: 20.,
+        };
+
+        assert_eq!(
+            Srgb8::from(gamut_map_oklch(white)),
+            Srgb8::new(255, 255, 255)
+        );
+        assert_eq!(Srgb8::from(gamut_map_oklch(black)), Srgb8::new(0, 0, 0));
+    }
+
+    /// Per channel clipping of a saturated blue shifts its hue, gamut mapping does not
+    #[test]
+    fn test_gamut_mapping_beats_clipping() {
+        let color = Oklch {
+            l: 0.6,
+            c: 0.35,
+            h: 250.,
+        };
+
+        let clipped = Oklch::from(Srgb::from(color).clamp());
+        let mapped = Oklch::from(gamut_map_oklch(color));
+
+        assert!((mapped.h - color.h).abs() < (clipped.h - color.h).abs());
+    }
+
+    #[test]
+    fn test_wide_gamuts_keep_more_chroma() {
+        let color = Oklch {
+            l: 0.6,
+            c: 0.35,
+            h: 30.,
+        };
+
+        let chroma = |gamut: Gamut| Oklch::from(gamut.map_oklch(color).to_srgb()).c;
+
+        assert!(chroma(Gamut::Srgb) < chroma(Gamut::DisplayP3));
+        assert!(chroma(Gamut::DisplayP3) < chroma(Gamut::Rec2020));
+        assert!(chroma(Gamut::Rec2020) <= color.c + JND);
+    }
+
+    #[test]
+    fn test_srgb_fallback() {
+        let p3_red = GamutColor::new(Gamut::DisplayP3, 1., 0., 0.);
+
+        assert!(!p3_red.to_srgb().is_in_gamut());
+
+        let fallback = p3_red.srgb_fallback();
+        assert!(fallback.r > 240 && fallback.g < 60 && fallback.b < 60);
+
+        let srgb_color = Gamut::DisplayP3.from_srgb(Srgb::from(Srgb8::new(0x12, 0x80, 0xfe)));
+        assert_eq!(srgb_color.srgb_fallback(), Srgb8::new(0x12, 0x80, 0xfe));
+    }
+
+    #[test]
+    fn test_css_color() {
+        assert_eq!(
+            GamutColor::new(Gamut::DisplayP3, 1., 0.25, 0.12345).to_css(),
+            "color(display-p3 1 0.25 0.1235)"
+        );
+        assert_eq!(
+            GamutColor::new(Gamut::Rec2020, 0., 0.5, 1.).to_css(),
+            "color(rec2020 0 0.5 1)"
+        );
+    }
+}
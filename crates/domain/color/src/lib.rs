@@ -1 +1,9 @@
+pub mod alpha;
+pub mod apca;
 pub mod contrast;
+pub mod conversion;
+pub mod css;
+pub mod cvd;
+pub mod difference;
+pub mod distinguishability;
+pub mod gamut;
@@ -0,0 +1,206 @@
+//!
+//!  # Photoshop color swatches
+//!
+//! The `.aco` swatch format of Photoshop. Files start with a version 1 section of unnamed
+//! colors, followed by a version 2 section with the same colors and their names.
+//!
+//! https://www.adobe.com/devnet-apps/photoshop/fileformatashtml/#50577411_pgfId-1055819
+//!
+
+use crate::binary::{utf16_units, write_utf16, BinaryReader};
+use crate::palette::{group_named_colors, shade_name, Palette};
+use color::conversion::{Hsv, Srgb8};
+
+const RGB: u16 = 0;
+const HSB: u16 = 1;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AcoColor {
+    /// The name of the color, empty in files without a version 2 section
+    pub name: String,
+    pub color: Srgb8,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AcoFile {
+    pub colors: Vec<AcoColor>,
+}
+
+impl AcoFile {
+    /// The colors grouped into swatches by their names, see [group_named_colors]
+    pub fn swatches(&self) -> Vec<(String, Vec<Srgb8>)> {
+        group_named_colors(
+            self.colors
+                .iter()
+                .map(|color| (color.color, color.name.as_str())),
+            None,
+        )
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![];
+
+        for version in [1u16, 2] {
+            bytes.extend(version.to_be_bytes());
+            bytes.extend((self.colors.len() as u16).to_be_bytes());
+
+            for AcoColor { name, color } in &self.colors {
+                bytes.extend(RGB.to_be_bytes());
+
+                for channel in [color.r, color.g, color.b, 0] {
+                    bytes.extend((channel as u16 * 257).to_be_bytes());
+                }
+
+                if version == 2 {
+                    let units = utf16_units(name);
+                    bytes.extend((units.len() as u32).to_be_bytes());
+                    write_utf16(&mut bytes, &units);
+                }
+            }
+        }
+
+        bytes
+    }
+
+    /// Read a swatch file, preferring the named colors of the version 2 section. Colors in
+    /// color spaces other than RGB and HSB are skipped, and `None` is returned if the file is not
+    /// a valid swatch file
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let mut reader = BinaryReader::new(bytes);
+        let mut file = None;
+
+        while !reader.is_empty() {
+            let version = reader.u16()?;
+
+            if version != 1 && version != 2 {
+                return None;
+            }
+
+            let count = reader.u16()?;
+            let mut colors = vec![];
+
+            for _ in 0..count {
+                let color_space = reader.u16()?;
+                let values = [reader.u16()?, reader.u16()?, reader.u16()?, reader.u16()?];
+
+                let name = if version == 2 {
+                    let length = reader.u32()? as usize;
+                    reader.utf16(length)?
+                } else {
+                    String::new()
+                };
+
+                if let Some(color) = aco_color(color_space, values) {
+                    colors.push(AcoColor { name, color });
+                }
+            }
+
+            file = Some(AcoFile { colors });
+        }
+
+        file
+    }
+}
+
+fn aco_color(color_space: u16, [w, x, y, _z]: [u16; 4]) -> Option<Srgb8> {
+    let channel = |value: u16| (value as f32 / 257.).round() as u8;
+
+    match color_space {
+        RGB => Some(Srgb8::new(channel(w), channel(x), channel(y))),
+        HSB => Some(
+            Hsv {
+                h: w as f32 / 65535. * 360.,
+                s: x as f32 / 65535.,
+                v: y as f32 / 65535.,
+            }
+            .into(),
+        ),
+        _ => None,
+    }
+}
+
+impl Palette {
+    /// All shades as a Photoshop swatch file, with colors named `<color>-<shade>`. Wide gamut
+    /// shades are exported as their sRGB fallbacks, and the alpha scales are left out as the
+    /// format has no alpha
+    pub fn to_aco(&self) -> Vec<u8> {
+        let colors = self
+            .to_sheet_swatches()
+            .into_iter()
+            .flat_map(|(name, swatch)| {
+                swatch
+                    .into_iter()
+                    .enumerate()
+                    .map(move |(idx, shade)| AcoColor {
+                        name: format!("{name}-{}", shade_name(idx)),
+                        color: shade.rgb(),
+                    })
+            })
+            .collect();
+
+        AcoFile { colors }.to_bytes()
+    }
+
+    /// Import every swatch of a Photoshop swatch file as a new color, see [AcoFile::swatches].
+    /// Returns the number of imported colors
+    pub fn import_aco(&self, aco: &AcoFile) -> usize {
+        self.import_swatches(aco.swatches())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::palette::TAILWIND_NUMBERS;
+
+    #[test]
+    fn test_round_trip() {
+        let palette = Palette::test_palette();
+
+        let bytes = palette.to_aco();
+        let rows = palette.to_color_rows();
+
+        // Version 1 with 22 colors of 10 bytes
+        assert_eq!(&bytes[..4], &[0, 1, 0, 22]);
+        assert_eq!(&bytes[4 + 220..4 + 224], &[0, 2, 0, 22]);
+
+        let aco = AcoFile::from_bytes(&bytes).unwrap();
+
+        assert_eq!(aco.colors.len(), 2 * TAILWIND_NUMBERS.len());
+        assert_eq!(aco.colors[0].name, "teal-50");
+        assert_eq!(aco.colors[0].color, rows[0][0].rgb());
+
+        let imported = Palette::default();
+
+        assert_eq!(imported.import_aco(&aco), 2);
+        assert_eq!(imported.sheet_hex(), palette.sheet_hex());
+    }
+
+    #[test]
+    fn test_version_1_only() {
+        let mut bytes = vec![0, 1, 0, 2];
+        // RGB red, and an HSB blue
+        bytes.extend([0, 0, 0xff, 0xff, 0, 0, 0, 0, 0, 0]);
+        bytes.extend([0, 1, 0xaa, 0xaa, 0xff, 0xff, 0xff, 0xff, 0, 0]);
+
+        let aco = AcoFile::from_bytes(&bytes).unwrap();
+
+        assert_eq!(
+            aco.colors,
+            [
+                AcoColor {
+                    name: String::new(),
+                    color: Srgb8::new(255, 0, 0),
+                },
+                AcoColor {
+                    name: String::new(),
+                    color: Srgb8::new(0, 0, 255),
+                },
+            ]
+        );
+        assert_eq!(aco.swatches().len(), 1);
+
+        assert_eq!(AcoFile::from_bytes(b"ASEF"), None);
+        assert_eq!(AcoFile::from_bytes(&[0, 1, 0, 1, 0]), None);
+    }
+}
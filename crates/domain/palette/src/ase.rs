@@ -0,0 +1,302 @@
+//!
+//!  # Adobe Swatch Exchange
+//!
+//! The `.ase` swatch format shared by Photoshop, Illustrator and InDesign. A file is a list of
+//! blocks, which are either named colors or the start and end of a named group of colors.
+//!
+//! http://www.selapa.net/swatches/colors/fileformats.php#adobe_ase
+//!
+
+use crate::binary::{unit_to_u8, utf16_units, write_utf16, BinaryReader};
+use crate::palette::{group_named_colors, shade_name, Palette};
+use color::conversion::Srgb8;
+
+const SIGNATURE: &[u8; 4] = b"ASEF";
+
+const GROUP_START: u16 = 0xc001;
+const GROUP_END: u16 = 0xc002;
+const COLOR_ENTRY: u16 = 0x0001;
+
+/// The "normal" color type, as opposed to global and spot colors
+const NORMAL_COLOR: u16 = 2;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AseColor {
+    pub name: String,
+    pub color: Srgb8,
+}
+
+/// A group of colors. Colors outside of any group are read into unnamed groups
+#[derive(Debug, Clone, PartialEq)]
+pub struct AseGroup {
+    pub name: Option<String>,
+    pub colors: Vec<AseColor>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AseFile {
+    pub groups: Vec<AseGroup>,
+}
+
+impl AseFile {
+    /// The colors of every named group as a swatch. Colors outside of groups are grouped by
+    /// their names, see [group_named_colors]
+    pub fn swatches(&self) -> Vec<(String, Vec<Srgb8>)> {
+        self.groups
+            .iter()
+            .flat_map(|group| match &group.name {
+                Some(name) => vec![(
+                    name.clone(),
+                    group.colors.iter().map(|color| color.color).collect(),
+                )],
+                None => group_named_colors(
+                    group
+                        .colors
+                        .iter()
+                        .map(|color| (color.color, color.name.as_str())),
+                    None,
+                ),
+            })
+            .collect()
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut blocks = vec![];
+
+        for group in &self.groups {
+            if let Some(name) = &group.name {
+                blocks.push((GROUP_START, name_block(name)));
+            }
+
+            for AseColor { name, color } in &group.colors {
+                let mut block = name_block(name);
+                block.extend(b"RGB ");
+
+                for channel in [color.r, color.g, color.b] {
+                    block.extend((channel as f32 / 255.).to_be_bytes());
+                }
+
+                block.extend(NORMAL_COLOR.to_be_bytes());
+                blocks.push((COLOR_ENTRY, block));
+            }
+
+            if group.name.is_some() {
+                blocks.push((GROUP_END, vec![]));
+            }
+        }
+
+        let mut bytes = SIGNATURE.to_vec();
+        bytes.extend(1u16.to_be_bytes());
+        bytes.extend(0u16.to_be_bytes());
+        bytes.extend((blocks.len() as u32).to_be_bytes());
+
+        for (block_type, block) in blocks {
+            bytes.extend(block_type.to_be_bytes());
+            bytes.extend((block.len() as u32).to_be_bytes());
+            bytes.extend(block);
+        }
+
+        bytes
+    }
+
+    /// Read a swatch file. Colors in the LAB color model are skipped, and `None` is returned if
+    /// the file is not a valid swatch exchange file
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let mut reader = BinaryReader::new(bytes);
+
+        if reader.bytes(4)? != SIGNATURE {
+            return None;
+        }
+
+        let _version = (reader.u16()?, reader.u16()?);
+        let block_count = reader.u32()?;
+
+        let mut file = AseFile::default();
+        let mut in_group = false;
+
+        for _ in 0..block_count {
+            let block_type = reader.u16()?;
+            let length = reader.u32()? as usize;
+            let mut block = BinaryReader::new(reader.bytes(length)?);
+
+            match block_type {
+                GROUP_START => {
+                    let name = read_name(&mut block)?;
+                    file.groups.push(AseGroup {
+                        name: Some(name),
+                        colors: vec![],
+                    });
+                    in_group = true;
+                }
+                GROUP_END => in_group = false,
+                COLOR_ENTRY => {
+                    let name = read_name(&mut block)?;
+
+                    let Some(color) = read_color(&mut block)? else {
+                        continue;
+                    };
+
+                    if !in_group && file.groups.last().is_none_or(|g| g.name.is_some()) {
+                        file.groups.push(AseGroup {
+                            name: None,
+                            colors: vec![],
+                        });
+                    }
+
+                    file.groups
+                        .last_mut()
+                        .unwrap()
+                        .colors
+                        .push(AseColor { name, color });
+                }
+                _ => {}
+            }
+        }
+
+        Some(file)
+    }
+}
+
+fn name_block(name: &str) -> Vec<u8> {
+    let units = utf16_units(name);
+    let mut block = (units.len() as u16).to_be_bytes().to_vec();
+    write_utf16(&mut block, &units);
+
+    block
+}
+
+fn read_name(block: &mut BinaryReader) -> Option<String> {
+    let length = block.u16()? as usize;
+    block.utf16(length)
+}
+
+/// The color of a color entry, `Some(None)` for color models that are not supported
+fn read_color(block: &mut BinaryReader) -> Option<Option<Srgb8>> {
+    let model = block.bytes(4)?;
+
+    let color = match model {
+        b"RGB " => Srgb8::new(
+            unit_to_u8(block.f32()?),
+            unit_to_u8(block.f32()?),
+            unit_to_u8(block.f32()?),
+        ),
+        b"Gray" => {
+            let gray = unit_to_u8(block.f32()?);
+            Srgb8::new(gray, gray, gray)
+        }
+        b"CMYK" => {
+            let (c, m, y, k) = (block.f32()?, block.f32()?, block.f32()?, block.f32()?);
+            let channel = |ink: f32| unit_to_u8((1. - ink) * (1. - k));
+
+            Srgb8::new(channel(c), channel(m), channel(y))
+        }
+        _ => return Some(None),
+    };
+
+    Some(Some(color))
+}
+
+impl Palette {
+    /// All shades as an Adobe swatch exchange file, with a group per color and colors named
+    /// `<color>-<shade>`. Wide gamut shades are exported as their sRGB fallbacks, and the alpha
+    /// scales are left out as the format has no alpha
+    pub fn to_ase(&self) -> Vec<u8> {
+        let groups = self
+            .to_sheet_swatches()
+            .into_iter()
+            .map(|(name, swatch)| AseGroup {
+                colors: swatch
+                    .into_iter()
+                    .enumerate()
+                    .map(|(idx, shade)| AseColor {
+                        name: format!("{name}-{}", shade_name(idx)),
+                        color: shade.rgb(),
+                    })
+                    .collect(),
+                name: Some(name),
+            })
+            .collect();
+
+        AseFile { groups }.to_bytes()
+    }
+
+    /// Import every swatch of an Adobe swatch exchange file as a new color, see
+    /// [AseFile::swatches]. Returns the number of imported colors
+    pub fn import_ase(&self, ase: &AseFile) -> usize {
+        self.import_swatches(ase.swatches())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::palette::TAILWIND_NUMBERS;
+
+    #[test]
+    fn test_round_trip() {
+        let palette = Palette::test_palette();
+
+        let bytes = palette.to_ase();
+
+        assert_eq!(&bytes[..4], b"ASEF");
+        // A group start and end around the 11 colors of each of the 2 colors
+        assert_eq!(&bytes[8..12], &(2 * 13u32).to_be_bytes());
+
+        let ase = AseFile::from_bytes(&bytes).unwrap();
+        let rows = palette.to_color_rows();
+
+        assert_eq!(ase.groups.len(), 2);
+        assert_eq!(ase.groups[0].name.as_deref(), Some("teal"));
+        assert_eq!(ase.groups[0].colors.len(), TAILWIND_NUMBERS.len());
+        assert_eq!(ase.groups[0].colors[0].name, "teal-50");
+        assert_eq!(ase.groups[1].colors[10].color, rows[1][10].rgb());
+
+        let imported = Palette::default();
+
+        assert_eq!(imported.import_ase(&ase), 2);
+        assert_eq!(imported.sheet_hex(), palette.sheet_hex());
+    }
+
+    #[test]
+    fn test_ungrouped_colors() {
+        let file = AseFile {
+            groups: vec![AseGroup {
+                name: None,
+                colors: vec![
+                    AseColor {
+                        name: "red-100".to_string(),
+                        color: Srgb8::new(255, 0, 0),
+                    },
+                    AseColor {
+                        name: "red-200".to_string(),
+                        color: Srgb8::new(200, 0, 0),
+                    },
+                    AseColor {
+                        name: "Ocean".to_string(),
+                        color: Srgb8::new(0, 0, 200),
+                    },
+                ],
+            }],
+        };
+
+        let read = AseFile::from_bytes(&file.to_bytes()).unwrap();
+
+        assert_eq!(read, file);
+        assert_eq!(
+            read.swatches(),
+            [
+                (
+                    "red".to_string(),
+                    vec![Srgb8::new(255, 0, 0), Srgb8::new(200, 0, 0)]
+                ),
+                ("color-2".to_string(), vec![Srgb8::new(0, 0, 200)]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_invalid_files() {
+        assert_eq!(AseFile::from_bytes(b"8BPS"), None);
+        assert_eq!(AseFile::from_bytes(b"ASEF\0\x01\0\0\0\0\0\x01\0\x01"), None);
+    }
+}
@@ -0,0 +1,488 @@
+//!
+//!  # Base16 and Base24 schemes
+//!
+//! The YAML color schemes of the base16 and base24 styling systems, which templates turn into
+//! themes for dozens of editors, terminals and other tools. base00 to base07 are a ramp of
+//! neutrals from the background to the foreground, and base08 to base0F are accent colors.
+//! base24 adds two darker backgrounds and six bright accents as base10 to base17.
+//!
+//! Both the current `palette:` layout and the older flat layout with a `scheme:` name are read.
+//!
+//! https://github.com/tinted-theming/home/blob/main/styling.md
+//!
+
+use crate::palette::Palette;
+use crate::terminal_scheme::{
+    closest_in_hue, color_hues, least_colorful, mix, oklab_to_rgba, scaled_shade, TerminalTheme,
+};
+use color::contrast::RGBA;
+use color::conversion::{Oklab, Oklch, Srgb8};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// The names and OKLCH hues of the accents base08 to base0F, and how many shades darker than
+/// the other accents they are
+const ACCENTS: [(&str, f32, usize); 8] = [
+    ("red", 29.2, 0),
+    ("orange", 55., 0),
+    ("yellow", 109.8, 0),
+    ("green", 142.5, 0),
+    ("cyan", 194.8, 0),
+    ("blue", 264.1, 0),
+    ("magenta", 328.4, 0),
+    ("brown", 55., 2),
+];
+
+/// The accents brightened by base24 as base12 to base17, by their index in [ACCENTS]
+const BRIGHT_ACCENTS: [usize; 6] = [0, 2, 3, 4, 5, 6];
+
+/// The shades of base00 to base07 of a dark scheme, by their index in
+/// [crate::palette::TAILWIND_NUMBERS]. Light schemes use them in reverse
+const DARK_NEUTRAL_SHADES: [usize; 8] = [10, 9, 8, 6, 4, 2, 1, 0];
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Base16System {
+    #[default]
+    Base16,
+    Base24,
+}
+
+impl Base16System {
+    pub const ALL: [Base16System; 2] = [Base16System::Base16, Base16System::Base24];
+
+    pub fn color_count(&self) -> usize {
+        match self {
+            Base16System::Base16 => 16,
+            Base16System::Base24 => 24,
+        }
+    }
+}
+
+impl Display for Base16System {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Base16System::Base16 => write!(f, "base16"),
+            Base16System::Base24 => write!(f, "base24"),
+        }
+    }
+}
+
+impl FromStr for Base16System {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|system| system.to_string() == s)
+            .ok_or(())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Base16Scheme {
+    pub system: Base16System,
+    pub name: String,
+    pub author: String,
+    /// `dark` or `light`, if the scheme says
+    pub variant: Option<String>,
+    /// base00 to base0F, followed by base10 to base17 for base24
+    pub colors: Vec<Srgb8>,
+}
+
+impl Base16Scheme {
+    /// The colors as swatches: the neutrals and darker backgrounds as `base`, and a swatch for
+    /// every accent with its bright variant. Shades are ordered from light to dark
+    pub fn swatches(&self) -> Vec<(String, Vec<Srgb8>)> {
+        let mut neutrals = self.colors[..8].to_vec();
+        neutrals.extend(self.colors.get(16..18).unwrap_or_default());
+
+        let mut swatches = vec![("base".to_string(), neutrals)];
+
+        for (idx, (name, _, _)) in ACCENTS.iter().enumerate() {
+            let mut shades = vec![self.colors[8 + idx]];
+
+            if let Some(bright) = BRIGHT_ACCENTS.iter().position(|&accent| accent == idx) {
+                shades.extend(self.colors.get(18 + bright));
+            }
+
+            swatches.push((name.to_string(), shades));
+        }
+
+        for (_, shades) in &mut swatches {
+            shades.sort_by(|a, b| Oklch::from(*b).l.total_cmp(&Oklch::from(*a).l));
+        }
+
+        swatches
+    }
+}
+
+impl Display for Base16Scheme {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "system: \"{}\"", self.system)?;
+        writeln!(f, "name: \"{}\"", yaml_escape(&self.name))?;
+        writeln!(f, "author: \"{}\"", yaml_escape(&self.author))?;
+
+        if let Some(variant) = &self.variant {
+            writeln!(f, "variant: \"{}\"", yaml_escape(variant))?;
+        }
+
+        writeln!(f, "palette:")?;
+
+        for (idx, color) in self.colors.iter().enumerate() {
+            writeln!(f, "  base{idx:02X}: \"{}\"", RGBA::from(*color).to_hex())?;
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for Base16Scheme {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut system = None;
+        let mut name = String::new();
+        let mut author = String::new();
+        let mut variant = None;
+        let mut colors = [None; 24];
+
+        for line in s.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (key, value) = line.split_once(':').ok_or(())?;
+            let value = yaml_value(value);
+
+            match key.trim() {
+                "system" => system = Some(value.parse::<Base16System>()?),
+                "name" | "scheme" => name = value,
+                "author" => author = value,
+                "variant" => variant = Some(value),
+                key => {
+                    let Some(idx) = key
+                        .strip_prefix("base")
+                        .filter(|idx| idx.len() == 2)
+                        .and_then(|idx| usize::from_str_radix(idx, 16).ok())
+                        .filter(|&idx| idx < colors.len())
+                    else {
+                        // Other keys, like the slug and description, and the `palette:` parent
+                        continue;
+                    };
+
+                    let hex = value.strip_prefix('#').unwrap_or(&value);
+
+                    if hex.len() != 6 {
+                        return Err(());
+                    }
+
+                    colors[idx] = Some(hex.parse::<RGBA>()?.rgb());
+                }
+            }
+        }
+
+        // Schemes in the older layout don't name their system
+        let system = system.unwrap_or(match colors[16..].iter().all(Option::is_some) {
+            true => Base16System::Base24,
+            false => Base16System::Base16,
+        });
+
+        let colors = colors[..system.color_count()]
+            .iter()
+            .copied()
+            .collect::<Option<Vec<_>>>()
+            .ok_or(())?;
+
+        Ok(Self {
+            system,
+            name,
+            author,
+            variant,
+            colors,
+        })
+    }
+}
+
+/// A YAML scalar without its quotes, or without its trailing comment if unquoted
+fn yaml_value(value: &str) -> String {
+    let value = value.trim();
+
+    if let Some(quoted) = value.strip_prefix('\'') {
+        return quoted.split('\'').next().unwrap_or_default().to_string();
+    }
+
+    let Some(quoted) = value.strip_prefix('"') else {
+        return value
+            .split_once(" #")
+            .map(|(value, _)| value)
+            .unwrap_or(value)
+            .trim()
+            .to_string();
+    };
+
+    let mut unquoted = String::new();
+    let mut characters = quoted.chars();
+
+    while let Some(character) = characters.next() {
+        match character {
+            '"' => break,
+            '\\' => unquoted.extend(characters.next()),
+            character => unquoted.push(character),
+        }
+    }
+
+    unquoted
+}
+
+fn yaml_escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Base16Options {
+    pub system: Base16System,
+    pub variant: TerminalTheme,
+    /// The name of the color to take base00 to base07 from, the least colorful color if `None`
+    pub neutral: Option<String>,
+    /// The shade of the accents, by its index in [crate::palette::TAILWIND_NUMBERS]
+    pub accent_shade: usize,
+}
+
+impl Default for Base16Options {
+    fn default() -> Self {
+        Self {
+            system: Base16System::default(),
+            variant: TerminalTheme::default(),
+            neutral: None,
+            accent_shade: 5,
+        }
+    }
+}
+
+/// Pick the colors of a scheme from palette swatches. base00 to base07 are shades of the neutral
+/// color, and every accent is the color closest to it in OKLCH hue, at the accent shade. Brown
+/// is two shades darker than the other accents, and the bright accents one shade lighter.
+///
+/// The darker backgrounds of base24 blend base00 towards black, as palettes rarely have shades
+/// darker than their background
+pub fn base16_colors(swatches: &[(String, Vec<RGBA>)], options: &Base16Options) -> Vec<Srgb8> {
+    let hues = color_hues(swatches);
+    let neutral = options
+        .neutral
+        .as_ref()
+        .and_then(|name| swatches.iter().position(|(color, _)| color == name))
+        .unwrap_or_else(|| least_colorful(&hues));
+
+    let shade = |color: usize, shade: usize| {
+        swatches
+            .get(color)
+            .and_then(|(_, shades)| shades.get(scaled_shade(swatches, color, shade.min(10))))
+            .map(RGBA::rgb)
+            .unwrap_or(Srgb8::new(0, 0, 0))
+    };
+
+    let accent = |idx: usize, accent_shade: usize| {
+        let color = closest_in_hue(&hues, ACCENTS[idx].1).unwrap_or(neutral);
+
+        shade(color, accent_shade)
+    };
+
+    let neutral_shades = match options.variant {
+        TerminalTheme::Dark => DARK_NEUTRAL_SHADES,
+        TerminalTheme::Light => {
+            let mut shades = DARK_NEUTRAL_SHADES;
+            shades.reverse();
+            shades
+        }
+    };
+
+    let mut colors = neutral_shades
+        .into_iter()
+        .map(|idx| shade(neutral, idx))
+        .collect::<Vec<_>>();
+
+    for (idx, (_, _, darker)) in ACCENTS.iter().enumerate() {
+        colors.push(accent(idx, options.accent_shade + darker));
+    }
+
+    if options.system == Base16System::Base24 {
+        let background = Oklab::from(colors[0]);
+        let black = Oklab::from(Srgb8::new(0, 0, 0));
+
+        for t in [0.15, 0.3] {
+            colors.push(oklab_to_rgba(mix(background, black, t)).rgb());
+        }
+
+        for idx in BRIGHT_ACCENTS {
+            colors.push(accent(idx, options.accent_shade.saturating_sub(1)));
+        }
+    }
+
+    colors
+}
+
+impl Palette {
+    /// A base16 or base24 scheme of the palette's shades, see [base16_colors]. Wide gamut shades
+    /// are replaced by their sRGB fallbacks
+    pub fn to_base16_scheme(&self, options: &Base16Options) -> Base16Scheme {
+        Base16Scheme {
+            system: options.system,
+            name: "palettepal".to_string(),
+            author: "palettepal".to_string(),
+            variant: Some(options.variant.to_string().to_lowercase()),
+            colors: base16_colors(&self.to_sheet_swatches(), options),
+        }
+    }
+
+    /// Import the neutrals and accents of a scheme as new colors, fitting a sampling curve to
+    /// their shades, see [Base16Scheme::swatches]. Returns the number of imported colors
+    pub fn import_base16_scheme(&self, scheme: &Base16Scheme) -> usize {
+        self.import_swatches(scheme.swatches())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::palette::TAILWIND_NUMBERS;
+
+    const DEFAULT_DARK: &str = r##"
+# Legacy layout, with an unquoted name
+scheme: Default Dark
+author: "Chris Kempson (http://chriskempson.com)"
+base00: "181818"
+base01: "282828"
+base02: "383838"
+base03: "585858"
+base04: "b8b8b8"
+base05: "d8d8d8"
+base06: "e8e8e8"
+base07: "f8f8f8"
+base08: "ab4642"
+base09: "dc9656"
+base0A: "f7ca88"
+base0B: "a1b56c"
+base0C: "86c1b9"
+base0D: "7cafc2"
+base0E: "ba8baf"
+base0F: "a16946" # brown
+"##;
+
+    #[test]
+    fn test_parse() {
+        let scheme = DEFAULT_DARK.parse::<Base16Scheme>().unwrap();
+
+        assert_eq!(scheme.system, Base16System::Base16);
+        assert_eq!(scheme.name, "Default Dark");
+        assert_eq!(scheme.author, "Chris Kempson (http://chriskempson.com)");
+        assert_eq!(scheme.colors.len(), 16);
+        assert_eq!(scheme.colors[0], Srgb8::new(0x18, 0x18, 0x18));
+        assert_eq!(scheme.colors[15], Srgb8::new(0xa1, 0x69, 0x46));
+
+        let swatches = scheme.swatches();
+
+        assert_eq!(swatches.len(), 1 + ACCENTS.len());
+        assert_eq!(swatches[0].1[0], Srgb8::new(0xf8, 0xf8, 0xf8));
+        assert_eq!(swatches[1], ("red".to_string(), vec![scheme.colors[8]]));
+
+        let imported = Palette::default();
+
+        assert_eq!(imported.import_base16_scheme(&scheme), 9);
+
+        let missing_color = DEFAULT_DARK.replace("base0A: \"f7ca88\"", "");
+
+        assert!(missing_color.parse::<Base16Scheme>().is_err());
+        assert!("system: \"base32\"".parse::<Base16Scheme>().is_err());
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let palette = Palette::new();
+        palette.add_new_color_hue(30.);
+        palette.add_new_color_hue(250.);
+
+        let options = Base16Options {
+            system: Base16System::Base24,
+            variant: TerminalTheme::Light,
+            neutral: None,
+            accent_shade: 5,
+        };
+
+        let mut scheme = palette.to_base16_scheme(&options);
+        scheme.name = "Quoted \"name\" \\ #1".to_string();
+
+        let yaml = scheme.to_string();
+
+        assert!(yaml.starts_with("system: \"base24\"\nname: \"Quoted \\\"name\\\" \\\\ #1\"\n"));
+        assert!(yaml.contains("variant: \"light\"\npalette:\n  base00: \"#"));
+        assert!(yaml.contains("  base17: \"#"));
+        assert_eq!(yaml.parse::<Base16Scheme>().unwrap(), scheme);
+
+        let imported = Palette::default();
+
+        assert_eq!(imported.import_base16_scheme(&scheme), 9);
+
+        let swatches = scheme
+            .swatches()
+            .into_iter()
+            .map(|(name, shades)| {
+                let shades = shades.into_iter().map(|shade| RGBA::from(shade).to_hex());
+
+                (name, shades.collect::<Vec<_>>())
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(imported.sheet_hex(), swatches);
+    }
+
+    #[test]
+    fn test_base16_colors() {
+        // 11 shades of a hue from light to dark
+        let ramp = |name: &str, h: f32, c: f32| {
+            let shades = (0..TAILWIND_NUMBERS.len())
+                .map(|idx| {
+                    Srgb8::from(Oklch {
+                        l: 0.97 - idx as f32 * 0.08,
+                        c,
+                        h,
+                    })
+                    .into()
+                })
+                .collect::<Vec<RGBA>>();
+
+            (name.to_string(), shades)
+        };
+
+        let swatches = [
+            ramp("red", 25., 0.15),
+            ramp("slate", 250., 0.02),
+            ramp("blue", 260., 0.12),
+            ramp("zinc", 0., 0.),
+        ];
+
+        let colors = base16_colors(&swatches, &Base16Options::default());
+
+        assert_eq!(colors.len(), 16);
+        // The least colorful color is the neutral, from darkest to lightest
+        assert_eq!(colors[0], swatches[3].1[10].rgb());
+        assert_eq!(colors[7], swatches[3].1[0].rgb());
+        assert_eq!(colors[8], swatches[0].1[5].rgb());
+        assert_eq!(colors[13], swatches[2].1[5].rgb());
+
+        let options = Base16Options {
+            system: Base16System::Base24,
+            neutral: Some("slate".to_string()),
+            accent_shade: 4,
+            ..Default::default()
+        };
+        let colors = base16_colors(&swatches, &options);
+
+        assert_eq!(colors.len(), 24);
+        assert_eq!(colors[0], swatches[1].1[10].rgb());
+        assert_eq!(colors[8], swatches[0].1[4].rgb());
+        assert_eq!(colors[15], swatches[0].1[6].rgb());
+        assert_eq!(colors[18], swatches[0].1[3].rgb());
+        assert!(Oklch::from(colors[17]).l < Oklch::from(colors[16]).l);
+        assert!(Oklch::from(colors[16]).l < Oklch::from(colors[0]).l);
+    }
+}
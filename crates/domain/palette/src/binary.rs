@@ -0,0 +1,66 @@
+//! Helpers for the big endian binary palette formats of Adobe
+
+/// Reads big endian values from a byte slice, returning `None` when it runs out of bytes
+pub(crate) struct BinaryReader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> BinaryReader<'a> {
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    pub fn bytes(&mut self, count: usize) -> Option<&'a [u8]> {
+        if count > self.bytes.len() {
+            return None;
+        }
+
+        let (bytes, rest) = self.bytes.split_at(count);
+        self.bytes = rest;
+
+        Some(bytes)
+    }
+
+    pub fn u16(&mut self) -> Option<u16> {
+        Some(u16::from_be_bytes(self.bytes(2)?.try_into().ok()?))
+    }
+
+    pub fn u32(&mut self) -> Option<u32> {
+        Some(u32::from_be_bytes(self.bytes(4)?.try_into().ok()?))
+    }
+
+    pub fn f32(&mut self) -> Option<f32> {
+        Some(f32::from_be_bytes(self.bytes(4)?.try_into().ok()?))
+    }
+
+    /// A UTF-16 string of `length` code units, including the null terminator
+    pub fn utf16(&mut self, length: usize) -> Option<String> {
+        let units = (0..length)
+            .map(|_| self.u16())
+            .collect::<Option<Vec<_>>>()?;
+
+        String::from_utf16(&units)
+            .ok()
+            .map(|string| string.trim_end_matches('\0').to_string())
+    }
+}
+
+/// The UTF-16 code units of a string, including the null terminator
+pub(crate) fn utf16_units(string: &str) -> Vec<u16> {
+    string.encode_utf16().chain([0]).collect()
+}
+
+pub(crate) fn write_utf16(bytes: &mut Vec<u8>, units: &[u16]) {
+    for unit in units {
+        bytes.extend(unit.to_be_bytes());
+    }
+}
+
+/// An 8 bit channel from a channel in the range 0..1
+pub(crate) fn unit_to_u8(value: f32) -> u8 {
+    (value.clamp(0., 1.) * 255.).round() as u8
+}
@@ -0,0 +1,227 @@
+//!
+//!  # Design tokens
+//!
+//! Export and import of palettes as W3C Design Tokens Community Group (DTCG) JSON, as used by
+//! Style Dictionary and Tokens Studio.
+//!
+//! Every color is a group of color tokens, one per shade, e.g. `color.teal.500`.
+//!
+//! https://design-tokens.github.io/community-group/format/
+//!
+
+use crate::palette::{shade_name, Palette};
+use color::contrast::RGBA;
+use color::conversion::Srgb8;
+use serde_json::{json, Map, Value};
+
+/// A group of color tokens found in a design tokens file
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColorGroup {
+    /// The path of the group, joined by `-`, e.g. `brand-primary` for `color.brand.primary`
+    pub name: String,
+    /// The colors of the group, in the order of the file
+    pub shades: Vec<Srgb8>,
+}
+
+impl Palette {
+    /// All colors and their alpha scales as DTCG color tokens, e.g. `color.teal.500` and
+    /// `color.teal-a.500`. Wide gamut shades are exported as their sRGB fallbacks
+    pub fn to_design_tokens(&self) -> Value {
+        let descriptions = self
+            .colors
+            .lock_ref()
+            .iter()
+            .flat_map(|color| {
+                let name = color.name.get_cloned();
+                let curve_name = self
+                    .sampling_curves
+                    .lock_ref()
+                    .get(&color.sampling_curve_id.get())
+                    .map(|curve| curve.name.get_cloned())
+                    .unwrap_or_default();
+
+                [
+                    format!(
+                        "{name}, hue {:.0} sampled in {} along {curve_name}",
+                        color.hue.get(),
+                        color.color_space.get()
+                    ),
+                    format!(
+                        "Alpha scale of {name} against {}",
+                        self.alpha_background.get()
+                    ),
+                ]
+            })
+            .collect::<Vec<_>>();
+
+        let groups = self
+            .srgb_swatches(|name| name.to_string())
+            .into_iter()
+            .zip(descriptions)
+            .map(|((name, swatch), description)| {
+                let mut group = Map::new();
+                group.insert("$description".to_string(), description.into());
+
+                for (idx, shade) in swatch.into_iter().enumerate() {
+                    group.insert(
+                        shade_name(idx),
+                        json!({ "$type": "color", "$value": shade.to_hex() }),
+                    );
+                }
+
+                (name, Value::Object(group))
+            })
+            .collect::<Map<_, _>>();
+
+        json!({ "color": groups })
+    }
+
+    /// Import every group of opaque color tokens as a new color, fitting a sampling curve to
+    /// its shades like [Palette::import_dwind_color]. Returns the number of imported colors
+    pub fn import_design_tokens(&self, tokens: &Value) -> usize {
+        self.import_swatches(
+            color_groups(tokens)
+                .into_iter()
+                .map(|group| (group.name, group.shades))
+                .collect(),
+        )
+    }
+}
+
+/// Find all groups of opaque color tokens. Groups with translucent colors, such as alpha scales,
+/// are skipped, as are references and tokens of other types
+pub fn color_groups(tokens: &Value) -> Vec<ColorGroup> {
+    let mut groups = vec![];
+    collect_color_groups(tokens, &mut vec![], None, &mut groups);
+
+    groups
+}
+
+fn collect_color_groups(
+    group: &Value,
+    path: &mut Vec<String>,
+    inherited_type: Option<&str>,
+    groups: &mut Vec<ColorGroup>,
+) {
+    let Value::Object(group) = group else {
+        return;
+    };
+
+    // Groups may declare the type of all their tokens
+    let group_type = group
+        .get("$type")
+        .and_then(Value::as_str)
+        .or(inherited_type);
+
+    let mut shades = vec![];
+    let mut translucent = false;
+
+    for (key, child) in group.iter().filter(|(key, _)| !key.starts_with('$')) {
+        if child.get("$value").is_none() {
+            path.push(key.clone());
+            collect_color_groups(child, path, group_type, groups);
+            path.pop();
+            continue;
+        }
+
+        let token_type = child.get("$type").and_then(Value::as_str).or(group_type);
+
+        if token_type == Some("color") {
+            if let Some(color) = token_color(&child["$value"]) {
+                translucent |= !color.is_opaque();
+                shades.push(color.rgb());
+            }
+        }
+    }
+
+    // Tokens at the top level have no group to name the color after
+    if !shades.is_empty() && !translucent && !path.is_empty() {
+        // The top level `color` group only namespaces the colors
+        let name = match path.as_slice() {
+            [root, rest @ ..] if root == "color" && !rest.is_empty() => rest.join("-"),
+            path => path.join("-"),
+        };
+
+        groups.push(ColorGroup { name, shades });
+    }
+}
+
+/// The color of a token value, either a hex string or an object with a `hex` fallback
+fn token_color(value: &Value) -> Option<RGBA> {
+    let hex = match value {
+        Value::String(hex) => hex.as_str(),
+        Value::Object(color) => color.get("hex")?.as_str()?,
+        _ => return None,
+    };
+
+    let color = hex.parse::<RGBA>().ok()?;
+
+    // The hex fallback of an object value has no alpha
+    match value.get("alpha").and_then(Value::as_f64) {
+        Some(alpha) => Some(color.with_alpha((alpha * 255.).round() as u8)),
+        None => Some(color),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_export() {
+        let palette = Palette::test_palette();
+
+        let tokens = palette.to_design_tokens();
+        let rows = palette.to_color_rows();
+
+        assert_eq!(tokens["color"]["teal"]["50"]["$type"], "color");
+        assert_eq!(tokens["color"]["teal"]["50"]["$value"], rows[0][0].to_hex());
+        assert!(tokens["color"]["teal"]["$description"]
+            .as_str()
+            .unwrap()
+            .starts_with("teal, hue 180 sampled in HSV along "));
+        assert!(tokens["color"]["teal-a"]["950"]["$value"].is_string());
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let palette = Palette::test_palette();
+
+        let tokens = palette.to_design_tokens();
+        let imported = Palette::default();
+
+        // The alpha scales are translucent, so only the shades are imported
+        assert_eq!(imported.import_design_tokens(&tokens), 2);
+        assert_eq!(imported.sheet_hex(), palette.sheet_hex());
+    }
+
+    #[test]
+    fn test_color_groups() {
+        let tokens = json!({
+            "color": {
+                "$type": "color",
+                "brand": {
+                    "primary": {
+                        "100": { "$value": "#eff6ff" },
+                        "200": { "$value": { "colorSpace": "srgb", "components": [0, 0, 0], "hex": "#000000" } },
+                        "300": { "$value": "{color.brand.primary.100}" },
+                    },
+                    "overlay": {
+                        "100": { "$value": "#000000" },
+                        "200": { "$value": "#00000080" },
+                    },
+                },
+                "spacing": { "$type": "dimension", "small": { "$value": "4px" } },
+            },
+            "accent": { "$type": "color", "$value": "#ff0000" },
+        });
+
+        assert_eq!(
+            color_groups(&tokens),
+            vec![ColorGroup {
+                name: "brand-primary".to_string(),
+                shades: vec![Srgb8::new(0xef, 0xf6, 0xff), Srgb8::new(0, 0, 0)],
+            }]
+        );
+    }
+}
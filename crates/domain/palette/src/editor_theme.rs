@@ -0,0 +1,496 @@
+//!
+//!  # Editor themes
+//!
+//! Maps palette shades to the semantic scopes of code editors: the background, foreground,
+//! selection and line numbers, syntax like keywords, strings and comments, and the colors of
+//! diagnostics. Like terminal schemes, the default mapping picks the palette color closest in
+//! hue to each scope, and the least colorful palette color for the neutral scopes.
+//!
+//! Themes export as a VS Code `color-theme.json` and a Neovim Lua colorscheme.
+//!
+
+use crate::palette::Palette;
+use crate::terminal_scheme::{
+    closest_in_hue, color_hues, least_colorful, scaled_shade, ShadeRef, TerminalTheme,
+};
+use color::contrast::RGBA;
+use serde_json::{json, Map};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// A semantic scope of an editor theme
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum EditorScope {
+    Background,
+    Foreground,
+    /// The background of selected text
+    Selection,
+    LineNumber,
+    Comment,
+    Keyword,
+    String,
+    /// Numbers and other literals, like `true`
+    Number,
+    Function,
+    Type,
+    Error,
+    Warning,
+    Info,
+    Hint,
+}
+
+impl EditorScope {
+    pub const ALL: [EditorScope; 14] = [
+        EditorScope::Background,
+        EditorScope::Foreground,
+        EditorScope::Selection,
+        EditorScope::LineNumber,
+        EditorScope::Comment,
+        EditorScope::Keyword,
+        EditorScope::String,
+        EditorScope::Number,
+        EditorScope::Function,
+        EditorScope::Type,
+        EditorScope::Error,
+        EditorScope::Warning,
+        EditorScope::Info,
+        EditorScope::Hint,
+    ];
+
+    /// The OKLCH hue the scope is usually colored in, `None` for the neutral scopes
+    fn hue(&self) -> Option<f32> {
+        match self {
+            EditorScope::Background
+            | EditorScope::Foreground
+            | EditorScope::Selection
+            | EditorScope::LineNumber
+            | EditorScope::Comment => None,
+            EditorScope::Keyword => Some(328.4),
+            EditorScope::String => Some(142.5),
+            EditorScope::Number => Some(55.),
+            EditorScope::Function => Some(264.1),
+            EditorScope::Type => Some(109.8),
+            EditorScope::Error => Some(29.2),
+            EditorScope::Warning => Some(80.),
+            EditorScope::Info => Some(264.1),
+            EditorScope::Hint => Some(194.8),
+        }
+    }
+
+    /// The shade of the scope by its index in [crate::palette::TAILWIND_NUMBERS]
+    fn shade(&self, theme: TerminalTheme) -> usize {
+        match (theme, self) {
+            (TerminalTheme::Dark, EditorScope::Background) => 10,
+            (TerminalTheme::Dark, EditorScope::Foreground) => 1,
+            (TerminalTheme::Dark, EditorScope::Selection) => 8,
+            (TerminalTheme::Dark, EditorScope::LineNumber) => 6,
+            (TerminalTheme::Dark, EditorScope::Comment) => 5,
+            (TerminalTheme::Dark, _) => 4,
+            (TerminalTheme::Light, EditorScope::Background) => 0,
+            (TerminalTheme::Light, EditorScope::Foreground) => 9,
+            (TerminalTheme::Light, EditorScope::Selection) => 2,
+            (TerminalTheme::Light, EditorScope::LineNumber) => 4,
+            (TerminalTheme::Light, EditorScope::Comment) => 5,
+            (TerminalTheme::Light, _) => 7,
+        }
+    }
+}
+
+impl Display for EditorScope {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EditorScope::Background => write!(f, "Background"),
+            EditorScope::Foreground => write!(f, "Foreground"),
+            EditorScope::Selection => write!(f, "Selection"),
+            EditorScope::LineNumber => write!(f, "Line numbers"),
+            EditorScope::Comment => write!(f, "Comments"),
+            EditorScope::Keyword => write!(f, "Keywords"),
+            EditorScope::String => write!(f, "Strings"),
+            EditorScope::Number => write!(f, "Numbers"),
+            EditorScope::Function => write!(f, "Functions"),
+            EditorScope::Type => write!(f, "Types"),
+            EditorScope::Error => write!(f, "Errors"),
+            EditorScope::Warning => write!(f, "Warnings"),
+            EditorScope::Info => write!(f, "Info"),
+            EditorScope::Hint => write!(f, "Hints"),
+        }
+    }
+}
+
+impl FromStr for EditorScope {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|scope| scope.to_string() == s)
+            .ok_or(())
+    }
+}
+
+/// The default shade of every scope, in the order of [EditorScope::ALL]. Scopes are mapped to
+/// the color closest in OKLCH hue to their usual color, and the neutral scopes to the color
+/// with the least chroma
+pub fn default_mapping(
+    swatches: &[(String, Vec<RGBA>)],
+    theme: TerminalTheme,
+) -> Vec<(EditorScope, ShadeRef)> {
+    let hues = color_hues(swatches);
+    let neutral = least_colorful(&hues);
+
+    EditorScope::ALL
+        .into_iter()
+        .map(|scope| {
+            let color = scope
+                .hue()
+                .and_then(|hue| closest_in_hue(&hues, hue))
+                .unwrap_or(neutral);
+            let shade = scaled_shade(swatches, color, scope.shade(theme));
+
+            (scope, ShadeRef { color, shade })
+        })
+        .collect()
+}
+
+/// The file formats of code editors
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum EditorFormat {
+    #[default]
+    VsCode,
+    Neovim,
+}
+
+impl EditorFormat {
+    pub const ALL: [EditorFormat; 2] = [EditorFormat::VsCode, EditorFormat::Neovim];
+
+    pub fn file_name(&self) -> &'static str {
+        match self {
+            EditorFormat::VsCode => "palettepal-color-theme.json",
+            EditorFormat::Neovim => "palettepal.lua",
+        }
+    }
+}
+
+impl Display for EditorFormat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EditorFormat::VsCode => write!(f, "VS Code"),
+            EditorFormat::Neovim => write!(f, "Neovim"),
+        }
+    }
+}
+
+impl FromStr for EditorFormat {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|format| format.to_string() == s)
+            .ok_or(())
+    }
+}
+
+/// The TextMate scopes VS Code colors syntax by
+const TEXTMATE_SCOPES: [(EditorScope, &[&str]); 6] = [
+    (
+        EditorScope::Comment,
+        &["comment", "punctuation.definition.comment"],
+    ),
+    (
+        EditorScope::Keyword,
+        &["keyword", "storage.type", "storage.modifier"],
+    ),
+    (EditorScope::String, &["string"]),
+    (
+        EditorScope::Number,
+        &[
+            "constant.numeric",
+            "constant.language",
+            "constant.character",
+        ],
+    ),
+    (
+        EditorScope::Function,
+        &["entity.name.function", "support.function"],
+    ),
+    (
+        EditorScope::Type,
+        &["entity.name.type", "entity.name.class", "support.type"],
+    ),
+];
+
+/// The Neovim highlight groups colored by a scope. Tree-sitter and LSP groups link to these
+const NEOVIM_GROUPS: [(&str, EditorScope); 13] = [
+    ("LineNr", EditorScope::LineNumber),
+    ("Comment", EditorScope::Comment),
+    ("Keyword", EditorScope::Keyword),
+    ("Statement", EditorScope::Keyword),
+    ("String", EditorScope::String),
+    ("Constant", EditorScope::Number),
+    ("Number", EditorScope::Number),
+    ("Function", EditorScope::Function),
+    ("Type", EditorScope::Type),
+    ("DiagnosticError", EditorScope::Error),
+    ("DiagnosticWarn", EditorScope::Warning),
+    ("DiagnosticInfo", EditorScope::Info),
+    ("DiagnosticHint", EditorScope::Hint),
+];
+
+/// The colors of an editor theme
+#[derive(Clone, Debug, PartialEq)]
+pub struct EditorTheme {
+    pub name: String,
+    pub theme: TerminalTheme,
+    /// The color of every scope, in the order of [EditorScope::ALL]
+    pub colors: Vec<(EditorScope, RGBA)>,
+}
+
+impl EditorTheme {
+    /// Look up the shades of a mapping. Shades that don't exist are black
+    pub fn from_mapping(
+        name: &str,
+        theme: TerminalTheme,
+        swatches: &[(String, Vec<RGBA>)],
+        mapping: &[(EditorScope, ShadeRef)],
+    ) -> Self {
+        let colors = mapping
+            .iter()
+            .map(|(scope, shade)| {
+                let color = swatches
+                    .get(shade.color)
+                    .and_then(|(_, shades)| shades.get(shade.shade))
+                    .copied()
+                    .unwrap_or(RGBA::opaque(0, 0, 0));
+
+                (*scope, color.rgb().into())
+            })
+            .collect();
+
+        Self {
+            name: name.to_string(),
+            theme,
+            colors,
+        }
+    }
+
+    pub fn color(&self, scope: EditorScope) -> RGBA {
+        self.colors
+            .iter()
+            .find(|(s, _)| *s == scope)
+            .map(|(_, color)| *color)
+            .unwrap_or(RGBA::opaque(0, 0, 0))
+    }
+
+    pub fn export(&self, format: EditorFormat) -> String {
+        match format {
+            EditorFormat::VsCode => self.to_vscode(),
+            EditorFormat::Neovim => self.to_neovim(),
+        }
+    }
+
+    fn hex(&self, scope: EditorScope) -> String {
+        self.color(scope).to_hex()
+    }
+
+    fn theme_type(&self) -> &'static str {
+        match self.theme {
+            TerminalTheme::Dark => "dark",
+            TerminalTheme::Light => "light",
+        }
+    }
+
+    fn to_vscode(&self) -> String {
+        let mut colors = Map::new();
+
+        for (key, scope) in [
+            ("editor.background", EditorScope::Background),
+            ("editor.foreground", EditorScope::Foreground),
+            ("editor.selectionBackground", EditorScope::Selection),
+            ("editorCursor.foreground", EditorScope::Foreground),
+            ("editorLineNumber.foreground", EditorScope::LineNumber),
+            ("editorLineNumber.activeForeground", EditorScope::Foreground),
+            ("editorError.foreground", EditorScope::Error),
+            ("editorWarning.foreground", EditorScope::Warning),
+            ("editorInfo.foreground", EditorScope::Info),
+            ("editorHint.foreground", EditorScope::Hint),
+        ] {
+            colors.insert(key.to_string(), self.hex(scope).into());
+        }
+
+        let token_colors = TEXTMATE_SCOPES
+            .iter()
+            .map(|(scope, textmate_scopes)| {
+                let mut settings = Map::new();
+                settings.insert("foreground".to_string(), self.hex(*scope).into());
+
+                if *scope == EditorScope::Comment {
+                    settings.insert("fontStyle".to_string(), "italic".into());
+                }
+
+                json!({
+                    "name": scope.to_string(),
+                    "scope": textmate_scopes,
+                    "settings": settings,
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let theme = json!({
+            "name": self.name,
+            "type": self.theme_type(),
+            "colors": colors,
+            "tokenColors": token_colors,
+        });
+
+        serde_json::to_string_pretty(&theme).unwrap_or_default()
+    }
+
+    fn to_neovim(&self) -> String {
+        let mut lua = format!(
+            "-- {0}\n-- Save as colors/{0}.lua and load with `:colorscheme {0}`\n\
+             vim.cmd(\"highlight clear\")\n\
+             vim.g.colors_name = \"{0}\"\n\
+             vim.o.background = \"{1}\"\n\n\
+             local set = vim.api.nvim_set_hl\n\n",
+            self.name,
+            self.theme_type()
+        );
+
+        lua += &format!(
+            "set(0, \"Normal\", {{ fg = \"{}\", bg = \"{}\" }})\n",
+            self.hex(EditorScope::Foreground),
+            self.hex(EditorScope::Background)
+        );
+        lua += &format!(
+            "set(0, \"Visual\", {{ bg = \"{}\" }})\n",
+            self.hex(EditorScope::Selection)
+        );
+
+        for (group, scope) in NEOVIM_GROUPS {
+            let italic = match scope {
+                EditorScope::Comment => ", italic = true",
+                _ => "",
+            };
+
+            lua += &format!(
+                "set(0, \"{group}\", {{ fg = \"{}\"{italic} }})\n",
+                self.hex(scope)
+            );
+        }
+
+        for (group, scope) in [
+            ("DiagnosticUnderlineError", EditorScope::Error),
+            ("DiagnosticUnderlineWarn", EditorScope::Warning),
+            ("DiagnosticUnderlineInfo", EditorScope::Info),
+            ("DiagnosticUnderlineHint", EditorScope::Hint),
+        ] {
+            lua += &format!(
+                "set(0, \"{group}\", {{ sp = \"{}\", undercurl = true }})\n",
+                self.hex(scope)
+            );
+        }
+
+        lua
+    }
+}
+
+impl Palette {
+    /// An editor theme with the default mapping of the palette's shades, see
+    /// [default_mapping]. Wide gamut shades are replaced by their sRGB fallbacks
+    pub fn to_editor_theme(&self, theme: TerminalTheme) -> EditorTheme {
+        let swatches = self.to_sheet_swatches();
+        let mapping = default_mapping(&swatches, theme);
+
+        EditorTheme::from_mapping("palettepal", theme, &swatches, &mapping)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use color::conversion::{Oklch, Srgb8};
+    use serde_json::Value;
+
+    /// 11 shades of a hue from light to dark
+    fn ramp(name: &str, h: f32, c: f32) -> (String, Vec<RGBA>) {
+        let shades = (0..11)
+            .map(|idx| {
+                Srgb8::from(Oklch {
+                    l: 0.97 - idx as f32 * 0.08,
+                    c,
+                    h,
+                })
+                .into()
+            })
+            .collect();
+
+        (name.to_string(), shades)
+    }
+
+    #[test]
+    fn test_default_mapping() {
+        let swatches = vec![
+            ramp("green", 145., 0.12),
+            ramp("red", 25., 0.15),
+            ramp("blue", 260., 0.12),
+            ramp("grey", 260., 0.01),
+        ];
+
+        let mapping = default_mapping(&swatches, TerminalTheme::Dark);
+        let shade = |scope: EditorScope| mapping.iter().find(|(s, _)| *s == scope).unwrap().1;
+
+        assert_eq!(mapping.len(), EditorScope::ALL.len());
+        assert_eq!(
+            shade(EditorScope::Background),
+            ShadeRef {
+                color: 3,
+                shade: 10
+            }
+        );
+        assert_eq!(shade(EditorScope::Comment).color, 3);
+        assert_eq!(shade(EditorScope::String), ShadeRef { color: 0, shade: 4 });
+        assert_eq!(shade(EditorScope::Error).color, 1);
+        assert_eq!(shade(EditorScope::Function).color, 2);
+
+        let light = default_mapping(&swatches, TerminalTheme::Light);
+
+        assert_eq!(
+            light[0],
+            (EditorScope::Background, ShadeRef { color: 3, shade: 0 })
+        );
+        assert_eq!(
+            light[6],
+            (EditorScope::String, ShadeRef { color: 0, shade: 7 })
+        );
+    }
+
+    #[test]
+    fn test_exports() {
+        let palette = Palette::new();
+        let theme = palette.to_editor_theme(TerminalTheme::Light);
+        let background = theme.hex(EditorScope::Background);
+
+        let vscode: Value = serde_json::from_str(&theme.export(EditorFormat::VsCode)).unwrap();
+
+        assert_eq!(vscode["name"], "palettepal");
+        assert_eq!(vscode["type"], "light");
+        assert_eq!(vscode["colors"]["editor.background"], background.as_str());
+        assert_eq!(
+            vscode["tokenColors"].as_array().unwrap().len(),
+            TEXTMATE_SCOPES.len()
+        );
+        assert_eq!(vscode["tokenColors"][0]["settings"]["fontStyle"], "italic");
+        assert_eq!(vscode["tokenColors"][2]["scope"][0], "string");
+
+        let neovim = theme.export(EditorFormat::Neovim);
+
+        assert!(
+            neovim.contains("vim.g.colors_name = \"palettepal\"\nvim.o.background = \"light\"\n")
+        );
+        assert!(neovim.contains("set(0, \"Normal\", { fg = \"#"));
+        assert!(neovim.contains(&format!("bg = \"{background}\" }})\n")));
+        assert!(neovim.contains("set(0, \"Comment\", { fg = \"#"));
+        assert!(neovim.contains("undercurl = true })\n"));
+    }
+}
@@ -0,0 +1,211 @@
+//!
+//!  # GIMP palettes
+//!
+//! The `.gpl` palette format of GIMP, which is also read by Inkscape, Krita and Aseprite.
+//! Unlike JASC-PAL, every entry has a name, and the file hints at the number of columns to
+//! show the colors in.
+//!
+//! https://developer.gimp.org/core/standards/gpl/
+//!
+
+use crate::palette::{group_named_colors, shade_name, Palette};
+use color::conversion::Srgb8;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+const HEADER: &str = "GIMP Palette";
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GimpPalette {
+    pub name: String,
+    /// The number of columns to show the colors in, 0 if the file has no hint
+    pub columns: usize,
+    pub entries: Vec<GimpPaletteEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GimpPaletteEntry {
+    pub color: Srgb8,
+    pub name: String,
+}
+
+impl GimpPalette {
+    /// Split the entries into swatches, see [group_named_colors]
+    pub fn swatches(&self) -> Vec<(String, Vec<Srgb8>)> {
+        group_named_colors(
+            self.entries
+                .iter()
+                .map(|entry| (entry.color, entry.name.as_str())),
+            (self.columns > 0).then_some(self.columns),
+        )
+    }
+}
+
+impl Display for GimpPalette {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{HEADER}")?;
+        writeln!(f, "Name: {}", self.name)?;
+
+        if self.columns > 0 {
+            writeln!(f, "Columns: {}", self.columns)?;
+        }
+
+        writeln!(f, "#")?;
+
+        for GimpPaletteEntry { color, name } in &self.entries {
+            writeln!(f, "{:3} {:3} {:3}\t{name}", color.r, color.g, color.b)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for GimpPalette {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = s.lines();
+
+        if lines.next().map(str::trim) != Some(HEADER) {
+            return Err(());
+        }
+
+        let mut palette = GimpPalette::default();
+
+        for line in lines.map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            if let Some(name) = line.strip_prefix("Name:") {
+                palette.name = name.trim().to_string();
+                continue;
+            }
+
+            if let Some(columns) = line.strip_prefix("Columns:") {
+                palette.columns = columns.trim().parse().map_err(|_| ())?;
+                continue;
+            }
+
+            // The channels are usually padded, so the name is whatever follows the third number
+            let mut parts = line.split_whitespace();
+            let mut channel = || parts.next().and_then(|c| c.parse::<u8>().ok()).ok_or(());
+            let color = Srgb8::new(channel()?, channel()?, channel()?);
+            let name = parts.collect::<Vec<_>>().join(" ");
+
+            palette.entries.push(GimpPaletteEntry { color, name });
+        }
+
+        Ok(palette)
+    }
+}
+
+impl Palette {
+    /// All shades as a GIMP palette, named `<color>-<shade>`, with one row per color. Wide gamut
+    /// shades are exported as their sRGB fallbacks, and the alpha scales are left out as the
+    /// format has no alpha
+    pub fn to_gimp_palette(&self) -> String {
+        let mut gimp_palette = GimpPalette {
+            name: "palettepal".to_string(),
+            ..Default::default()
+        };
+
+        for (name, swatch) in self.to_sheet_swatches() {
+            gimp_palette.columns = gimp_palette.columns.max(swatch.len());
+
+            for (idx, shade) in swatch.into_iter().enumerate() {
+                gimp_palette.entries.push(GimpPaletteEntry {
+                    color: shade.rgb(),
+                    name: format!("{name}-{}", shade_name(idx)),
+                });
+            }
+        }
+
+        gimp_palette.to_string()
+    }
+
+    /// Import every swatch of a GIMP palette as a new color, fitting a sampling curve to its
+    /// shades like [Palette::import_dwind_color]. Returns the number of imported colors
+    pub fn import_gimp_palette(&self, gimp_palette: &GimpPalette) -> usize {
+        self.import_swatches(gimp_palette.swatches())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::palette::TAILWIND_NUMBERS;
+
+    #[test]
+    fn test_export() {
+        let palette = Palette::test_palette();
+
+        let gpl = palette.to_gimp_palette();
+        let rows = palette.to_color_rows();
+        let first = rows[0][0];
+
+        assert!(gpl.starts_with("GIMP Palette\nName: palettepal\nColumns: 11\n#\n"));
+        assert!(gpl.contains(&format!(
+            "{:3} {:3} {:3}\tteal-50\n",
+            first.r, first.g, first.b
+        )));
+        assert!(gpl.ends_with("\tpurple-950\n"));
+
+        let parsed = gpl.parse::<GimpPalette>().unwrap();
+
+        assert_eq!(parsed.columns, TAILWIND_NUMBERS.len());
+        assert_eq!(parsed.entries.len(), 2 * TAILWIND_NUMBERS.len());
+        assert_eq!(parsed.entries[0].color, first.rgb());
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let palette = Palette::test_palette();
+
+        let gpl = palette.to_gimp_palette().parse::<GimpPalette>().unwrap();
+        let imported = Palette::default();
+
+        assert_eq!(imported.import_gimp_palette(&gpl), 2);
+        assert_eq!(imported.sheet_hex(), palette.sheet_hex());
+    }
+
+    #[test]
+    fn test_swatches() {
+        let gpl = "GIMP Palette
+Name: Mixed
+Columns: 2
+# A comment
+255   0   0\tred-100
+200   0   0\tred-200
+  0   0 255\tblue-100
+  1   1   1\tUntitled
+  2   2   2
+  3   3   3\tUntitled
+"
+        .parse::<GimpPalette>()
+        .unwrap();
+
+        assert_eq!(gpl.name, "Mixed");
+        assert_eq!(gpl.entries[3].name, "Untitled");
+        assert_eq!(gpl.entries[4].name, "");
+
+        let names = gpl
+            .swatches()
+            .into_iter()
+            .map(|(name, shades)| (name, shades.len()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            names,
+            [
+                ("red".to_string(), 2),
+                ("blue".to_string(), 1),
+                ("color-3".to_string(), 2),
+                ("color-4".to_string(), 1),
+            ]
+        );
+
+        assert!("JASC-PAL\n0100\n".parse::<GimpPalette>().is_err());
+        assert!("GIMP Palette\n300 0 0 red".parse::<GimpPalette>().is_err());
+    }
+}
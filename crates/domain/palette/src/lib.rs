@@ -0,0 +1,17 @@
+pub mod aco;
+pub mod ase;
+pub mod base16;
+mod binary;
+mod bitmap_font;
+pub mod design_tokens;
+pub mod editor_theme;
+pub mod gimp_palette;
+pub mod lospec;
+pub mod palette;
+pub mod palette_color;
+mod pdf;
+pub mod sampling;
+pub mod sampling_curve;
+pub mod swatch_book;
+pub mod swatch_sheet;
+pub mod terminal_scheme;
@@ -0,0 +1,299 @@
+//!
+//!  # Lospec palettes
+//!
+//! The plain color list formats offered by Lospec: `.hex` files with one `rrggbb` color per
+//! line, and paint.net `.txt` palettes with one `aarrggbb` color per line and `;` comments.
+//!
+//! The lists have no structure, so imported colors are clustered into hue families, which each
+//! become a color of the palette.
+//!
+//! https://lospec.com/palette-list
+//!
+
+use crate::palette::Palette;
+use color::contrast::RGBA;
+use color::conversion::{Oklch, Srgb8};
+use std::collections::HashMap;
+
+/// Colors with less OKLCH chroma than this are grouped as greys
+pub(crate) const GREY_CHROMA: f32 = 0.03;
+/// Hue families are split until they span at most this many degrees
+const MAX_FAMILY_SPAN: f32 = 60.;
+/// Hue families are split at gaps in hue larger than this many degrees
+const MAX_HUE_GAP: f32 = 30.;
+
+/// Names of hue families, by the OKLCH hue they are centered on
+const HUE_NAMES: [(f32, &str); 12] = [
+    (25., "red"),
+    (50., "orange"),
+    (90., "yellow"),
+    (130., "lime"),
+    (150., "green"),
+    (180., "teal"),
+    (215., "cyan"),
+    (260., "blue"),
+    (277., "indigo"),
+    (300., "purple"),
+    (325., "magenta"),
+    (355., "pink"),
+];
+
+/// Parse a `.hex` or paint.net `.txt` color list. Colors may be prefixed by `#`, and the alpha of
+/// `aarrggbb` colors is ignored. Returns `None` unless every line is a color or a comment
+pub fn parse_color_list(text: &str) -> Option<Vec<Srgb8>> {
+    let colors = text
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with(';'))
+        .map(|line| {
+            let hex = line.strip_prefix('#').unwrap_or(line);
+
+            // Check the digits before slicing, a multibyte character is not a char boundary
+            if !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+                return None;
+            }
+
+            match hex.len() {
+                6 => hex.parse::<RGBA>().ok(),
+                8 => hex[2..].parse::<RGBA>().ok(),
+                _ => None,
+            }
+            .map(|color| color.rgb())
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    (!colors.is_empty()).then_some(colors)
+}
+
+/// Cluster colors into families of similar hue, named after their hue, e.g. `blue` or `grey`.
+/// Families are ordered by hue with the greys last, and their shades from light to dark
+pub fn hue_families(colors: &[Srgb8]) -> Vec<(String, Vec<Srgb8>)> {
+    let (greys, mut chromatic): (Vec<_>, Vec<_>) = colors
+        .iter()
+        .map(|&color| (color, Oklch::from(color)))
+        .partition(|(_, oklch)| oklch.c < GREY_CHROMA);
+
+    chromatic.sort_by(|(_, a), (_, b)| a.h.total_cmp(&b.h));
+
+    // Hue is circular, so start after the largest gap to not split a family at 0°
+    let count = chromatic.len();
+
+    if let Some(start) =
+        (0..count).max_by(|&a, &b| hue_gap(&chromatic, a).total_cmp(&hue_gap(&chromatic, b)))
+    {
+        chromatic.rotate_left((start + 1) % count);
+    }
+
+    let first_hue = chromatic.first().map(|(_, oklch)| oklch.h).unwrap_or(0.);
+    let unwrapped = chromatic
+        .into_iter()
+        .map(|(color, oklch)| (color, first_hue + (oklch.h - first_hue).rem_euclid(360.)))
+        .collect::<Vec<_>>();
+
+    let mut families = vec![];
+    split_families(&unwrapped, &mut families);
+
+    let mut families = families
+        .into_iter()
+        .map(|family| {
+            let mean_hue = family.iter().map(|(_, hue)| hue).sum::<f32>() / family.len() as f32;
+            let colors = family
+                .into_iter()
+                .map(|(color, _)| color)
+                .collect::<Vec<_>>();
+
+            (mean_hue.rem_euclid(360.), colors)
+        })
+        .collect::<Vec<_>>();
+
+    families.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+
+    let mut named_families = families
+        .into_iter()
+        .map(|(hue, colors)| (hue_name(hue).to_string(), colors))
+        .collect::<Vec<_>>();
+
+    if !greys.is_empty() {
+        named_families.push((
+            "grey".to_string(),
+            greys.into_iter().map(|(color, _)| color).collect(),
+        ));
+    }
+
+    let mut name_counts = HashMap::<String, usize>::new();
+
+    for (name, shades) in &mut named_families {
+        shades.sort_by(|a, b| Oklch::from(*b).l.total_cmp(&Oklch::from(*a).l));
+
+        let count = name_counts.entry(name.clone()).or_default();
+        *count += 1;
+
+        if *count > 1 {
+            *name = format!("{name}-{count}");
+        }
+    }
+
+    named_families
+}
+
+/// The gap in hue from the color at `idx` to the next one, wrapping around
+fn hue_gap(colors: &[(Srgb8, Oklch)], idx: usize) -> f32 {
+    if colors.len() == 1 {
+        return 360.;
+    }
+
+    let next = &colors[(idx + 1) % colors.len()].1;
+
+    (next.h - colors[idx].1.h).rem_euclid(360.)
+}
+
+/// Split colors sorted by unwrapped hue at their largest gap, until every family is compact
+fn split_families(colors: &[(Srgb8, f32)], families: &mut Vec<Vec<(Srgb8, f32)>>) {
+    let Some(((_, first), (_, last))) = colors.first().zip(colors.last()) else {
+        return;
+    };
+
+    let largest_gap = colors
+        .windows(2)
+        .enumerate()
+        .map(|(idx, pair)| (idx, pair[1].1 - pair[0].1))
+        .max_by(|(_, a), (_, b)| a.total_cmp(b));
+
+    match largest_gap {
+        Some((idx, gap)) if last - first > MAX_FAMILY_SPAN || gap > MAX_HUE_GAP => {
+            split_families(&colors[..=idx], families);
+            split_families(&colors[idx + 1..], families);
+        }
+        _ => families.push(colors.to_vec()),
+    }
+}
+
+/// The name of the closest hue in [HUE_NAMES]
+fn hue_name(hue: f32) -> &'static str {
+    let distance = |center: f32| {
+        let difference = (hue - center).rem_euclid(360.);
+        difference.min(360. - difference)
+    };
+
+    HUE_NAMES
+        .iter()
+        .min_by(|(a, _), (b, _)| distance(*a).total_cmp(&distance(*b)))
+        .map(|(_, name)| *name)
+        .unwrap_or("color")
+}
+
+impl Palette {
+    /// All shades as a Lospec `.hex` file. Wide gamut shades are exported as their sRGB
+    /// fallbacks, and the alpha scales are left out as the format has no alpha
+    pub fn to_hex_list(&self) -> String {
+        self.srgb_shades()
+            .map(|shade| format!("{}\n", shade.rgb().to_hex().trim_start_matches('#')))
+            .collect()
+    }
+
+    /// All shades as a paint.net palette, see [Palette::to_hex_list]
+    pub fn to_paint_net_palette(&self) -> String {
+        let shades = self.srgb_shades().collect::<Vec<_>>();
+        let mut palette = format!(
+            ";paint.net Palette File\n;Palette Name: palettepal\n;Colors: {}\n",
+            shades.len()
+        );
+
+        for shade in shades {
+            palette += &format!(
+                "FF{}\n",
+                shade.rgb().to_hex().trim_start_matches('#').to_uppercase()
+            );
+        }
+
+        palette
+    }
+
+    /// Import a list of colors, with a new color for each hue family, see [hue_families].
+    /// Returns the number of imported colors
+    pub fn import_color_list(&self, colors: &[Srgb8]) -> usize {
+        self.import_swatches(hue_families(colors))
+    }
+
+    fn srgb_shades(&self) -> impl Iterator<Item = RGBA> {
+        self.to_sheet_swatches()
+            .into_iter()
+            .flat_map(|(_, swatch)| swatch)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::palette::TAILWIND_NUMBERS;
+
+    #[test]
+    fn test_exports() {
+        let palette = Palette::new();
+        let first = palette.to_color_rows()[0][0];
+
+        let hex = palette.to_hex_list();
+
+        assert_eq!(hex.lines().count(), TAILWIND_NUMBERS.len());
+        assert_eq!(hex.lines().next(), Some(&first.to_hex()[1..]));
+        assert_eq!(parse_color_list(&hex).unwrap()[0], first.rgb());
+
+        let paint_net = palette.to_paint_net_palette();
+
+        assert!(paint_net.starts_with(";paint.net Palette File\n"));
+        assert!(paint_net.contains(";Colors: 11\n"));
+        assert_eq!(
+            parse_color_list(&paint_net).unwrap(),
+            parse_color_list(&hex).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_parse_color_list() {
+        assert_eq!(
+            parse_color_list("ff0044\n#00FF00\n\n;comment\n800000ff\n"),
+            Some(vec![
+                Srgb8::new(0xff, 0, 0x44),
+                Srgb8::new(0, 0xff, 0),
+                Srgb8::new(0, 0, 0xff)
+            ])
+        );
+        assert_eq!(parse_color_list(""), None);
+        assert_eq!(parse_color_list("ff0044\n{\"name\": 1}"), None);
+        assert_eq!(parse_color_list("ff0044\naébbccd"), None);
+        assert_eq!(parse_color_list("ff0044\n+f+f+f"), None);
+    }
+
+    #[test]
+    fn test_hue_families() {
+        let colors = [
+            Srgb8::new(0xfe, 0xe2, 0xe2),
+            Srgb8::new(0xdc, 0x26, 0x26),
+            Srgb8::new(0x7f, 0x1d, 0x1d),
+            Srgb8::new(0xdb, 0xea, 0xfe),
+            Srgb8::new(0x25, 0x63, 0xeb),
+            Srgb8::new(0x1e, 0x3a, 0x8a),
+            Srgb8::new(0xff, 0xff, 0xff),
+            Srgb8::new(0x00, 0x00, 0x00),
+            // Close in hue to the other reds
+            Srgb8::new(0xff, 0x00, 0x20),
+        ];
+
+        let families = hue_families(&colors);
+        let names = families
+            .iter()
+            .map(|(name, shades)| (name.as_str(), shades.len()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(names, [("red", 4), ("blue", 3), ("grey", 2)]);
+        assert_eq!(families[1].1[0], Srgb8::new(0xdb, 0xea, 0xfe));
+        assert_eq!(
+            families[2].1,
+            [Srgb8::new(255, 255, 255), Srgb8::new(0, 0, 0)]
+        );
+
+        let imported = Palette::default();
+
+        assert_eq!(imported.import_color_list(&colors), 3);
+    }
+}
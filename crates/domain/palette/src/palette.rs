@@ -0,0 +1,850 @@
+use crate::palette_color::{ColorSpace, PaletteColor};
+use crate::sampling_curve::SamplingCurve;
+use color::contrast::RGBA;
+use color::conversion::{Hsv, Srgb8};
+use color::css::CssColorSyntax;
+use color::gamut::Gamut;
+use dwind_build::colors::{Color, ColorFile};
+use futures_signals::signal::Mutable;
+use futures_signals::signal_map::MutableBTreeMap;
+use futures_signals::signal_vec::MutableVec;
+use glam::Vec2;
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use uuid::Uuid;
+
+pub const TAILWIND_NUMBERS: [u32; 11] = [50, 100, 200, 300, 400, 500, 600, 700, 800, 900, 950];
+
+/// A palette of colors and the curves their shades are sampled along.
+///
+/// The fields are `Mutable`s the website binds its editors to. Native users read them with
+/// `get`, `get_cloned` and `lock_ref`, and the exports only read the current values. They
+/// serialize as their values, so a `.palettepal` file is plain data.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Palette {
+    pub colors: MutableVec<PaletteColor>,
+    pub sampling_curves: MutableBTreeMap<Uuid, SamplingCurve>,
+    /// The gamut colors are sampled in for exports
+    #[serde(default)]
+    pub gamut: Mutable<Gamut>,
+    /// The background the alpha scales are computed against
+    #[serde(default = "default_alpha_background")]
+    pub alpha_background: Mutable<Srgb8>,
+    /// The syntax colors are written in by the stylesheet exports
+    #[serde(default)]
+    pub css_syntax: Mutable<CssColorSyntax>,
+}
+
+/// A shade of an imported color that is not a hex color
+#[derive(Clone, Debug, PartialEq)]
+pub struct InvalidShade {
+    pub shade: u32,
+    pub value: String,
+}
+
+impl Display for InvalidShade {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Failed to parse shade {}: '{}'", self.shade, self.value)
+    }
+}
+
+impl std::error::Error for InvalidShade {}
+
+/// A color that samples along a curve the palette does not have
+#[derive(Clone, Debug, PartialEq)]
+pub struct MissingSamplingCurve {
+    pub color: String,
+    pub curve_id: Uuid,
+}
+
+impl Display for MissingSamplingCurve {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "The color '{}' samples along the missing curve {}",
+            self.color, self.curve_id
+        )
+    }
+}
+
+impl std::error::Error for MissingSamplingCurve {}
+
+/// The mean hue of the shades, weighted by their chroma. Greys have no hue, and the near black
+/// and white ends of a ramp have little chroma, so they do not pull the hue towards red
+fn swatch_hue(shades: &[Hsv]) -> f32 {
+    let (sin, cos) = shades.iter().fold((0., 0.), |(sin, cos), hsv| {
+        let chroma = hsv.s * hsv.v;
+        let (hue_sin, hue_cos) = hsv.h.to_radians().sin_cos();
+
+        (sin + chroma * hue_sin, cos + chroma * hue_cos)
+    });
+
+    f32::atan2(sin, cos).to_degrees().rem_euclid(360.)
+}
+
+/// The name of the shade at `idx` of a swatch, its tailwind number where there is one
+pub fn shade_name(idx: usize) -> String {
+    shade_number(idx).to_string()
+}
+
+/// The number of the shade at `idx` of a swatch, see [shade_name]
+pub fn shade_number(idx: usize) -> u32 {
+    TAILWIND_NUMBERS.get(idx).copied().unwrap_or(idx as u32)
+}
+
+/// Group colors named `<color>-<shade>` into swatches, e.g. for palette formats without groups.
+///
+/// Consecutive colors of the same color name are grouped. Other colors are grouped by row if
+/// `row_length` is known, and all together otherwise
+pub fn group_named_colors<'a>(
+    colors: impl IntoIterator<Item = (Srgb8, &'a str)>,
+    row_length: Option<usize>,
+) -> Vec<(String, Vec<Srgb8>)> {
+    let mut swatches: Vec<(String, Vec<Srgb8>)> = vec![];
+    let mut unnamed_count = 0;
+
+    for (color, name) in colors {
+        let color_name = name
+            .rsplit_once('-')
+            .map(|(color_name, _shade)| color_name.to_string());
+
+        let continues_swatch = match (swatches.last(), &color_name) {
+            (Some((last_name, _)), Some(color_name)) => last_name == color_name,
+            (Some(_), None) if unnamed_count > 0 => {
+                row_length.is_none_or(|row_length| unnamed_count % row_length != 0)
+            }
+            _ => false,
+        };
+
+        if color_name.is_none() {
+            unnamed_count += 1;
+        } else {
+            unnamed_count = 0;
+        }
+
+        if continues_swatch {
+            swatches.last_mut().unwrap().1.push(color);
+        } else {
+            let name = color_name.unwrap_or_else(|| format!("color-{}", swatches.len() + 1));
+            swatches.push((name, vec![color]));
+        }
+    }
+
+    swatches
+}
+
+/// A color name usable in class and variable names, e.g. `Light Blue` becomes `light-blue`
+fn kebab_case_name(name: &str) -> String {
+    name.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+fn default_alpha_background() -> Mutable<Srgb8> {
+    Mutable::new(Srgb8::new(255, 255, 255))
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Self {
+            colors: Default::default(),
+            sampling_curves: Default::default(),
+            gamut: Default::default(),
+            alpha_background: default_alpha_background(),
+            css_syntax: Default::default(),
+        }
+    }
+}
+
+impl Palette {
+    pub fn new() -> Self {
+        let colors = MutableVec::new_with_values(vec![PaletteColor::new(180.)]);
+
+        let sampling_curves = MutableBTreeMap::new();
+        sampling_curves
+            .lock_mut()
+            .insert_cloned(Uuid::nil(), SamplingCurve::tailwind_happy());
+
+        sampling_curves
+            .lock_mut()
+            .insert_cloned(Uuid::from_u128(1), SamplingCurve::tailwind_diagonal());
+
+        sampling_curves
+            .lock_mut()
+            .insert_cloned(Uuid::from_u128(2), SamplingCurve::pixelart_5());
+
+        Self {
+            colors,
+            sampling_curves,
+            gamut: Default::default(),
+            alpha_background: default_alpha_background(),
+            css_syntax: Default::default(),
+        }
+    }
+
+    /// Import a DWIND color as a new color, see [Palette::import_swatch]. Nothing is imported
+    /// if any of its shades is not a hex color
+    pub fn import_dwind_color(&self, color: Color) -> Result<(), InvalidShade> {
+        let mut shades: Vec<_> = color.shades.into_iter().collect();
+        shades.sort_by_key(|(shade, _)| *shade);
+
+        let shades = shades
+            .into_iter()
+            .map(|(shade, value)| {
+                value
+                    .parse::<Srgb8>()
+                    .map_err(|_| InvalidShade { shade, value })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        self.import_swatch(color.name, shades);
+
+        Ok(())
+    }
+
+    /// Import every swatch as a new color, see [Palette::import_swatch]. Returns the number of
+    /// imported colors
+    pub fn import_swatches(&self, swatches: Vec<(String, Vec<Srgb8>)>) -> usize {
+        let count = swatches.len();
+
+        for (name, shades) in swatches {
+            self.import_swatch(name, shades);
+        }
+
+        count
+    }
+
+    /// Add a color sampled along a new curve through the given shades, keeping their order. The
+    /// curve is fitted in HSV, at the mean hue of the shades weighted by their chroma
+    pub fn import_swatch(&self, name: String, shades: impl IntoIterator<Item = Srgb8>) {
+        let curve_id = Uuid::new_v4();
+        let curve = SamplingCurve::new();
+        let shades: Vec<_> = shades.into_iter().map(Hsv::from).collect();
+
+        curve.name.set(format!("{name}_curve"));
+        curve
+            .curve
+            .set(shades.iter().map(|hsv| Vec2::new(hsv.s, hsv.v)).collect());
+
+        let hue = swatch_hue(&shades);
+
+        self.sampling_curves
+            .lock_mut()
+            .insert_cloned(curve_id, curve);
+
+        let palette_color = PaletteColor::new(hue);
+        palette_color.name.set(name);
+        palette_color.sampling_curve_id.set(curve_id);
+        palette_color.color_space.set(ColorSpace::HSV);
+
+        self.colors.lock_mut().push_cloned(palette_color);
+    }
+
+    /// Check that every color samples along a curve of the palette. Deserializing does not check
+    /// this, and sampling a color without its curve panics
+    pub fn validate(&self) -> Result<(), MissingSamplingCurve> {
+        let sampling_curves = self.sampling_curves.lock_ref();
+
+        for color in self.colors.lock_ref().iter() {
+            let curve_id = color.sampling_curve_id.get();
+
+            if !sampling_curves.contains_key(&curve_id) {
+                return Err(MissingSamplingCurve {
+                    color: color.name.get_cloned(),
+                    curve_id,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn add_new_curve(&self) -> Uuid {
+        let id = Uuid::new_v4();
+        let new_curve = SamplingCurve::new();
+        self.sampling_curves.lock_mut().insert_cloned(id, new_curve);
+
+        id
+    }
+
+    pub fn add_new_color(&self) {
+        let new_color =
+            PaletteColor::new((self.colors.lock_mut().len() as f32 * 26.).rem_euclid(360.));
+        self.colors.lock_mut().push_cloned(new_color);
+    }
+
+    pub fn add_new_color_hue(&self, hue: f32) {
+        let new_color = PaletteColor::new(hue.rem_euclid(360.));
+        self.colors.lock_mut().push_cloned(new_color);
+    }
+
+    pub fn remove_hue(&self, hue: f32) {
+        let mut colors = self.colors.lock_mut();
+
+        if colors.is_empty() {
+            return;
+        }
+
+        colors.replace_cloned(
+            colors
+                .iter()
+                .filter(|&v| (v.hue.get() - hue).abs() >= 2.)
+                .cloned()
+                .collect::<Vec<_>>(),
+        );
+    }
+
+    /// The sRGB shades of every color, one row per color. This is the layout of the PNG export
+    pub fn to_color_rows(&self) -> Vec<Vec<RGBA>> {
+        self.to_sheet_swatches()
+            .into_iter()
+            .map(|(_, shades)| shades)
+            .collect()
+    }
+
+    /// Every shade by its name, e.g. `red-500`, followed by the alpha shades, e.g. `red-a-500`
+    pub fn to_named_shades(&self) -> Vec<(String, RGBA)> {
+        self.srgb_swatches(|name| name.to_string())
+            .into_iter()
+            .flat_map(|(name, swatch)| {
+                swatch
+                    .into_iter()
+                    .enumerate()
+                    .map(move |(idx, shade)| (format!("{name}-{}", shade_name(idx)), shade))
+            })
+            .collect()
+    }
+
+    /// The sRGB shades of every color followed by its alpha scale, named `{name}` and
+    /// `{name}-a`. Wide gamut shades are replaced by their sRGB fallbacks
+    pub(crate) fn srgb_swatches(
+        &self,
+        color_name: impl Fn(&str) -> String,
+    ) -> Vec<(String, Vec<RGBA>)> {
+        let gamut = self.gamut.get();
+        let alpha_background = self.alpha_background.get();
+        let mut swatches = vec![];
+
+        for color in self.colors.lock_ref().iter() {
+            let name = color_name(&color.name.get_cloned());
+            let samples = color.samples(&self.sampling_curves);
+
+            swatches.push((name.clone(), color.srgb_colors(&samples, gamut)));
+            swatches.push((
+                format!("{name}-a"),
+                color.alpha_colors(&samples, gamut, alpha_background),
+            ));
+        }
+
+        swatches
+    }
+
+    /// The sRGB shades of every color by name, without the alpha scales. This is what swatch
+    /// sheets and the swatch file formats without alpha hold
+    pub fn to_sheet_swatches(&self) -> Vec<(String, Vec<RGBA>)> {
+        let gamut = self.gamut.get();
+
+        self.colors
+            .lock_ref()
+            .iter()
+            .map(|color| {
+                let samples = color.samples(&self.sampling_curves);
+
+                (color.name.get_cloned(), color.srgb_colors(&samples, gamut))
+            })
+            .collect()
+    }
+
+    /// All colors followed by their alpha scales, as a DWIND color file
+    pub fn to_dwind_color_file(&self) -> ColorFile {
+        let gamut = self.gamut.get();
+        let alpha_background = self.alpha_background.get();
+        let mut colors = vec![];
+
+        for color in self.colors.lock_ref().iter() {
+            colors.extend(color.clone().into_dwind_color(&self.sampling_curves, gamut));
+            colors.extend(color.clone().into_dwind_alpha_color(
+                &self.sampling_curves,
+                gamut,
+                alpha_background,
+            ));
+        }
+
+        ColorFile { colors }
+    }
+
+    /// All shades as a JASC palette, using the sRGB fallbacks of wide gamut shades
+    pub fn to_jasc_pal(&self) -> String {
+        let mut palette = jascpal::Palette::new();
+
+        for color in self.to_color_rows().into_iter().flatten() {
+            palette
+                .colors_mut()
+                .push(jascpal::Color::new(color.r, color.g, color.b));
+        }
+
+        palette.to_string()
+    }
+
+    /// A tailwind v3 `tailwind.config.js` extending the theme colors with all shades and their
+    /// alpha versions. Tailwind v3 has no wide gamut support, so shades are exported as their
+    /// sRGB fallbacks
+    pub fn to_tailwind_config(&self) -> String {
+        let mut colors = String::new();
+
+        for (name, swatch) in self.srgb_swatches(kebab_case_name) {
+            colors += &format!("        '{name}': {{\n");
+
+            for (idx, shade) in swatch.into_iter().enumerate() {
+                colors += &format!("          {}: '{}',\n", shade_name(idx), shade.to_hex());
+            }
+
+            colors += "        },\n";
+        }
+
+        format!(
+            "/** @type {{import('tailwindcss').Config}} */\nmodule.exports = {{\n  theme: {{\n    extend: {{\n      colors: {{\n{colors}      }},\n    }},\n  }},\n}};\n"
+        )
+    }
+
+    /// A tailwind v4 `@theme` block declaring a `--color-{name}-{shade}` variable for all shades
+    /// and their alpha versions. Wide gamut palettes use `color()`, which tailwind v4 supports
+    pub fn to_tailwind_theme(&self) -> String {
+        let gamut = self.gamut.get();
+        let alpha_background = self.alpha_background.get();
+        let mut variables = String::new();
+
+        for color in self.colors.lock_ref().iter() {
+            let name = kebab_case_name(&color.name.get_cloned());
+            let samples = color.samples(&self.sampling_curves);
+
+            for (idx, shade) in color
+                .colors_in_gamut(&samples, gamut)
+                .into_iter()
+                .enumerate()
+            {
+                let value = if gamut == Gamut::Srgb {
+                    shade.srgb_fallback().to_hex()
+                } else {
+                    shade.to_css()
+                };
+
+                variables += &format!("  --color-{name}-{}: {value};\n", shade_name(idx));
+            }
+
+            for (idx, shade) in color
+                .alpha_colors(&samples, gamut, alpha_background)
+                .into_iter()
+                .enumerate()
+            {
+                variables += &format!(
+                    "  --color-{name}-a-{}: {};\n",
+                    shade_name(idx),
+                    shade.to_hex()
+                );
+            }
+        }
+
+        format!("@import \"tailwindcss\";\n\n@theme {{\n{variables}}}\n")
+    }
+
+    /// CSS custom properties for all shades, e.g. `--red-500`, and their alpha versions, e.g.
+    /// `--red-a-500`, in the palette's [CssColorSyntax]. Wide gamut palettes declare sRGB
+    /// fallbacks first, and override them with `color()` where it is supported
+    pub fn to_css_variables(&self) -> String {
+        let gamut = self.gamut.get();
+        let syntax = self.css_syntax.get();
+        let mut fallbacks = String::new();
+        let mut wide_colors = String::new();
+
+        for (name, swatch) in self.srgb_swatches(kebab_case_name) {
+            for (idx, shade) in swatch.into_iter().enumerate() {
+                fallbacks += &format!(
+                    "  --{name}-{}: {};\n",
+                    shade_name(idx),
+                    syntax.format(shade)
+                );
+            }
+        }
+
+        // Alpha scales are computed from the sRGB shades, so they have no wide gamut version
+        for color in self.colors.lock_ref().iter() {
+            let name = kebab_case_name(&color.name.get_cloned());
+            let samples = color.samples(&self.sampling_curves);
+
+            for (idx, shade) in color
+                .colors_in_gamut(&samples, gamut)
+                .into_iter()
+                .enumerate()
+            {
+                wide_colors += &format!("    --{name}-{}: {};\n", shade_name(idx), shade.to_css());
+            }
+        }
+
+        let mut css = format!(":root {{\n{fallbacks}}}\n");
+
+        if gamut != Gamut::Srgb {
+            css += &format!(
+                "\n@supports (color: color({} 0 0 0)) {{\n  :root {{\n{wide_colors}  }}\n}}\n",
+                gamut.css_name()
+            );
+        }
+
+        css
+    }
+
+    /// A SCSS map of all colors and their alpha scales, with a nested map of shades for each
+    /// color, e.g. `map.get($palette, "red", 500)`. Shades are written in the palette's
+    /// [CssColorSyntax], using the sRGB fallbacks of wide gamut shades
+    pub fn to_scss_map(&self) -> String {
+        let syntax = self.css_syntax.get();
+        let mut colors = String::new();
+
+        for (name, swatch) in self.srgb_swatches(kebab_case_name) {
+            colors += &format!("  \"{name}\": (\n");
+
+            for (idx, shade) in swatch.into_iter().enumerate() {
+                colors += &format!("    {}: {},\n", shade_name(idx), syntax.format(shade));
+            }
+
+            colors += "  ),\n";
+        }
+
+        format!("$palette: (\n{colors});\n")
+    }
+
+    /// LESS variables for all shades, e.g. `@red-500`, and their alpha versions, e.g.
+    /// `@red-a-500`. Shades are written in the palette's [CssColorSyntax], using the sRGB
+    /// fallbacks of wide gamut shades
+    pub fn to_less_variables(&self) -> String {
+        let syntax = self.css_syntax.get();
+        let mut less = String::new();
+
+        for (name, swatch) in self.srgb_swatches(kebab_case_name) {
+            for (idx, shade) in swatch.into_iter().enumerate() {
+                less += &format!("@{name}-{}: {};\n", shade_name(idx), syntax.format(shade));
+            }
+        }
+
+        less
+    }
+}
+
+#[cfg(test)]
+impl Palette {
+    /// A teal and a purple color. 8 bit shades keep their HSV hues exactly, so they survive a
+    /// round trip through the swatch formats unchanged
+    pub(crate) fn test_palette() -> Self {
+        let palette = Palette::new();
+        let colors = [("teal", 180.), ("purple", 300.)].map(|(name, hue)| {
+            let color = PaletteColor::new(hue);
+            color.name.set(name.to_string());
+            color
+        });
+
+        palette.colors.lock_mut().replace_cloned(colors.to_vec());
+
+        palette
+    }
+
+    /// The hex values of the shades of every color, see [Palette::to_sheet_swatches]
+    pub(crate) fn sheet_hex(&self) -> Vec<(String, Vec<String>)> {
+        self.to_sheet_swatches()
+            .into_iter()
+            .map(|(name, shades)| (name, shades.iter().map(RGBA::to_hex).collect()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_import_dwind_color_rejects_invalid_shades() {
+        let palette = Palette::new();
+        let count = palette.colors.lock_ref().len();
+
+        let color = Color {
+            name: "red".to_string(),
+            shades: [(50, "#fef2f2".to_string()), (500, "+f0000".to_string())].into(),
+        };
+
+        assert_eq!(
+            palette.import_dwind_color(color),
+            Err(InvalidShade {
+                shade: 500,
+                value: "+f0000".to_string()
+            })
+        );
+        assert_eq!(palette.colors.lock_ref().len(), count);
+
+        let color = Color {
+            name: "red".to_string(),
+            shades: [(50, "#fef2f2".to_string()), (500, "#ef4444".to_string())].into(),
+        };
+
+        assert_eq!(palette.import_dwind_color(color), Ok(()));
+        assert_eq!(palette.colors.lock_ref().len(), count + 1);
+    }
+
+    /// Exporting an imported swatch gives back its shades, in order and with their hue
+    #[test]
+    fn test_import_swatch_round_trip() {
+        let palette = Palette::new();
+        let swatches = palette.to_sheet_swatches();
+        let imported = Palette::default();
+
+        imported.import_swatches(
+            swatches
+                .iter()
+                .map(|(name, shades)| {
+                    (
+                        name.clone(),
+                        shades.iter().map(|shade| shade.rgb()).collect(),
+                    )
+                })
+                .collect(),
+        );
+
+        let hex = |swatches: Vec<(String, Vec<RGBA>)>| {
+            swatches
+                .into_iter()
+                .map(|(_, shades)| shades.iter().map(RGBA::to_hex).collect::<Vec<_>>())
+                .collect::<Vec<_>>()
+        };
+
+        let exported = hex(imported.to_sheet_swatches());
+
+        assert_eq!(exported[0][1], "#c2f2f2");
+        assert_eq!(exported, hex(swatches));
+    }
+
+    #[test]
+    fn test_swatch_hue_ignores_greys() {
+        let shades = ["#ffffff", "#c2f2f2", "#1a6b6b", "#000000", "#808080"]
+            .map(|hex| Hsv::from(hex.parse::<Srgb8>().unwrap()));
+
+        assert!((swatch_hue(&shades) - 180.).abs() < 0.01);
+        assert_eq!(swatch_hue(&shades[3..]), 0.);
+    }
+
+    #[test]
+    fn test_validate() {
+        let palette = Palette::test_palette();
+
+        assert_eq!(palette.validate(), Ok(()));
+
+        let curve_id = Uuid::from_u128(42);
+        palette.colors.lock_ref()[1].sampling_curve_id.set(curve_id);
+
+        assert_eq!(
+            palette.validate(),
+            Err(MissingSamplingCurve {
+                color: "purple".to_string(),
+                curve_id
+            })
+        );
+    }
+
+    #[test]
+    fn test_new_palette_samples_tailwind_shades() {
+        let palette = Palette::new();
+        let color = palette.colors.lock_ref()[0].clone();
+        let samples = color.samples(&palette.sampling_curves);
+
+        assert_eq!(color.colors_u8(&samples).len(), TAILWIND_NUMBERS.len());
+        assert!(color
+            .colors_u8(&samples)
+            .iter()
+            .all(|shade| shade.is_opaque()));
+    }
+
+    #[test]
+    fn test_exports() {
+        let palette = Palette::test_palette();
+
+        let jasc = palette.to_jasc_pal();
+        let mut lines = jasc.lines();
+
+        assert_eq!(lines.next(), Some("JASC-PAL"));
+        assert_eq!(lines.nth(1), Some("22"));
+
+        let css = palette.to_css_variables();
+
+        assert!(css.contains("--teal-50: #"));
+        assert!(css.contains("--teal-a-950: #"));
+        assert!(!css.contains("@supports"));
+
+        let rows = palette.to_color_rows();
+
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].len(), TAILWIND_NUMBERS.len());
+
+        let color_file = palette.to_dwind_color_file();
+        let names: Vec<_> = color_file.colors.iter().map(|c| c.name.as_str()).collect();
+
+        assert_eq!(names, ["teal", "teal-a", "purple", "purple-a"]);
+        assert_eq!(color_file.colors[0].shades[&50], rows[0][0].to_hex());
+
+        let shades = palette.to_named_shades();
+
+        assert_eq!(shades.len(), 4 * TAILWIND_NUMBERS.len());
+        assert_eq!(shades[0], ("teal-50".to_string(), rows[0][0]));
+        assert_eq!(shades[11].0, "teal-a-50");
+    }
+
+    /// Every sRGB export of a wide gamut palette uses the same sRGB fallbacks
+    #[test]
+    fn test_wide_gamut_srgb_exports_agree() {
+        let palette = Palette::test_palette();
+        palette.gamut.set(Gamut::DisplayP3);
+
+        let rows = palette.to_color_rows();
+        let shade = rows[0][5];
+        let color = palette.colors.lock_ref()[0].clone();
+
+        assert_ne!(
+            rows[0],
+            color.colors_u8(&color.samples(&palette.sampling_curves))
+        );
+
+        let jasc = palette.to_jasc_pal();
+
+        assert_eq!(
+            jasc.lines().nth(3 + 5),
+            Some(format!("{} {} {}", shade.r, shade.g, shade.b).as_str())
+        );
+        assert_eq!(
+            palette.to_dwind_color_file().colors[0].shades[&500],
+            shade.to_hex()
+        );
+        assert!(palette.to_css_variables().contains(&format!(
+            "  --teal-500: {};\n",
+            palette.css_syntax.get().format(shade)
+        )));
+        assert!(palette
+            .to_named_shades()
+            .contains(&("teal-500".to_string(), shade)));
+    }
+
+    /// The alpha shades of a wide gamut palette reproduce the exported sRGB shades
+    #[test]
+    fn test_wide_gamut_alpha_scale() {
+        let palette = Palette::test_palette();
+        palette.gamut.set(Gamut::DisplayP3);
+        palette.alpha_background.set(Srgb8::new(0x10, 0x20, 0x30));
+
+        let shades = palette.srgb_swatches(|name| name.to_string());
+        let (_, teal) = &shades[0];
+        let (_, teal_alpha) = &shades[1];
+
+        for (shade, alpha_shade) in teal.iter().zip(teal_alpha) {
+            assert_eq!(
+                alpha_shade.over(palette.alpha_background.get()),
+                shade.rgb()
+            );
+        }
+    }
+
+    #[test]
+    fn test_tailwind_exports() {
+        let palette = Palette::new();
+        palette.colors.lock_ref()[0]
+            .name
+            .set("Light Teal".to_string());
+
+        let rows = palette.to_color_rows();
+        let config = palette.to_tailwind_config();
+
+        assert!(config.starts_with("/** @type"));
+        assert!(config.contains("        'light-teal': {\n"));
+        assert!(config.contains(&format!("          50: '{}',\n", rows[0][0].to_hex())));
+        assert!(config.contains("        'light-teal-a': {\n"));
+        assert!(config.contains("          950: '#"));
+
+        let theme = palette.to_tailwind_theme();
+
+        assert!(theme.contains(&format!(
+            "  --color-light-teal-50: {};\n",
+            rows[0][0].to_hex()
+        )));
+        assert!(theme.contains("  --color-light-teal-a-950: #"));
+        assert_eq!(
+            theme.matches("--color-").count(),
+            2 * TAILWIND_NUMBERS.len()
+        );
+
+        palette.gamut.set(Gamut::DisplayP3);
+
+        assert!(palette
+            .to_tailwind_theme()
+            .contains("  --color-light-teal-50: color(display-p3 "));
+    }
+
+    #[test]
+    fn test_stylesheet_exports() {
+        let palette = Palette::new();
+        palette.colors.lock_ref()[0]
+            .name
+            .set("Primary Teal".to_string());
+        palette.css_syntax.set(CssColorSyntax::Rgb);
+
+        let rows = palette.to_color_rows();
+        let first_shade = CssColorSyntax::Rgb.format(rows[0][0]);
+
+        let css = palette.to_css_variables();
+
+        assert!(css.contains(&format!("  --primary-teal-50: {first_shade};\n")));
+        assert!(css.contains("  --primary-teal-a-950: rgb("));
+
+        palette.gamut.set(Gamut::DisplayP3);
+
+        assert!(palette
+            .to_css_variables()
+            .contains("    --primary-teal-50: color(display-p3 "));
+
+        palette.gamut.set(Gamut::Srgb);
+
+        let scss = palette.to_scss_map();
+
+        assert!(scss.starts_with("$palette: (\n  \"primary-teal\": (\n"));
+        assert!(scss.contains(&format!("    50: {first_shade},\n")));
+        assert!(scss.contains("  \"primary-teal-a\": (\n"));
+        assert!(scss.ends_with("  ),\n);\n"));
+
+        let less = palette.to_less_variables();
+
+        assert!(less.starts_with(&format!("@primary-teal-50: {first_shade};\n")));
+        assert!(less.contains("@primary-teal-a-950: rgb("));
+        assert_eq!(less.lines().count(), 2 * TAILWIND_NUMBERS.len());
+    }
+
+    #[test]
+    fn test_serde_round_trip() {
+        let palette = Palette::new();
+        palette.add_new_color_hue(42.);
+        palette.alpha_background.set(Srgb8::new(0x10, 0x10, 0x10));
+
+        let json = serde_json::to_string(&palette).unwrap();
+        let loaded: Palette = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(loaded.colors.lock_ref().len(), 2);
+        assert_eq!(loaded.colors.lock_ref()[1].hue.get(), 42.);
+        assert_eq!(loaded.sampling_curves.lock_ref().len(), 3);
+        assert_eq!(loaded.alpha_background.get(), Srgb8::new(0x10, 0x10, 0x10));
+    }
+
+    /// Files saved before gamuts and alpha scales were added still load
+    #[test]
+    fn test_missing_fields_use_defaults() {
+        let loaded: Palette =
+            serde_json::from_str(r#"{"colors": [], "sampling_curves": {}}"#).unwrap();
+
+        assert_eq!(loaded.gamut.get(), Gamut::Srgb);
+        assert_eq!(loaded.alpha_background.get(), Srgb8::new(0xff, 0xff, 0xff));
+        assert_eq!(loaded.css_syntax.get(), CssColorSyntax::Hex);
+    }
+}
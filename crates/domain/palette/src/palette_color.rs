@@ -0,0 +1,497 @@
+use crate::palette::shade_number;
+use crate::sampling::{
+    hsl_colors_u8, hsluv_colors_u8, hsv_colors_u8, lch_colors_u8, oklch_colors_u8, static_sample,
+    static_sample_signal, SamplingRect, LCH_MAX_CHROMA, OKLCH_MAX_CHROMA,
+};
+use crate::sampling_curve::SamplingCurve;
+use color::alpha::alpha_scale;
+use color::contrast::RGBA;
+use color::conversion::{Hsl, Hsluv, Hsv, Lch, Oklch, Srgb, Srgb8};
+use color::gamut::{Gamut, GamutColor};
+use dwind_build::colors::Color;
+use futures_signals::map_ref;
+use futures_signals::signal::{Mutable, Signal, SignalExt};
+use futures_signals::signal_map::{MutableBTreeMap, SignalMapExt};
+use glam::Vec2;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use uuid::Uuid;
+
+pub const DWIND_CURVE: [(f32, f32); 11] = [
+    (0., 1.),
+    (0.2, 0.9),
+    (0.3, 0.8),
+    (0.4, 0.67),
+    (0.47, 0.57),
+    (0.55, 0.47),
+    (0.62, 0.4),
+    (0.7, 0.36),
+    (0.8, 0.3),
+    (0.9, 0.2),
+    (1., 0.),
+];
+
+pub const DWIND_CURVE2: [(f32, f32); 11] = [
+    (0., 1.),
+    (0.2, 0.95),
+    (0.3, 0.92),
+    (0.4, 0.86),
+    (0.47, 0.8),
+    (0.55, 0.72),
+    (0.62, 0.63),
+    (0.7, 0.53),
+    (0.8, 0.4),
+    (0.9, 0.2),
+    (1., 0.),
+];
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, Default)]
+pub enum ColorSpace {
+    #[default]
+    HSV,
+    HSL,
+    /// Chroma by lightness planes in OKLCH
+    OKLCH,
+    /// Chroma by lightness planes in CIELAB LCh
+    LCH,
+    /// Saturation by lightness planes in HSLuv
+    HSLUV,
+}
+
+impl ColorSpace {
+    /// Sample colors in this color space, at the given hue and color plane angle
+    pub fn colors_u8(&self, hue: f32, angle: f32, sample_coords: &Vec<Vec2>) -> Vec<RGBA> {
+        match self {
+            ColorSpace::HSV => hsv_colors_u8(hue, angle, sample_coords),
+            ColorSpace::HSL => hsl_colors_u8(hue, angle, sample_coords),
+            ColorSpace::OKLCH => oklch_colors_u8(hue, angle, sample_coords),
+            ColorSpace::LCH => lch_colors_u8(hue, angle, sample_coords),
+            ColorSpace::HSLUV => hsluv_colors_u8(hue, angle, sample_coords),
+        }
+    }
+
+    /// Sample colors in this color space, in the target gamut. The HSV and HSL planes span the
+    /// RGB cube of the gamut, OKLCH and LCh are gamut mapped into it. HSLuv is defined by the
+    /// sRGB gamut, so it is the same in all gamuts
+    pub fn colors_in_gamut(
+        &self,
+        hue: f32,
+        angle: f32,
+        sample_coords: &[Vec2],
+        gamut: Gamut,
+    ) -> Vec<GamutColor> {
+        sample_coords
+            .iter()
+            .map(|shade| {
+                // Shift the hue based on the color plane angle
+                let h = (hue - angle * shade.y).rem_euclid(360.);
+
+                match self {
+                    ColorSpace::HSV => {
+                        let Srgb { r, g, b } = Hsv {
+                            h,
+                            s: shade.x,
+                            v: shade.y,
+                        }
+                        .into();
+                        GamutColor::new(gamut, r, g, b)
+                    }
+                    ColorSpace::HSL => {
+                        let Srgb { r, g, b } = Hsl {
+                            h,
+                            s: shade.x,
+                            l: shade.y,
+                        }
+                        .into();
+                        GamutColor::new(gamut, r, g, b)
+                    }
+                    ColorSpace::OKLCH => gamut.map_oklch(Oklch {
+                        l: shade.y,
+                        c: shade.x * OKLCH_MAX_CHROMA,
+                        h,
+                    }),
+                    ColorSpace::LCH => gamut.map_oklch(Oklch::from(Srgb::from(Lch {
+                        l: shade.y * 100.,
+                        c: shade.x * LCH_MAX_CHROMA,
+                        h,
+                    }))),
+                    ColorSpace::HSLUV => gamut.from_srgb(Srgb::from(Hsluv {
+                        h,
+                        s: shade.x * 100.,
+                        l: shade.y * 100.,
+                    })),
+                }
+            })
+            .collect()
+    }
+
+    /// Sample colors in this color space, in the target gamut as sRGB. Wide gamut colors are
+    /// replaced by their sRGB fallbacks
+    pub fn srgb_colors(
+        &self,
+        hue: f32,
+        angle: f32,
+        sample_coords: &[Vec2],
+        gamut: Gamut,
+    ) -> Vec<RGBA> {
+        self.colors_in_gamut(hue, angle, sample_coords, gamut)
+            .into_iter()
+            .map(|color| color.srgb_fallback().into())
+            .collect()
+    }
+
+    /// The value of the `u_space` uniform used by the color space shaders
+    pub fn shader_id(&self) -> i32 {
+        match self {
+            ColorSpace::HSV => 0,
+            ColorSpace::HSL => 1,
+            ColorSpace::OKLCH => 2,
+            ColorSpace::LCH => 3,
+            ColorSpace::HSLUV => 4,
+        }
+    }
+
+    /// Names of the x and y axis of the sampling plane
+    pub fn axis_labels(&self) -> (&'static str, &'static str) {
+        match self {
+            ColorSpace::HSV => ("Saturation", "Value"),
+            ColorSpace::HSL | ColorSpace::HSLUV => ("Saturation", "Lightness"),
+            ColorSpace::OKLCH | ColorSpace::LCH => ("Chroma", "Lightness"),
+        }
+    }
+}
+
+impl Display for ColorSpace {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ColorSpace::HSV => write!(f, "HSV"),
+            ColorSpace::HSL => write!(f, "HSL"),
+            ColorSpace::OKLCH => write!(f, "OKLCH"),
+            ColorSpace::LCH => write!(f, "LCH"),
+            ColorSpace::HSLUV => write!(f, "HSLuv"),
+        }
+    }
+}
+
+impl FromStr for ColorSpace {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "HSV" => Ok(Self::HSV),
+            "HSL" => Ok(Self::HSL),
+            "OKLCH" => Ok(Self::OKLCH),
+            "LCH" => Ok(Self::LCH),
+            "HSLuv" => Ok(Self::HSLUV),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct PaletteColor {
+    pub name: Mutable<String>,
+    pub hue: Mutable<f32>,
+    pub color_space: Mutable<ColorSpace>,
+    pub sampling_rect: Mutable<SamplingRect>,
+    pub sampling_curve_id: Mutable<Uuid>,
+    pub cake_type: Mutable<CakeType>,
+    pub color_plane_angle: Mutable<f32>,
+}
+
+impl PaletteColor {
+    pub fn new(hue: f32) -> Self {
+        Self {
+            name: Mutable::new(format!("some-color-{hue}")),
+            hue: Mutable::new(hue),
+            color_space: Default::default(),
+            sampling_rect: Default::default(),
+            sampling_curve_id: Uuid::nil().into(),
+            cake_type: Default::default(),
+            color_plane_angle: Default::default(),
+        }
+    }
+
+    /// Returns the points at which the color should be sampled in its color plane.
+    /// Coordinates in this list are in the color space (hsv) coordinates
+    ///
+    /// The color curve is transformed to the sampling rects coordinate space, i.e.
+    /// you can have a rotated or smaller rectangle to apply the full curve inside
+    pub fn samples_signal(
+        &self,
+        sampling_curves: MutableBTreeMap<Uuid, SamplingCurve>,
+    ) -> impl Signal<Item = Vec<Vec2>> + 'static {
+        let sampling_rect = self.sampling_rect.clone();
+        let sampling_curve = self
+            .sampling_curve_id
+            .signal()
+            .map(move |sampling_curve_key| {
+                sampling_curves
+                    .signal_map_cloned()
+                    .key_cloned(sampling_curve_key)
+            })
+            .flatten();
+
+        let out = sampling_curve.map(move |curve: Option<SamplingCurve>| {
+            let matrices_signal = sampling_rect
+                .signal_cloned()
+                .map(|m| m.matrices_signal())
+                .flatten();
+
+            static_sample_signal(
+                matrices_signal,
+                curve.expect("Missing sampling curve").curve.signal_cloned(),
+            )
+        });
+
+        out.flatten()
+    }
+
+    pub fn samples(&self, sampling_curves: &MutableBTreeMap<Uuid, SamplingCurve>) -> Vec<Vec2> {
+        let sampling_rect = self.sampling_rect.get_cloned();
+        let curve = sampling_curves
+            .lock_ref()
+            .get(&self.sampling_curve_id.get_cloned())
+            .expect("Missing sampling curve")
+            .clone();
+
+        let matrices = sampling_rect.matrices();
+        static_sample(&matrices, &curve.curve.get_cloned())
+    }
+
+    pub fn colors_u8_signal(
+        &self,
+        sampling_curves: &MutableBTreeMap<Uuid, SamplingCurve>,
+    ) -> impl Signal<Item = Vec<RGBA>> {
+        map_ref! {
+            let shades = self.samples_signal(sampling_curves.clone()),
+            let space = self.color_space.signal(),
+            let angle = self.color_plane_angle.signal(),
+            let hue = self.hue.signal() => {
+                space.colors_u8(*hue, *angle, shades)
+            }
+        }
+    }
+
+    pub fn colors_u8(&self, sample_coords: &Vec<Vec2>) -> Vec<RGBA> {
+        self.color_space.get().colors_u8(
+            self.hue.get(),
+            self.color_plane_angle.get(),
+            sample_coords,
+        )
+    }
+
+    /// The alpha scale of the sRGB colors against the background, see [alpha_scale] and
+    /// [PaletteColor::srgb_colors]
+    pub fn alpha_colors_signal(
+        &self,
+        sampling_curves: &MutableBTreeMap<Uuid, SamplingCurve>,
+        gamut_signal: impl Signal<Item = Gamut>,
+        background_signal: impl Signal<Item = Srgb8>,
+    ) -> impl Signal<Item = Vec<RGBA>> {
+        map_ref! {
+            let shades = self.samples_signal(sampling_curves.clone()),
+            let space = self.color_space.signal(),
+            let angle = self.color_plane_angle.signal(),
+            let hue = self.hue.signal(),
+            let gamut = gamut_signal,
+            let background = background_signal => {
+                alpha_scale(space.srgb_colors(*hue, *angle, shades, *gamut), *background)
+            }
+        }
+    }
+
+    /// The alpha scale of the sRGB colors against the background, so that every alpha shade
+    /// over the background gives the shade of [PaletteColor::srgb_colors]
+    pub fn alpha_colors(
+        &self,
+        sample_coords: &[Vec2],
+        gamut: Gamut,
+        background: Srgb8,
+    ) -> Vec<RGBA> {
+        alpha_scale(self.srgb_colors(sample_coords, gamut), background)
+    }
+
+    /// The sampled colors in the target gamut as sRGB, with wide gamut colors replaced by their
+    /// sRGB fallbacks. These are the shades of every sRGB export
+    pub fn srgb_colors(&self, sample_coords: &[Vec2], gamut: Gamut) -> Vec<RGBA> {
+        self.color_space.get().srgb_colors(
+            self.hue.get(),
+            self.color_plane_angle.get(),
+            sample_coords,
+            gamut,
+        )
+    }
+
+    pub fn colors_in_gamut(&self, sample_coords: &[Vec2], gamut: Gamut) -> Vec<GamutColor> {
+        self.color_space.get().colors_in_gamut(
+            self.hue.get(),
+            self.color_plane_angle.get(),
+            sample_coords,
+            gamut,
+        )
+    }
+
+    pub fn is_tailwind_signal(
+        &self,
+        sampling_curves: MutableBTreeMap<Uuid, SamplingCurve>,
+    ) -> impl Signal<Item = bool> + 'static {
+        self.sampling_curve_id
+            .signal_cloned()
+            .map(move |sampling_curve_key| {
+                sampling_curves
+                    .lock_ref()
+                    .get(&sampling_curve_key)
+                    .expect("Missing sampling curve")
+                    .curve
+                    .signal_ref(|v| v.len() == 11)
+            })
+            .flatten()
+    }
+}
+
+impl PaletteColor {
+    /// The sRGB shades as a DWIND color, see [PaletteColor::srgb_colors]
+    pub fn into_dwind_color(
+        self,
+        sampling_curves: &MutableBTreeMap<Uuid, SamplingCurve>,
+        gamut: Gamut,
+    ) -> Option<Color> {
+        let samples = self.samples(sampling_curves);
+        let color = self
+            .srgb_colors(&samples, gamut)
+            .into_iter()
+            .enumerate()
+            .map(|(idx, color)| (shade_number(idx), color.to_hex()));
+
+        let mut shades = HashMap::new();
+
+        for color in color {
+            shades.insert(color.0, color.1);
+        }
+
+        Some(Color {
+            name: self.name.get_cloned(),
+            shades,
+        })
+    }
+
+    /// The alpha scale as a DWIND color, named like `red-a`
+    pub fn into_dwind_alpha_color(
+        self,
+        sampling_curves: &MutableBTreeMap<Uuid, SamplingCurve>,
+        gamut: Gamut,
+        background: Srgb8,
+    ) -> Option<Color> {
+        let samples = self.samples(sampling_curves);
+        let shades = self
+            .alpha_colors(&samples, gamut, background)
+            .into_iter()
+            .enumerate()
+            .map(|(idx, color)| (shade_number(idx), color.to_hex()))
+            .collect();
+
+        Some(Color {
+            name: format!("{}-a", self.name.get_cloned()),
+            shades,
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, Eq, PartialEq, Default)]
+pub enum CakeType {
+    #[default]
+    Cylinder,
+    Brick,
+}
+
+impl Display for CakeType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CakeType::Cylinder => write!(f, "Cylinder"),
+            CakeType::Brick => write!(f, "Brick"),
+        }
+    }
+}
+
+impl FromStr for CakeType {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Cylinder" => Ok(Self::Cylinder),
+            "Brick" => Ok(Self::Brick),
+            _ => Err(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::palette::Palette;
+
+    const SPACES: [ColorSpace; 5] = [
+        ColorSpace::HSV,
+        ColorSpace::HSL,
+        ColorSpace::OKLCH,
+        ColorSpace::LCH,
+        ColorSpace::HSLUV,
+    ];
+
+    #[test]
+    fn test_color_space_from_str() {
+        for space in SPACES {
+            assert_eq!(
+                space
+                    .to_string()
+                    .parse::<ColorSpace>()
+                    .map(|s| s.shader_id()),
+                Ok(space.shader_id())
+            );
+        }
+    }
+
+    /// The plane runs from white at the top left to black at the bottom in every color space
+    #[test]
+    fn test_plane_corners() {
+        let corners = vec![Vec2::new(0., 1.), Vec2::new(0., 0.), Vec2::new(1., 0.)];
+
+        for space in SPACES {
+            let colors = space.colors_u8(200., 0., &corners);
+
+            assert_eq!(colors[0], RGBA::opaque(255, 255, 255), "{space}");
+            assert_eq!(colors[1], RGBA::opaque(0, 0, 0), "{space}");
+            assert_eq!(colors[2], RGBA::opaque(0, 0, 0), "{space}");
+        }
+    }
+
+    /// Shades past the tailwind numbers are numbered by their index
+    #[test]
+    fn test_dwind_color_with_more_than_11_shades() {
+        let palette = Palette::default();
+        let shades = (0..13).map(|idx| Srgb8::new(255 - idx * 20, 0, 0));
+
+        palette.import_swatch("red".to_string(), shades);
+
+        let color = palette.colors.lock_ref()[0].clone();
+        let dwind = color
+            .clone()
+            .into_dwind_color(&palette.sampling_curves, Gamut::Srgb)
+            .unwrap();
+        let alpha = color
+            .into_dwind_alpha_color(
+                &palette.sampling_curves,
+                Gamut::Srgb,
+                Srgb8::new(255, 255, 255),
+            )
+            .unwrap();
+
+        assert_eq!(dwind.shades.len(), 13);
+        assert_eq!(dwind.shades[&950], "#370000");
+        assert_eq!(dwind.shades[&12], "#0f0000");
+        assert!(alpha.shades.contains_key(&11));
+    }
+}
@@ -0,0 +1,102 @@
+//! A minimal PDF writer for pages of vector graphics and text in the standard Helvetica fonts
+
+/// The font resource of regular text
+pub(crate) const REGULAR_FONT: &str = "F1";
+/// The font resource of bold text
+pub(crate) const BOLD_FONT: &str = "F2";
+
+/// Write a PDF document with a page per content stream. Content streams can use the fonts
+/// [REGULAR_FONT] and [BOLD_FONT]
+pub(crate) fn write_pdf(page_width: f32, page_height: f32, pages: &[Vec<u8>]) -> Vec<u8> {
+    // The catalog, page tree and fonts come first, then a page and its contents for every page
+    let page_ids = (0..pages.len()).map(|idx| 5 + 2 * idx).collect::<Vec<_>>();
+    let kids = page_ids
+        .iter()
+        .map(|id| format!("{id} 0 R"))
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let mut objects = vec![
+        b"<< /Type /Catalog /Pages 2 0 R >>".to_vec(),
+        format!("<< /Type /Pages /Kids [{kids}] /Count {} >>", pages.len()).into_bytes(),
+        b"<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding /WinAnsiEncoding >>"
+            .to_vec(),
+        b"<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica-Bold /Encoding /WinAnsiEncoding >>"
+            .to_vec(),
+    ];
+
+    for (page_id, content) in page_ids.iter().zip(pages) {
+        objects.push(
+            format!(
+                "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] \
+                 /Resources << /Font << /{REGULAR_FONT} 3 0 R /{BOLD_FONT} 4 0 R >> >> \
+                 /Contents {} 0 R >>",
+                number(page_width),
+                number(page_height),
+                page_id + 1
+            )
+            .into_bytes(),
+        );
+
+        let mut stream = format!("<< /Length {} >>\nstream\n", content.len()).into_bytes();
+        stream.extend(content);
+        stream.extend(b"\nendstream");
+        objects.push(stream);
+    }
+
+    let mut pdf = b"%PDF-1.4\n".to_vec();
+    let mut offsets = vec![];
+
+    for (idx, object) in objects.iter().enumerate() {
+        offsets.push(pdf.len());
+        pdf.extend(format!("{} 0 obj\n", idx + 1).into_bytes());
+        pdf.extend(object);
+        pdf.extend(b"\nendobj\n");
+    }
+
+    let xref_offset = pdf.len();
+    pdf.extend(format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1).into_bytes());
+
+    for offset in offsets {
+        pdf.extend(format!("{offset:010} 00000 n \n").into_bytes());
+    }
+
+    pdf.extend(
+        format!(
+            "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{xref_offset}\n%%EOF\n",
+            objects.len() + 1
+        )
+        .into_bytes(),
+    );
+
+    pdf
+}
+
+/// A PDF string literal in the WinAnsi encoding of the standard fonts. Characters outside of
+/// Latin-1 are replaced by `?`
+pub(crate) fn pdf_string(text: &str) -> Vec<u8> {
+    let mut string = vec![b'('];
+
+    for character in text.chars() {
+        match character {
+            '(' | ')' | '\\' => string.extend([b'\\', character as u8]),
+            character if (' '..='~').contains(&character) => string.push(character as u8),
+            character if ('\u{a0}'..='\u{ff}').contains(&character) => string.push(character as u8),
+            _ => string.push(b'?'),
+        }
+    }
+
+    string.push(b')');
+    string
+}
+
+/// A number with at most 2 decimals, as PDF has no exponent notation
+pub(crate) fn number(value: f32) -> String {
+    let formatted = format!("{value:.2}");
+    let trimmed = formatted.trim_end_matches('0').trim_end_matches('.');
+
+    match trimmed {
+        "-0" => "0".to_string(),
+        trimmed => trimmed.to_string(),
+    }
+}
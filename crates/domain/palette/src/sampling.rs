@@ -0,0 +1,260 @@
+use color::contrast::RGBA;
+use color::conversion::{Hsl, Hsluv, Hsv, Lch, Oklch, Srgb, Srgb8};
+use color::gamut::{gamut_map, gamut_map_oklch};
+use futures_signals::map_ref;
+use futures_signals::signal::{Mutable, Signal};
+use glam::{Mat3, Vec2};
+use serde::{Deserialize, Serialize};
+
+pub fn get_equidistant_points_in_range(start: f32, end: f32, count: usize) -> Vec<f32> {
+    let mut points = vec![];
+
+    for idx in 0..count {
+        let t = idx as f32 / (count as f32 - 1.);
+        let x = start + t * (end - start);
+
+        points.push(x);
+    }
+
+    points
+}
+
+pub fn algebraic_simple(x: f64) -> f64 {
+    x / (1. + x.powi(2)).sqrt()
+}
+
+/// Defines the rectangle within a color plane for which the sampling method applies.
+/// The sampling coordinate space (0..1, 0..1) is in this rectangles local coordinate system
+///
+/// Note: This rectangle can be rotated...
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SamplingRect {
+    pub x: Mutable<f32>,
+    pub y: Mutable<f32>,
+    pub width: Mutable<f32>,
+    pub height: Mutable<f32>,
+    pub rotation: Mutable<f32>,
+}
+
+impl Default for SamplingRect {
+    fn default() -> Self {
+        Self {
+            x: Mutable::new(0.),
+            y: Mutable::new(0.),
+            width: Mutable::new(1.),
+            height: Mutable::new(1.),
+            rotation: Mutable::new(0.),
+        }
+    }
+}
+
+impl SamplingRect {
+    pub fn translate_mat(&self) -> Mat3 {
+        Mat3::from_translation(glam::Vec2::new(self.x.get(), self.y.get()))
+    }
+
+    pub fn scale_mat(&self) -> Mat3 {
+        Mat3::from_scale(glam::Vec2::new(self.width.get(), self.height.get()))
+    }
+
+    pub fn rotate_mat(&self) -> Mat3 {
+        Mat3::from_rotation_z(self.rotation.get())
+    }
+
+    pub fn matrices_signal(&self) -> impl Signal<Item = (Mat3, Mat3, Mat3)> {
+        map_ref! {
+            let x = self.x.signal(),
+            let y = self.y.signal(),
+            let width = self.width.signal(),
+            let height = self.height.signal(),
+            let rotation= self.rotation.signal() => {
+                (Mat3::from_translation(glam::Vec2::new(*x, *y)),
+                 Mat3::from_scale(glam::Vec2::new(*width, *height)),
+                 Mat3::from_rotation_z(*rotation))
+            }
+        }
+    }
+
+    pub fn matrices(&self) -> (Mat3, Mat3, Mat3) {
+        (
+            Mat3::from_translation(glam::Vec2::new(self.x.get(), self.y.get())),
+            Mat3::from_scale(glam::Vec2::new(self.width.get(), self.height.get())),
+            Mat3::from_rotation_z(self.rotation.get()),
+        )
+    }
+}
+
+pub fn hsv_colors_u8(hue: f32, angle: f32, sample_coords: &Vec<Vec2>) -> Vec<RGBA> {
+    let mut out_colors = vec![];
+
+    for shade in sample_coords {
+        // Shift the hue based on the color plane angle
+        let color_hue = (hue - angle * shade.y).rem_euclid(360.);
+        let color = Srgb8::from(Hsv {
+            h: color_hue,
+            s: shade.x,
+            v: shade.y,
+        });
+        out_colors.push(color.into());
+    }
+
+    out_colors
+}
+
+pub fn hsl_colors_u8(hue: f32, angle: f32, sample_coords: &Vec<Vec2>) -> Vec<RGBA> {
+    let mut out_colors = vec![];
+
+    for shade in sample_coords {
+        // Shift the hue based on the color plane angle
+        let color_hue = hue - angle * shade.y;
+        let color = Srgb8::from(Hsl {
+            h: color_hue.rem_euclid(360.),
+            s: shade.x,
+            l: shade.y,
+        });
+        out_colors.push(color.into());
+    }
+
+    out_colors
+}
+
+/// The OKLCH chroma at the right edge of the sampling plane. Matches 100% chroma in CSS `oklch()`
+pub const OKLCH_MAX_CHROMA: f32 = 0.4;
+
+/// Samples the chroma (x) by lightness (y) plane at the given hue. Samples outside of the sRGB
+/// gamut are gamut mapped, preserving their lightness and hue
+pub fn oklch_colors_u8(hue: f32, angle: f32, sample_coords: &Vec<Vec2>) -> Vec<RGBA> {
+    let mut out_colors = vec![];
+
+    for shade in sample_coords {
+        // Shift the hue based on the color plane angle
+        let color_hue = (hue - angle * shade.y).rem_euclid(360.);
+        let color = Srgb8::from(gamut_map_oklch(Oklch {
+            l: shade.y,
+            c: shade.x * OKLCH_MAX_CHROMA,
+            h: color_hue,
+        }));
+        out_colors.push(color.into());
+    }
+
+    out_colors
+}
+
+/// The CIELAB chroma at the right edge of the sampling plane. Matches 100% chroma in CSS `lch()`
+pub const LCH_MAX_CHROMA: f32 = 150.;
+
+/// Samples the chroma (x) by lightness (y) plane at the given hue in CIELAB LCh. Samples outside
+/// of the sRGB gamut are gamut mapped
+pub fn lch_colors_u8(hue: f32, angle: f32, sample_coords: &Vec<Vec2>) -> Vec<RGBA> {
+    let mut out_colors = vec![];
+
+    for shade in sample_coords {
+        // Shift the hue based on the color plane angle
+        let color_hue = (hue - angle * shade.y).rem_euclid(360.);
+        let color = Srgb8::from(gamut_map(Srgb::from(Lch {
+            l: shade.y * 100.,
+            c: shade.x * LCH_MAX_CHROMA,
+            h: color_hue,
+        })));
+        out_colors.push(color.into());
+    }
+
+    out_colors
+}
+
+/// Samples the saturation (x) by lightness (y) plane at the given hue in HSLuv.
+/// All of the plane is within the sRGB gamut
+pub fn hsluv_colors_u8(hue: f32, angle: f32, sample_coords: &Vec<Vec2>) -> Vec<RGBA> {
+    let mut out_colors = vec![];
+
+    for shade in sample_coords {
+        // Shift the hue based on the color plane angle
+        let color_hue = (hue - angle * shade.y).rem_euclid(360.);
+        let color = Srgb8::from(Hsluv {
+            h: color_hue,
+            s: shade.x * 100.,
+            l: shade.y * 100.,
+        });
+        out_colors.push(color.into());
+    }
+
+    out_colors
+}
+
+pub fn static_sample_signal(
+    sampling_rect_matrices_signal: impl Signal<Item = (Mat3, Mat3, Mat3)> + 'static,
+    points_signal: impl Signal<Item = Vec<Vec2>> + 'static,
+) -> impl Signal<Item = Vec<Vec2>> + 'static {
+    map_ref! {
+        let points = points_signal,
+        let sampling_rect_matrices = sampling_rect_matrices_signal => {
+            static_sample(sampling_rect_matrices, points)
+        }
+    }
+}
+
+pub fn static_sample(
+    sampling_rect_matrices: &(Mat3, Mat3, Mat3),
+    input_points: &Vec<Vec2>,
+) -> Vec<Vec2> {
+    let mut points = vec![];
+
+    for point in input_points {
+        let point = Vec2::new(point.x.clamp(0., 1.), point.y.clamp(0., 1.));
+        let mat = sampling_rect_matrices.0 * sampling_rect_matrices.1 * sampling_rect_matrices.2;
+        let point = mat.transform_point2(point);
+
+        points.push(Vec2::new(point.x.clamp(0., 1.), point.y.clamp(0., 1.)));
+    }
+
+    points
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::f32::consts::FRAC_PI_2;
+
+    #[test]
+    fn test_identity_rect_keeps_points() {
+        let points = vec![Vec2::new(0., 1.), Vec2::new(0.25, 0.5), Vec2::new(1., 0.)];
+
+        assert_eq!(
+            static_sample(&SamplingRect::default().matrices(), &points),
+            points
+        );
+    }
+
+    #[test]
+    fn test_samples_are_transformed_and_clamped() {
+        let rect = SamplingRect::default();
+        rect.x.set(0.5);
+        rect.width.set(0.5);
+
+        let samples = static_sample(
+            &rect.matrices(),
+            &vec![Vec2::new(0., 0.5), Vec2::new(2., 0.5)],
+        );
+
+        assert_eq!(samples, vec![Vec2::new(0.5, 0.5), Vec2::new(1., 0.5)]);
+
+        rect.x.set(0.);
+        rect.width.set(1.);
+        rect.rotation.set(FRAC_PI_2);
+
+        let rotated = static_sample(&rect.matrices(), &vec![Vec2::new(0.5, 0.)]);
+
+        assert!(rotated[0].x.abs() < 0.0001 && (rotated[0].y - 0.5).abs() < 0.0001);
+    }
+
+    #[test]
+    fn test_sampling_helpers() {
+        assert_eq!(
+            get_equidistant_points_in_range(0., 1., 5),
+            vec![0., 0.25, 0.5, 0.75, 1.]
+        );
+        assert_eq!(algebraic_simple(0.), 0.);
+        assert!((algebraic_simple(1.) - 1. / 2f64.sqrt()).abs() < 1e-12);
+        assert!(algebraic_simple(1e6) < 1.);
+    }
+}
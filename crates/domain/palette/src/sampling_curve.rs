@@ -1,4 +1,4 @@
-use crate::model::palette_color::DWIND_CURVE2;
+use crate::palette_color::DWIND_CURVE2;
 use futures_signals::signal::Mutable;
 use glam::Vec2;
 use serde::{Deserialize, Serialize};
@@ -123,3 +123,37 @@ impl SamplingCurve {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_points_are_sorted_by_x() {
+        let curve = SamplingCurve::new();
+        curve.add_new_point(Vec2::new(0.1, 0.2));
+        curve.add_new_point(Vec2::new(0.9, 0.3));
+
+        assert_eq!(
+            curve.curve.get_cloned(),
+            vec![
+                Vec2::new(0.1, 0.2),
+                Vec2::new(0.5, 0.5),
+                Vec2::new(0.9, 0.3)
+            ]
+        );
+
+        let new_idx = curve.replace_point(
+            0,
+            Vec2::new(0.7, 0.8),
+            Modifiers {
+                alt: false,
+                ctrl: true,
+                shift: false,
+            },
+        );
+
+        assert_eq!(new_idx, 1);
+        assert_eq!(curve.curve.get_cloned()[1], Vec2::new(0.7, 0.2));
+    }
+}
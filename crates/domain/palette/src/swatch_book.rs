@@ -0,0 +1,500 @@
+//!
+//!  # Swatch books
+//!
+//! Printable vector documents of the palette, for brand reviews. Every color is a row of
+//! shades, and every shade is labelled with its name and its value in each CSS syntax.
+//! Shades can also be badged with the WCAG level of white or black text on them.
+//!
+//! Books are exported as a single SVG image, or as a PDF document with as many rows per page
+//! as fit.
+//!
+
+use crate::palette::{shade_name, Palette};
+use crate::pdf::{number, pdf_string, write_pdf, BOLD_FONT, REGULAR_FONT};
+use color::contrast::{contrast, RGBA};
+use color::css::CssColorSyntax;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// Margin around the pages, in points
+const MARGIN: f32 = 36.;
+/// Space between the swatches of a row, in points
+const SWATCH_GAP: f32 = 4.;
+/// Size of the color names above each row, in points
+const TITLE_SIZE: f32 = 11.;
+/// Size of the page numbers of PDF pages, in points
+const FOOTER_SIZE: f32 = 7.;
+
+const WHITE: RGBA = RGBA::opaque(255, 255, 255);
+const BLACK: RGBA = RGBA::opaque(0, 0, 0);
+const TEXT: RGBA = RGBA::opaque(0x1f, 0x29, 0x37);
+const SECONDARY_TEXT: RGBA = RGBA::opaque(0x6b, 0x72, 0x80);
+/// The outline of swatches, to show light shades on white paper
+const OUTLINE: RGBA = RGBA::opaque(0xd1, 0xd5, 0xdb);
+
+/// The paper size of swatch books, in landscape
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum PageSize {
+    #[default]
+    A4,
+    A3,
+    Letter,
+}
+
+impl PageSize {
+    pub const ALL: [PageSize; 3] = [PageSize::A4, PageSize::A3, PageSize::Letter];
+
+    /// Width and height in points, in landscape
+    pub fn dimensions(&self) -> (f32, f32) {
+        match self {
+            PageSize::A4 => (842., 595.),
+            PageSize::A3 => (1191., 842.),
+            PageSize::Letter => (792., 612.),
+        }
+    }
+}
+
+impl Display for PageSize {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PageSize::A4 => write!(f, "A4"),
+            PageSize::A3 => write!(f, "A3"),
+            PageSize::Letter => write!(f, "Letter"),
+        }
+    }
+}
+
+impl FromStr for PageSize {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|size| size.to_string() == s)
+            .ok_or(())
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct SwatchBookOptions {
+    pub page_size: PageSize,
+    /// Badge every shade with the WCAG level of the better of white and black text on it
+    pub contrast_badges: bool,
+}
+
+/// Something drawn on a page, positioned in points from the top left corner
+#[derive(Clone, Debug, PartialEq)]
+enum Item {
+    Swatch {
+        x: f32,
+        y: f32,
+        width: f32,
+        height: f32,
+        fill: RGBA,
+    },
+    /// Text with its baseline at `y`
+    Text {
+        x: f32,
+        y: f32,
+        size: f32,
+        bold: bool,
+        fill: RGBA,
+        text: String,
+    },
+}
+
+/// The sizes of the rows of a book, which are fit to the page width
+struct Layout {
+    swatch_width: f32,
+    swatch_height: f32,
+    font_size: f32,
+    line_height: f32,
+}
+
+impl Layout {
+    fn new(page_width: f32, shades: usize) -> Self {
+        let shades = shades.max(1) as f32;
+        let swatch_width = (page_width - 2. * MARGIN - (shades - 1.) * SWATCH_GAP) / shades;
+        // Fits the longest value, e.g. `oklch(97.05% 0.0142 254.6)`, to the swatch
+        let font_size = (swatch_width / 14.).clamp(4., 9.);
+
+        Self {
+            swatch_width,
+            swatch_height: swatch_width * 0.6,
+            font_size,
+            line_height: font_size * 1.35,
+        }
+    }
+
+    /// The height of a row including the space below it
+    fn row_height(&self) -> f32 {
+        TITLE_SIZE * 1.6 + self.swatch_height + self.line_height * 5.5 + TITLE_SIZE
+    }
+
+    /// Lay out the row of a color with its top at `top`
+    fn row(&self, name: &str, shades: &[RGBA], top: f32, options: &SwatchBookOptions) -> Vec<Item> {
+        let mut items = vec![Item::Text {
+            x: MARGIN,
+            y: top + TITLE_SIZE,
+            size: TITLE_SIZE,
+            bold: true,
+            fill: TEXT,
+            text: name.to_string(),
+        }];
+
+        let swatch_top = top + TITLE_SIZE * 1.6;
+
+        for (idx, shade) in shades.iter().enumerate() {
+            let x = MARGIN + idx as f32 * (self.swatch_width + SWATCH_GAP);
+
+            items.push(Item::Swatch {
+                x,
+                y: swatch_top,
+                width: self.swatch_width,
+                height: self.swatch_height,
+                fill: *shade,
+            });
+
+            if options.contrast_badges {
+                let (text_color, badge) = contrast_badge(*shade);
+
+                items.push(Item::Text {
+                    x: x + self.font_size * 0.6,
+                    y: swatch_top + self.swatch_height - self.font_size * 0.6,
+                    size: self.font_size,
+                    bold: true,
+                    fill: text_color,
+                    text: badge,
+                });
+            }
+
+            let label = format!("{name}-{}", shade_name(idx));
+            let values = CssColorSyntax::ALL.map(|syntax| syntax.format(*shade));
+
+            for (line, (text, bold)) in [(label, true)]
+                .into_iter()
+                .chain(values.into_iter().map(|value| (value, false)))
+                .enumerate()
+            {
+                items.push(Item::Text {
+                    x,
+                    y: swatch_top + self.swatch_height + self.line_height * (line as f32 + 1.2),
+                    size: self.font_size,
+                    bold,
+                    fill: if bold { TEXT } else { SECONDARY_TEXT },
+                    text,
+                });
+            }
+        }
+
+        items
+    }
+}
+
+/// The better of white and black text on the shade, and its WCAG level and contrast ratio,
+/// e.g. `AA 4.8:1`
+fn contrast_badge(shade: RGBA) -> (RGBA, String) {
+    let (text_color, ratio) = [WHITE, BLACK]
+        .map(|text_color| (text_color, contrast(text_color, shade)))
+        .into_iter()
+        .max_by(|(_, a), (_, b)| a.total_cmp(b))
+        .unwrap();
+
+    let level = match ratio {
+        ratio if ratio >= 7. => "AAA",
+        ratio if ratio >= 4.5 => "AA",
+        ratio if ratio >= 3. => "AA large",
+        _ => "Fail",
+    };
+
+    (text_color, format!("{level} {ratio:.1}:1"))
+}
+
+/// The rows of the book, split into pages of `page_height` points
+fn pages(
+    swatches: &[(String, Vec<RGBA>)],
+    page_width: f32,
+    page_height: Option<f32>,
+    options: &SwatchBookOptions,
+) -> (Layout, Vec<Vec<Item>>) {
+    let shades = swatches
+        .iter()
+        .map(|(_, shades)| shades.len())
+        .max()
+        .unwrap_or(0);
+    let layout = Layout::new(page_width, shades);
+
+    let rows_per_page = match page_height {
+        Some(height) => (((height - 2. * MARGIN) / layout.row_height()) as usize).max(1),
+        None => swatches.len().max(1),
+    };
+
+    let pages = swatches
+        .chunks(rows_per_page)
+        .map(|rows| {
+            rows.iter()
+                .enumerate()
+                .flat_map(|(idx, (name, shades))| {
+                    let top = MARGIN + idx as f32 * layout.row_height();
+                    layout.row(name, shades, top, options)
+                })
+                .collect()
+        })
+        .collect();
+
+    (layout, pages)
+}
+
+/// Render the swatches as a swatch book in a single SVG image, as wide as a page
+pub fn swatch_book_svg(swatches: &[(String, Vec<RGBA>)], options: &SwatchBookOptions) -> String {
+    let (width, _) = options.page_size.dimensions();
+    let (layout, pages) = pages(swatches, width, None, options);
+    let height = 2. * MARGIN + swatches.len() as f32 * layout.row_height();
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}pt\" height=\"{h}pt\" \
+         viewBox=\"0 0 {w} {h}\" font-family=\"Helvetica, Arial, sans-serif\">\n",
+        w = number(width),
+        h = number(height),
+    );
+    svg += &format!(
+        "  <rect width=\"100%\" height=\"100%\" fill=\"{}\"/>\n",
+        WHITE.to_hex()
+    );
+
+    for item in pages.into_iter().flatten() {
+        match item {
+            Item::Swatch {
+                x,
+                y,
+                width,
+                height,
+                fill,
+            } => {
+                svg += &format!(
+                    "  <rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\" \
+                     stroke=\"{}\" stroke-width=\"0.5\"/>\n",
+                    number(x),
+                    number(y),
+                    number(width),
+                    number(height),
+                    fill.to_hex(),
+                    OUTLINE.to_hex()
+                );
+            }
+            Item::Text {
+                x,
+                y,
+                size,
+                bold,
+                fill,
+                text,
+            } => {
+                svg += &format!(
+                    "  <text x=\"{}\" y=\"{}\" font-size=\"{}\"{} fill=\"{}\">{}</text>\n",
+                    number(x),
+                    number(y),
+                    number(size),
+                    if bold { " font-weight=\"bold\"" } else { "" },
+                    fill.to_hex(),
+                    escape_xml(&text)
+                );
+            }
+        }
+    }
+
+    svg += "</svg>\n";
+    svg
+}
+
+/// Render the swatches as a swatch book PDF, with as many rows per page as fit, and the page
+/// number at the bottom of every page
+pub fn swatch_book_pdf(swatches: &[(String, Vec<RGBA>)], options: &SwatchBookOptions) -> Vec<u8> {
+    let (width, height) = options.page_size.dimensions();
+    let (_, mut pages) = pages(swatches, width, Some(height), options);
+
+    if pages.is_empty() {
+        pages.push(vec![]);
+    }
+
+    let page_count = pages.len();
+
+    let contents = pages
+        .into_iter()
+        .enumerate()
+        .map(|(idx, mut items)| {
+            items.push(Item::Text {
+                x: MARGIN,
+                y: height - MARGIN / 2.,
+                size: FOOTER_SIZE,
+                bold: false,
+                fill: SECONDARY_TEXT,
+                text: format!("Page {} of {page_count}", idx + 1),
+            });
+
+            let mut content = vec![];
+
+            for item in items {
+                match item {
+                    Item::Swatch {
+                        x,
+                        y,
+                        width,
+                        height: swatch_height,
+                        fill,
+                    } => {
+                        content.extend(
+                            format!(
+                                "{} {} 0.5 w {} {} {} {} re B\n",
+                                pdf_color(fill, "rg"),
+                                pdf_color(OUTLINE, "RG"),
+                                number(x),
+                                number(height - y - swatch_height),
+                                number(width),
+                                number(swatch_height)
+                            )
+                            .into_bytes(),
+                        );
+                    }
+                    Item::Text {
+                        x,
+                        y,
+                        size,
+                        bold,
+                        fill,
+                        text,
+                    } => {
+                        let font = if bold { BOLD_FONT } else { REGULAR_FONT };
+
+                        content.extend(
+                            format!(
+                                "BT /{font} {} Tf {} {} {} Td ",
+                                number(size),
+                                pdf_color(fill, "rg"),
+                                number(x),
+                                number(height - y)
+                            )
+                            .into_bytes(),
+                        );
+                        content.extend(pdf_string(&text));
+                        content.extend(b" Tj ET\n");
+                    }
+                }
+            }
+
+            content
+        })
+        .collect::<Vec<_>>();
+
+    write_pdf(width, height, &contents)
+}
+
+/// A color operator, `rg` for filling or `RG` for stroking
+fn pdf_color(color: RGBA, operator: &str) -> String {
+    let channel = |value: u8| number(value as f32 / 255.);
+
+    format!(
+        "{} {} {} {operator}",
+        channel(color.r),
+        channel(color.g),
+        channel(color.b)
+    )
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+impl Palette {
+    /// All colors as a swatch book SVG, see [swatch_book_svg]. Wide gamut shades are shown as
+    /// their sRGB fallbacks, and the alpha scales are left out
+    pub fn to_swatch_book_svg(&self, options: &SwatchBookOptions) -> String {
+        swatch_book_svg(&self.to_sheet_swatches(), options)
+    }
+
+    /// All colors as a swatch book PDF, see [Palette::to_swatch_book_svg]
+    pub fn to_swatch_book_pdf(&self, options: &SwatchBookOptions) -> Vec<u8> {
+        swatch_book_pdf(&self.to_sheet_swatches(), options)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn swatches(count: usize) -> Vec<(String, Vec<RGBA>)> {
+        (0..count)
+            .map(|idx| {
+                (
+                    format!("brand-{idx}"),
+                    vec![
+                        RGBA::opaque(0xef, 0xf6, 0xff),
+                        RGBA::opaque(0x1e, 0x3a, 0x8a),
+                    ],
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_svg() {
+        let options = SwatchBookOptions {
+            contrast_badges: true,
+            ..Default::default()
+        };
+
+        let svg = swatch_book_svg(&swatches(2), &options);
+
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"842pt\""));
+        assert!(svg.ends_with("</svg>\n"));
+        assert_eq!(svg.matches("fill=\"#eff6ff\" stroke").count(), 2);
+        assert!(svg.contains(">brand-1</text>"));
+        assert!(svg.contains(">brand-0-100</text>"));
+        assert!(svg.contains(">rgb(239 246 255)</text>"));
+        assert!(svg.contains(">oklch("));
+        // Black text on the light shade, and white text on the dark one
+        assert!(svg.contains("fill=\"#000000\">AAA 19.3:1</text>"));
+        assert!(svg.contains("fill=\"#ffffff\">AAA 10.4:1</text>"));
+
+        let without_badges = swatch_book_svg(&swatches(2), &SwatchBookOptions::default());
+
+        assert!(!without_badges.contains(":1</text>"));
+    }
+
+    #[test]
+    fn test_pdf() {
+        let options = SwatchBookOptions::default();
+        let pdf = swatch_book_pdf(&swatches(12), &options);
+        let text = String::from_utf8_lossy(&pdf);
+
+        assert!(text.starts_with("%PDF-1.4\n"));
+        assert!(text.ends_with("%%EOF\n"));
+        assert!(text.contains("/MediaBox [0 0 842 595]"));
+        assert!(text.contains("(brand-11) Tj"));
+
+        // Rows don't fit on a single A4 page
+        let pages = text.matches("/Type /Page ").count();
+
+        assert!(pages > 1);
+        assert!(text.contains(&format!("/Count {pages}")));
+        assert!(text.contains(&format!("(Page {pages} of {pages}) Tj")));
+
+        // The cross reference table points at every object
+        let xref = text.rfind("xref\n").unwrap();
+
+        for (idx, line) in text[xref..].lines().skip(3).take(4 + 2 * pages).enumerate() {
+            let offset = line[..10].parse::<usize>().unwrap();
+
+            assert!(text[offset..].starts_with(&format!("{} 0 obj", idx + 1)));
+        }
+    }
+
+    #[test]
+    fn test_pdf_strings() {
+        assert_eq!(pdf_string("a(b)\\"), b"(a\\(b\\)\\\\)");
+        assert_eq!(pdf_string("blå→"), b"(bl\xe5?)");
+    }
+}
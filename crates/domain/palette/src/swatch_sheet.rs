@@ -0,0 +1,654 @@
+//!
+//!  # Swatch sheets
+//!
+//! PNG images of the palette, with a swatch per shade. Sheets can be labelled with the names of
+//! the colors and the hex codes of their shades, which are drawn with a built-in pixel font.
+//!
+//! Indexed PNGs store the shades in their PLTE chunk, so pixel art editors such as Aseprite can
+//! load them as palettes.
+//!
+
+use crate::bitmap_font::{draw_text, text_width, ADVANCE, GLYPH_HEIGHT};
+use crate::palette::Palette;
+use color::contrast::{contrast, RGBA};
+use color::conversion::Srgb8;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// The number of colors a PLTE chunk can hold
+const MAX_PALETTE_SIZE: usize = 256;
+
+/// The largest sheet drawn, 1 GiB of RGBA pixels
+const MAX_SHEET_PIXELS: u64 = 1 << 28;
+
+/// How the colors are laid out on a swatch sheet
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum SheetOrientation {
+    /// One row of shades per color
+    #[default]
+    Rows,
+    /// One column of shades per color
+    Columns,
+}
+
+impl SheetOrientation {
+    pub const ALL: [SheetOrientation; 2] = [SheetOrientation::Rows, SheetOrientation::Columns];
+}
+
+impl Display for SheetOrientation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SheetOrientation::Rows => write!(f, "Rows"),
+            SheetOrientation::Columns => write!(f, "Columns"),
+        }
+    }
+}
+
+impl FromStr for SheetOrientation {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|orientation| orientation.to_string() == s)
+            .ok_or(())
+    }
+}
+
+/// The labels drawn on a swatch sheet
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum SwatchLabels {
+    None,
+    /// The name of each color
+    Names,
+    /// The hex code of each shade, below its swatch
+    HexCodes,
+    #[default]
+    NamesAndHexCodes,
+}
+
+impl SwatchLabels {
+    pub const ALL: [SwatchLabels; 4] = [
+        SwatchLabels::None,
+        SwatchLabels::Names,
+        SwatchLabels::HexCodes,
+        SwatchLabels::NamesAndHexCodes,
+    ];
+
+    pub fn names(&self) -> bool {
+        matches!(self, SwatchLabels::Names | SwatchLabels::NamesAndHexCodes)
+    }
+
+    pub fn hex_codes(&self) -> bool {
+        matches!(
+            self,
+            SwatchLabels::HexCodes | SwatchLabels::NamesAndHexCodes
+        )
+    }
+}
+
+impl Display for SwatchLabels {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SwatchLabels::None => write!(f, "None"),
+            SwatchLabels::Names => write!(f, "Names"),
+            SwatchLabels::HexCodes => write!(f, "Hex codes"),
+            SwatchLabels::NamesAndHexCodes => write!(f, "Names and hex codes"),
+        }
+    }
+}
+
+impl FromStr for SwatchLabels {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|labels| labels.to_string() == s)
+            .ok_or(())
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SwatchSheetOptions {
+    /// Width and height of each swatch in pixels
+    pub swatch_size: u32,
+    /// Space between swatches, and around the sheet, in pixels
+    pub gap: u32,
+    pub labels: SwatchLabels,
+    pub orientation: SheetOrientation,
+    /// The color behind the swatches and labels, transparent if `None`
+    pub background: Option<Srgb8>,
+}
+
+impl Default for SwatchSheetOptions {
+    fn default() -> Self {
+        Self {
+            swatch_size: 48,
+            gap: 8,
+            labels: SwatchLabels::default(),
+            orientation: SheetOrientation::default(),
+            background: Some(Srgb8::new(255, 255, 255)),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum SwatchSheetError {
+    /// There are no shades to draw
+    Empty,
+    /// An indexed PNG can hold at most 256 colors
+    TooManyColors(usize),
+    /// Swatches must be at least a pixel wide
+    ZeroSwatchSize,
+    /// The sheet has more than [MAX_SHEET_PIXELS] pixels
+    TooLarge,
+    Encoding(png::EncodingError),
+}
+
+impl Display for SwatchSheetError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SwatchSheetError::Empty => write!(f, "Nothing to draw, the palette has no colors"),
+            SwatchSheetError::TooManyColors(count) => write!(
+                f,
+                "The palette has {count} shades, but an indexed PNG holds at most {MAX_PALETTE_SIZE}"
+            ),
+            SwatchSheetError::ZeroSwatchSize => write!(f, "The swatch size must be at least 1"),
+            SwatchSheetError::TooLarge => write!(
+                f,
+                "The sheet would have more than {MAX_SHEET_PIXELS} pixels, use a smaller swatch size"
+            ),
+            SwatchSheetError::Encoding(error) => write!(f, "Failed to encode the PNG: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for SwatchSheetError {}
+
+impl From<png::EncodingError> for SwatchSheetError {
+    fn from(error: png::EncodingError) -> Self {
+        SwatchSheetError::Encoding(error)
+    }
+}
+
+/// A rendered swatch sheet
+#[derive(Clone, Debug, PartialEq)]
+pub struct SwatchSheet {
+    pub width: u32,
+    pub height: u32,
+    /// RGBA pixels, row by row
+    pub pixels: Vec<u8>,
+}
+
+impl SwatchSheet {
+    /// Draw the shades of each swatch next to each other, in a row or column per swatch.
+    /// Labels are drawn at a larger scale for large swatches, and swatches are widened to fit
+    /// their hex codes
+    pub fn render(
+        swatches: &[(String, Vec<RGBA>)],
+        options: &SwatchSheetOptions,
+    ) -> Result<Self, SwatchSheetError> {
+        let SwatchSheetOptions {
+            swatch_size,
+            gap,
+            labels,
+            orientation,
+            background,
+        } = *options;
+
+        if swatch_size == 0 {
+            return Err(SwatchSheetError::ZeroSwatchSize);
+        }
+
+        let colors = swatches.len() as u64;
+        let shades = swatches
+            .iter()
+            .map(|(_, shades)| shades.len())
+            .max()
+            .unwrap_or(0) as u64;
+
+        if shades == 0 {
+            return Ok(Self {
+                width: 0,
+                height: 0,
+                pixels: vec![],
+            });
+        }
+
+        // The layout is computed in u64 and checked against the size limit, as large swatch
+        // sizes overflow u32
+        let size = u64::from(swatch_size);
+        let gap = u64::from(gap);
+        let text_scale = (size / 64).max(1);
+        let line_height = u64::from(GLYPH_HEIGHT) * text_scale;
+        let label_spacing = u64::from(ADVANCE) * text_scale;
+        let text_width = |text: &str| u64::from(text_width(text, 1)) * text_scale;
+
+        let name_width = match labels.names() {
+            true => swatches
+                .iter()
+                .map(|(name, _)| text_width(name))
+                .max()
+                .unwrap_or(0),
+            false => 0,
+        };
+
+        // Hex codes are written below their swatch
+        let (cell_width, caption_height) = match labels.hex_codes() {
+            true => {
+                let hex_width = swatches
+                    .iter()
+                    .flat_map(|(_, shades)| shades)
+                    .map(|shade| text_width(&shade.to_hex()))
+                    .max()
+                    .unwrap_or(0);
+
+                (size.max(hex_width), line_height + 2 * text_scale)
+            }
+            false => (size, 0),
+        };
+
+        let cell_height = size + caption_height;
+        let span = |count: u64, size: u64| {
+            count
+                .checked_mul(size)?
+                .checked_add(count.saturating_sub(1).checked_mul(gap)?)
+        };
+
+        let name_offset = match (labels.names(), orientation) {
+            (false, _) => 0,
+            (true, SheetOrientation::Rows) => name_width + label_spacing,
+            (true, SheetOrientation::Columns) => line_height + label_spacing,
+        };
+
+        // Each color is a line of shades, which is a row or a column of the sheet
+        let line_width = match orientation {
+            SheetOrientation::Rows => cell_height,
+            SheetOrientation::Columns => cell_width.max(name_width),
+        };
+
+        let (width, height) = match orientation {
+            SheetOrientation::Rows => (
+                span(shades, cell_width).map(|span| 2 * gap + name_offset + span),
+                span(colors, line_width).map(|span| 2 * gap + span),
+            ),
+            SheetOrientation::Columns => (
+                span(colors, line_width).map(|span| 2 * gap + span),
+                span(shades, cell_height).map(|span| 2 * gap + name_offset + span),
+            ),
+        };
+
+        let (Some(width), Some(height)) = (width, height) else {
+            return Err(SwatchSheetError::TooLarge);
+        };
+
+        if width
+            .checked_mul(height)
+            .is_none_or(|pixels| pixels > MAX_SHEET_PIXELS)
+        {
+            return Err(SwatchSheetError::TooLarge);
+        }
+
+        // Every length is at most the width or height of the sheet, so they fit in u32
+        let [width, height, gap, text_scale, line_height, cell_width, cell_height, name_offset, line_width] =
+            [
+                width,
+                height,
+                gap,
+                text_scale,
+                line_height,
+                cell_width,
+                cell_height,
+                name_offset,
+                line_width,
+            ]
+            .map(|length| length as u32);
+
+        let mut sheet = Self {
+            width,
+            height,
+            pixels: match background {
+                Some(Srgb8 { r, g, b }) => [r, g, b, 255].repeat((width * height) as usize),
+                None => vec![0; (width * height * 4) as usize],
+            },
+        };
+
+        let text_color = match background {
+            Some(background) if is_dark(background) => RGBA::opaque(255, 255, 255),
+            _ => RGBA::opaque(0, 0, 0),
+        };
+
+        for (color, (name, shades)) in (0..).zip(swatches) {
+            let line = gap + color * (line_width + gap);
+
+            if labels.names() {
+                let (x, y) = match orientation {
+                    SheetOrientation::Rows => {
+                        (gap, line + swatch_size.saturating_sub(line_height) / 2)
+                    }
+                    SheetOrientation::Columns => (line, gap),
+                };
+
+                sheet.draw_text(name, x, y, text_scale, text_color);
+            }
+
+            for (shade_idx, shade) in (0..).zip(shades) {
+                let (x, y) = match orientation {
+                    SheetOrientation::Rows => {
+                        (gap + name_offset + shade_idx * (cell_width + gap), line)
+                    }
+                    SheetOrientation::Columns => {
+                        (line, gap + name_offset + shade_idx * (cell_height + gap))
+                    }
+                };
+
+                sheet.fill_rect(x, y, cell_width, swatch_size, *shade);
+
+                if labels.hex_codes() {
+                    let y = y + swatch_size + 2 * text_scale;
+                    sheet.draw_text(&shade.to_hex(), x, y, text_scale, text_color);
+                }
+            }
+        }
+
+        Ok(sheet)
+    }
+
+    /// Encode the sheet as an RGBA PNG
+    pub fn to_png(&self) -> Result<Vec<u8>, SwatchSheetError> {
+        if self.width == 0 || self.height == 0 {
+            return Err(SwatchSheetError::Empty);
+        }
+
+        let mut out = vec![];
+        let mut encoder = png::Encoder::new(&mut out, self.width, self.height);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&self.pixels)?;
+        writer.finish()?;
+
+        Ok(out)
+    }
+
+    fn fill_rect(&mut self, x: u32, y: u32, width: u32, height: u32, color: RGBA) {
+        for row in y..(y + height).min(self.height) {
+            for column in x..(x + width).min(self.width) {
+                let idx = ((row * self.width + column) * 4) as usize;
+                self.pixels[idx..idx + 4].copy_from_slice(&[color.r, color.g, color.b, color.a]);
+            }
+        }
+    }
+
+    fn draw_text(&mut self, text: &str, x: u32, y: u32, scale: u32, color: RGBA) {
+        draw_text(text, scale, |dx, dy| {
+            self.fill_rect(x + dx, y + dy, scale, scale, color)
+        });
+    }
+}
+
+fn is_dark(color: Srgb8) -> bool {
+    let color = RGBA::opaque(color.r, color.g, color.b);
+
+    contrast(RGBA::opaque(255, 255, 255), color) > contrast(RGBA::opaque(0, 0, 0), color)
+}
+
+/// Encode the swatches as an indexed PNG, with a `swatch_size` by `swatch_size` square per
+/// shade and a row per swatch. The palette of the PNG holds every shade in order, and rows
+/// shorter than the longest row are padded with their last shade
+pub fn encode_indexed_png(
+    swatches: &[(String, Vec<RGBA>)],
+    swatch_size: u32,
+) -> Result<Vec<u8>, SwatchSheetError> {
+    let shades = swatches.iter().flat_map(|(_, shades)| shades);
+    let shade_count = shades.clone().count();
+    let columns = swatches
+        .iter()
+        .map(|(_, shades)| shades.len())
+        .max()
+        .unwrap_or(0);
+
+    if swatch_size == 0 {
+        return Err(SwatchSheetError::ZeroSwatchSize);
+    }
+
+    if shade_count == 0 {
+        return Err(SwatchSheetError::Empty);
+    }
+
+    if shade_count > MAX_PALETTE_SIZE {
+        return Err(SwatchSheetError::TooManyColors(shade_count));
+    }
+
+    let rows = swatches.iter().filter(|(_, s)| !s.is_empty()).count();
+    let width = columns as u64 * u64::from(swatch_size);
+    let height = rows as u64 * u64::from(swatch_size);
+
+    if width
+        .checked_mul(height)
+        .is_none_or(|pixels| pixels > MAX_SHEET_PIXELS)
+    {
+        return Err(SwatchSheetError::TooLarge);
+    }
+
+    let (width, height) = (width as u32, height as u32);
+    let mut indices = Vec::with_capacity((width * height) as usize);
+    let mut first_index = 0;
+
+    for (_, row) in swatches.iter().filter(|(_, shades)| !shades.is_empty()) {
+        let mut line = Vec::with_capacity(width as usize);
+
+        for column in 0..columns {
+            let index = first_index + column.min(row.len() - 1);
+            line.extend([index as u8].repeat(swatch_size as usize));
+        }
+
+        for _ in 0..swatch_size {
+            indices.extend(&line);
+        }
+
+        first_index += row.len();
+    }
+
+    let mut out = vec![];
+    let mut encoder = png::Encoder::new(&mut out, width, height);
+    encoder.set_color(png::ColorType::Indexed);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder.set_palette(
+        shades
+            .clone()
+            .flat_map(|shade| [shade.r, shade.g, shade.b])
+            .collect::<Vec<_>>(),
+    );
+
+    if shades.clone().any(|shade| !shade.is_opaque()) {
+        encoder.set_trns(shades.map(|shade| shade.a).collect::<Vec<_>>());
+    }
+
+    let mut writer = encoder.write_header()?;
+    writer.write_image_data(&indices)?;
+    writer.finish()?;
+
+    Ok(out)
+}
+
+impl Palette {
+    /// Render the shades of every color as a swatch sheet, named after the colors. Wide gamut
+    /// shades are drawn as their sRGB fallbacks, and the alpha scales are left out
+    pub fn to_swatch_sheet(
+        &self,
+        options: &SwatchSheetOptions,
+    ) -> Result<SwatchSheet, SwatchSheetError> {
+        SwatchSheet::render(&self.to_sheet_swatches(), options)
+    }
+
+    /// The shades of every color as an indexed PNG, see [encode_indexed_png]
+    pub fn to_indexed_png(&self, swatch_size: u32) -> Result<Vec<u8>, SwatchSheetError> {
+        encode_indexed_png(&self.to_sheet_swatches(), swatch_size)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::palette::TAILWIND_NUMBERS;
+
+    fn decode(png: &[u8]) -> (png::OutputInfo, Vec<u8>, Option<Vec<u8>>) {
+        let mut decoder = png::Decoder::new(png);
+        decoder.set_transformations(png::Transformations::IDENTITY);
+
+        let mut reader = decoder.read_info().unwrap();
+        let palette = reader
+            .info()
+            .palette
+            .as_ref()
+            .map(|palette| palette.to_vec());
+        let mut pixels = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut pixels).unwrap();
+
+        (info, pixels, palette)
+    }
+
+    fn swatches() -> Vec<(String, Vec<RGBA>)> {
+        vec![
+            (
+                "red".to_string(),
+                vec![RGBA::opaque(255, 0, 0), RGBA::opaque(128, 0, 0)],
+            ),
+            ("blue".to_string(), vec![RGBA::opaque(0, 0, 255)]),
+        ]
+    }
+
+    #[test]
+    fn test_unlabelled_sheet() {
+        let options = SwatchSheetOptions {
+            swatch_size: 2,
+            gap: 0,
+            labels: SwatchLabels::None,
+            orientation: SheetOrientation::Rows,
+            background: None,
+        };
+
+        let sheet = SwatchSheet::render(&swatches(), &options).unwrap();
+        let (info, pixels, _) = decode(&sheet.to_png().unwrap());
+
+        assert_eq!((info.width, info.height), (4, 4));
+
+        let pixel = |x: usize, y: usize| &pixels[(y * 4 + x) * 4..(y * 4 + x) * 4 + 4];
+
+        assert_eq!(pixel(1, 1), [255, 0, 0, 255]);
+        assert_eq!(pixel(2, 0), [128, 0, 0, 255]);
+        assert_eq!(pixel(0, 3), [0, 0, 255, 255]);
+        assert_eq!(pixel(3, 3), [0, 0, 0, 0]);
+
+        let columns = SwatchSheet::render(
+            &swatches(),
+            &SwatchSheetOptions {
+                orientation: SheetOrientation::Columns,
+                gap: 1,
+                ..options
+            },
+        )
+        .unwrap();
+
+        assert_eq!((columns.width, columns.height), (7, 7));
+        assert_eq!(&columns.pixels[(4 * 7 + 1) * 4..][..4], [128, 0, 0, 255]);
+    }
+
+    #[test]
+    fn test_labelled_sheet() {
+        let options = SwatchSheetOptions {
+            swatch_size: 48,
+            gap: 8,
+            labels: SwatchLabels::NamesAndHexCodes,
+            ..Default::default()
+        };
+
+        let sheet = SwatchSheet::render(&swatches(), &options).unwrap();
+
+        // "blue" and "red" are labelled with at most 4 characters, and each hex code is 9 pixels
+        // high including its spacing
+        assert_eq!(sheet.width, 8 + 23 + 6 + 48 + 8 + 48 + 8);
+        assert_eq!(sheet.height, 8 + 57 + 8 + 57 + 8);
+
+        // The text is drawn in black on the white background
+        assert!(sheet.pixels.chunks(4).any(|pixel| pixel == [0, 0, 0, 255]));
+        assert_eq!(&sheet.pixels[..4], [255, 255, 255, 255]);
+
+        assert!(matches!(
+            SwatchSheet::render(&[], &options).unwrap().to_png(),
+            Err(SwatchSheetError::Empty)
+        ));
+    }
+
+    #[test]
+    fn test_sheet_size_limits() {
+        let options = |swatch_size| SwatchSheetOptions {
+            swatch_size,
+            ..Default::default()
+        };
+
+        for swatch_size in [70000, u32::MAX] {
+            assert!(matches!(
+                SwatchSheet::render(&swatches(), &options(swatch_size)),
+                Err(SwatchSheetError::TooLarge)
+            ));
+            assert!(matches!(
+                encode_indexed_png(&swatches(), swatch_size),
+                Err(SwatchSheetError::TooLarge)
+            ));
+        }
+
+        assert!(matches!(
+            SwatchSheet::render(&swatches(), &options(0)),
+            Err(SwatchSheetError::ZeroSwatchSize)
+        ));
+        assert!(matches!(
+            encode_indexed_png(&swatches(), 0),
+            Err(SwatchSheetError::ZeroSwatchSize)
+        ));
+    }
+
+    #[test]
+    fn test_indexed_png() {
+        let png = encode_indexed_png(&swatches(), 1).unwrap();
+        let (info, indices, palette) = decode(&png);
+
+        assert_eq!(info.color_type, png::ColorType::Indexed);
+        assert_eq!((info.width, info.height), (2, 2));
+        assert_eq!(palette.unwrap(), [255, 0, 0, 128, 0, 0, 0, 0, 255]);
+        // The short blue row repeats its last shade
+        assert_eq!(indices, [0, 1, 2, 2]);
+
+        let too_many = vec![("grey".to_string(), vec![RGBA::opaque(0, 0, 0); 257])];
+
+        assert!(matches!(
+            encode_indexed_png(&too_many, 1),
+            Err(SwatchSheetError::TooManyColors(257))
+        ));
+    }
+
+    #[test]
+    fn test_palette_exports() {
+        let palette = Palette::new();
+        let rows = palette.to_color_rows();
+
+        let sheet = palette
+            .to_swatch_sheet(&SwatchSheetOptions::default())
+            .unwrap();
+
+        assert!(sheet.width > TAILWIND_NUMBERS.len() as u32 * 48);
+        assert_eq!(sheet.to_png().unwrap()[1..4], *b"PNG");
+
+        let (_, _, indexed_palette) = decode(&palette.to_indexed_png(8).unwrap());
+        let indexed_palette = indexed_palette.unwrap();
+
+        assert_eq!(indexed_palette.len(), TAILWIND_NUMBERS.len() * 3);
+        assert_eq!(
+            indexed_palette[..3],
+            [rows[0][0].r, rows[0][0].g, rows[0][0].b]
+        );
+    }
+}
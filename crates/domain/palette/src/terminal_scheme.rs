@@ -0,0 +1,864 @@
+//!
+//!  # Terminal color schemes
+//!
+//! Maps palette shades to the colors of terminal emulators: the background, foreground, cursor
+//! and selection, and the 8 normal and 8 bright ANSI colors. The default mapping picks the
+//! palette color closest in hue to each ANSI color, and the least colorful palette color for
+//! the greys.
+//!
+//! Schemes can also set the other 240 colors of the 256 color palette, blended from the ANSI
+//! colors like the standard 6x6x6 color cube and grey ramp.
+//!
+
+use crate::lospec::GREY_CHROMA;
+use crate::palette::Palette;
+use color::contrast::RGBA;
+use color::conversion::{Oklab, Oklch, Srgb8};
+use serde_json::{Map, Value};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// The 8 ANSI colors, in the order of their color numbers
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum AnsiColor {
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+}
+
+impl AnsiColor {
+    pub const ALL: [AnsiColor; 8] = [
+        AnsiColor::Black,
+        AnsiColor::Red,
+        AnsiColor::Green,
+        AnsiColor::Yellow,
+        AnsiColor::Blue,
+        AnsiColor::Magenta,
+        AnsiColor::Cyan,
+        AnsiColor::White,
+    ];
+
+    /// The OKLCH hue of the pure sRGB color, `None` for black and white
+    fn hue(&self) -> Option<f32> {
+        match self {
+            AnsiColor::Black | AnsiColor::White => None,
+            AnsiColor::Red => Some(29.2),
+            AnsiColor::Green => Some(142.5),
+            AnsiColor::Yellow => Some(109.8),
+            AnsiColor::Blue => Some(264.1),
+            AnsiColor::Magenta => Some(328.4),
+            AnsiColor::Cyan => Some(194.8),
+        }
+    }
+}
+
+impl Display for AnsiColor {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AnsiColor::Black => write!(f, "Black"),
+            AnsiColor::Red => write!(f, "Red"),
+            AnsiColor::Green => write!(f, "Green"),
+            AnsiColor::Yellow => write!(f, "Yellow"),
+            AnsiColor::Blue => write!(f, "Blue"),
+            AnsiColor::Magenta => write!(f, "Magenta"),
+            AnsiColor::Cyan => write!(f, "Cyan"),
+            AnsiColor::White => write!(f, "White"),
+        }
+    }
+}
+
+/// A color of a terminal color scheme
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum TerminalRole {
+    Background,
+    Foreground,
+    Cursor,
+    /// The background of selected text
+    Selection,
+    Normal(AnsiColor),
+    Bright(AnsiColor),
+}
+
+impl TerminalRole {
+    pub const ALL: [TerminalRole; 20] = [
+        TerminalRole::Background,
+        TerminalRole::Foreground,
+        TerminalRole::Cursor,
+        TerminalRole::Selection,
+        TerminalRole::Normal(AnsiColor::Black),
+        TerminalRole::Normal(AnsiColor::Red),
+        TerminalRole::Normal(AnsiColor::Green),
+        TerminalRole::Normal(AnsiColor::Yellow),
+        TerminalRole::Normal(AnsiColor::Blue),
+        TerminalRole::Normal(AnsiColor::Magenta),
+        TerminalRole::Normal(AnsiColor::Cyan),
+        TerminalRole::Normal(AnsiColor::White),
+        TerminalRole::Bright(AnsiColor::Black),
+        TerminalRole::Bright(AnsiColor::Red),
+        TerminalRole::Bright(AnsiColor::Green),
+        TerminalRole::Bright(AnsiColor::Yellow),
+        TerminalRole::Bright(AnsiColor::Blue),
+        TerminalRole::Bright(AnsiColor::Magenta),
+        TerminalRole::Bright(AnsiColor::Cyan),
+        TerminalRole::Bright(AnsiColor::White),
+    ];
+}
+
+impl Display for TerminalRole {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TerminalRole::Background => write!(f, "Background"),
+            TerminalRole::Foreground => write!(f, "Foreground"),
+            TerminalRole::Cursor => write!(f, "Cursor"),
+            TerminalRole::Selection => write!(f, "Selection"),
+            TerminalRole::Normal(color) => write!(f, "{color}"),
+            TerminalRole::Bright(color) => {
+                write!(f, "Bright {}", color.to_string().to_lowercase())
+            }
+        }
+    }
+}
+
+impl FromStr for TerminalRole {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|role| role.to_string() == s)
+            .ok_or(())
+    }
+}
+
+/// Whether the scheme has a dark or a light background
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum TerminalTheme {
+    #[default]
+    Dark,
+    Light,
+}
+
+impl TerminalTheme {
+    pub const ALL: [TerminalTheme; 2] = [TerminalTheme::Dark, TerminalTheme::Light];
+
+    /// The shade of a role by its index in [crate::palette::TAILWIND_NUMBERS], e.g. 4 for 400
+    fn shade(&self, role: TerminalRole) -> usize {
+        use AnsiColor::{Black, White};
+
+        match (self, role) {
+            (TerminalTheme::Dark, TerminalRole::Background) => 10,
+            (TerminalTheme::Dark, TerminalRole::Foreground | TerminalRole::Cursor) => 2,
+            (TerminalTheme::Dark, TerminalRole::Selection) => 7,
+            (TerminalTheme::Dark, TerminalRole::Normal(Black)) => 8,
+            (TerminalTheme::Dark, TerminalRole::Bright(Black)) => 6,
+            (TerminalTheme::Dark, TerminalRole::Normal(White)) => 3,
+            (TerminalTheme::Dark, TerminalRole::Bright(White)) => 0,
+            (TerminalTheme::Dark, TerminalRole::Normal(_)) => 4,
+            (TerminalTheme::Dark, TerminalRole::Bright(_)) => 3,
+            (TerminalTheme::Light, TerminalRole::Background) => 0,
+            (TerminalTheme::Light, TerminalRole::Foreground | TerminalRole::Cursor) => 9,
+            (TerminalTheme::Light, TerminalRole::Selection) => 2,
+            (TerminalTheme::Light, TerminalRole::Normal(Black)) => 9,
+            (TerminalTheme::Light, TerminalRole::Bright(Black)) => 6,
+            (TerminalTheme::Light, TerminalRole::Normal(White)) => 3,
+            (TerminalTheme::Light, TerminalRole::Bright(White)) => 1,
+            (TerminalTheme::Light, TerminalRole::Normal(_)) => 7,
+            (TerminalTheme::Light, TerminalRole::Bright(_)) => 6,
+        }
+    }
+}
+
+impl Display for TerminalTheme {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TerminalTheme::Dark => write!(f, "Dark"),
+            TerminalTheme::Light => write!(f, "Light"),
+        }
+    }
+}
+
+impl FromStr for TerminalTheme {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|theme| theme.to_string() == s)
+            .ok_or(())
+    }
+}
+
+/// A shade of a palette color, by their indices. Written as `<color>:<shade>`, e.g. `0:5`
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub struct ShadeRef {
+    pub color: usize,
+    pub shade: usize,
+}
+
+impl Display for ShadeRef {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.color, self.shade)
+    }
+}
+
+impl FromStr for ShadeRef {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (color, shade) = s.split_once(':').ok_or(())?;
+
+        Ok(Self {
+            color: color.parse().map_err(|_| ())?,
+            shade: shade.parse().map_err(|_| ())?,
+        })
+    }
+}
+
+/// The default shade of every role, in the order of [TerminalRole::ALL]. Roles are mapped to
+/// the color closest in OKLCH hue to their ANSI color, and the greys, background and foreground
+/// to the color with the least chroma
+pub fn default_mapping(
+    swatches: &[(String, Vec<RGBA>)],
+    theme: TerminalTheme,
+) -> Vec<(TerminalRole, ShadeRef)> {
+    let hues = color_hues(swatches);
+    let neutral = least_colorful(&hues);
+
+    TerminalRole::ALL
+        .into_iter()
+        .map(|role| {
+            let color = match role {
+                TerminalRole::Normal(ansi) | TerminalRole::Bright(ansi) => ansi
+                    .hue()
+                    .and_then(|hue| closest_in_hue(&hues, hue))
+                    .unwrap_or(neutral),
+                _ => neutral,
+            };
+
+            let shade = scaled_shade(swatches, color, theme.shade(role));
+
+            (role, ShadeRef { color, shade })
+        })
+        .collect()
+}
+
+/// The hue and chroma of every color, by its most colorful shade
+pub(crate) fn color_hues(swatches: &[(String, Vec<RGBA>)]) -> Vec<Oklch> {
+    swatches
+        .iter()
+        .map(|(_, shades)| {
+            shades
+                .iter()
+                .map(|shade| Oklch::from(shade.rgb()))
+                .max_by(|a, b| a.c.total_cmp(&b.c))
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+/// The index of the color with the least chroma, see [color_hues]
+pub(crate) fn least_colorful(hues: &[Oklch]) -> usize {
+    (0..hues.len())
+        .min_by(|&a, &b| hues[a].c.total_cmp(&hues[b].c))
+        .unwrap_or(0)
+}
+
+/// The index of the color closest to an OKLCH hue, ignoring greys
+pub(crate) fn closest_in_hue(hues: &[Oklch], hue: f32) -> Option<usize> {
+    let distance = |idx: usize| {
+        let difference = (hues[idx].h - hue).rem_euclid(360.);
+        difference.min(360. - difference)
+    };
+
+    (0..hues.len())
+        .filter(|&idx| hues[idx].c >= GREY_CHROMA)
+        .min_by(|&a, &b| distance(a).total_cmp(&distance(b)))
+}
+
+/// A shade index of the 11 tailwind shades, scaled to the number of shades of a color
+pub(crate) fn scaled_shade(swatches: &[(String, Vec<RGBA>)], color: usize, shade: usize) -> usize {
+    let shade_count = swatches
+        .get(color)
+        .map(|(_, shades)| shades.len())
+        .unwrap_or(0);
+
+    (shade as f32 / 10. * shade_count.saturating_sub(1) as f32).round() as usize
+}
+
+/// The file formats of terminal emulators
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum TerminalFormat {
+    #[default]
+    Alacritty,
+    Kitty,
+    WezTerm,
+    WindowsTerminal,
+    ITerm2,
+    Xresources,
+}
+
+impl TerminalFormat {
+    pub const ALL: [TerminalFormat; 6] = [
+        TerminalFormat::Alacritty,
+        TerminalFormat::Kitty,
+        TerminalFormat::WezTerm,
+        TerminalFormat::WindowsTerminal,
+        TerminalFormat::ITerm2,
+        TerminalFormat::Xresources,
+    ];
+
+    pub fn file_name(&self) -> &'static str {
+        match self {
+            TerminalFormat::Alacritty => "palettepal.toml",
+            TerminalFormat::Kitty => "palettepal.conf",
+            TerminalFormat::WezTerm => "palettepal.lua",
+            TerminalFormat::WindowsTerminal => "palettepal.json",
+            TerminalFormat::ITerm2 => "palettepal.itermcolors",
+            TerminalFormat::Xresources => "palettepal.Xresources",
+        }
+    }
+
+    /// Whether the format can set the colors beyond the 16 ANSI colors
+    pub fn supports_256_colors(&self) -> bool {
+        !matches!(
+            self,
+            TerminalFormat::WindowsTerminal | TerminalFormat::ITerm2
+        )
+    }
+}
+
+impl Display for TerminalFormat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TerminalFormat::Alacritty => write!(f, "Alacritty"),
+            TerminalFormat::Kitty => write!(f, "kitty"),
+            TerminalFormat::WezTerm => write!(f, "WezTerm"),
+            TerminalFormat::WindowsTerminal => write!(f, "Windows Terminal"),
+            TerminalFormat::ITerm2 => write!(f, "iTerm2"),
+            TerminalFormat::Xresources => write!(f, "Xresources"),
+        }
+    }
+}
+
+impl FromStr for TerminalFormat {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|format| format.to_string() == s)
+            .ok_or(())
+    }
+}
+
+/// The colors of a terminal color scheme
+#[derive(Clone, Debug, PartialEq)]
+pub struct TerminalScheme {
+    pub name: String,
+    /// The color of every role, in the order of [TerminalRole::ALL]
+    pub colors: Vec<(TerminalRole, RGBA)>,
+}
+
+impl TerminalScheme {
+    /// Look up the shades of a mapping. Shades that don't exist are black
+    pub fn from_mapping(
+        name: &str,
+        swatches: &[(String, Vec<RGBA>)],
+        mapping: &[(TerminalRole, ShadeRef)],
+    ) -> Self {
+        let colors = mapping
+            .iter()
+            .map(|(role, shade)| {
+                let color = swatches
+                    .get(shade.color)
+                    .and_then(|(_, shades)| shades.get(shade.shade))
+                    .copied()
+                    .unwrap_or(RGBA::opaque(0, 0, 0));
+
+                (*role, color.rgb().into())
+            })
+            .collect();
+
+        Self {
+            name: name.to_string(),
+            colors,
+        }
+    }
+
+    pub fn color(&self, role: TerminalRole) -> RGBA {
+        self.colors
+            .iter()
+            .find(|(r, _)| *r == role)
+            .map(|(_, color)| *color)
+            .unwrap_or(RGBA::opaque(0, 0, 0))
+    }
+
+    /// The 16 ANSI colors by their color number, the normal colors followed by the bright ones
+    pub fn ansi_colors(&self) -> Vec<RGBA> {
+        let normal = AnsiColor::ALL.map(|ansi| self.color(TerminalRole::Normal(ansi)));
+        let bright = AnsiColor::ALL.map(|ansi| self.color(TerminalRole::Bright(ansi)));
+
+        normal.into_iter().chain(bright).collect()
+    }
+
+    /// Colors 16 to 255 of the 256 color palette. The 6x6x6 color cube is blended between the
+    /// background, the 6 normal hues and the foreground at its corners, and the grey ramp from
+    /// the background to the foreground. Colors are blended in OKLab
+    pub fn extended_colors(&self) -> Vec<RGBA> {
+        let oklab = |role: TerminalRole| Oklab::from(self.color(role).rgb());
+        let normal = |ansi: AnsiColor| oklab(TerminalRole::Normal(ansi));
+
+        let background = oklab(TerminalRole::Background);
+        let foreground = oklab(TerminalRole::Foreground);
+
+        // Corners of the cube, indexed by red + 2 * green + 4 * blue
+        let corners = [
+            background,
+            normal(AnsiColor::Red),
+            normal(AnsiColor::Green),
+            normal(AnsiColor::Yellow),
+            normal(AnsiColor::Blue),
+            normal(AnsiColor::Magenta),
+            normal(AnsiColor::Cyan),
+            foreground,
+        ];
+
+        let mut colors = Vec::with_capacity(240);
+
+        for r in 0..6 {
+            for g in 0..6 {
+                for b in 0..6 {
+                    let [r, g, b] = [r, g, b].map(|step| step as f32 / 5.);
+                    let mut blended = Oklab::default();
+
+                    for (idx, corner) in corners.iter().enumerate() {
+                        let weight = |t: f32, bit: usize| if idx & bit != 0 { t } else { 1. - t };
+                        let weight = weight(r, 1) * weight(g, 2) * weight(b, 4);
+
+                        blended.l += corner.l * weight;
+                        blended.a += corner.a * weight;
+                        blended.b += corner.b * weight;
+                    }
+
+                    colors.push(oklab_to_rgba(blended));
+                }
+            }
+        }
+
+        for step in 1..=24 {
+            colors.push(oklab_to_rgba(mix(
+                background,
+                foreground,
+                step as f32 / 25.,
+            )));
+        }
+
+        colors
+    }
+
+    pub fn export(&self, format: TerminalFormat, include_256_colors: bool) -> String {
+        let extended = match include_256_colors && format.supports_256_colors() {
+            true => self.extended_colors(),
+            false => vec![],
+        };
+
+        match format {
+            TerminalFormat::Alacritty => self.to_alacritty(&extended),
+            TerminalFormat::Kitty => self.to_kitty(&extended),
+            TerminalFormat::WezTerm => self.to_wezterm(&extended),
+            TerminalFormat::WindowsTerminal => self.to_windows_terminal(),
+            TerminalFormat::ITerm2 => self.to_iterm2(),
+            TerminalFormat::Xresources => self.to_xresources(&extended),
+        }
+    }
+
+    fn hex(&self, role: TerminalRole) -> String {
+        self.color(role).to_hex()
+    }
+
+    fn to_alacritty(&self, extended: &[RGBA]) -> String {
+        let ansi_table = |table: &str, role: fn(AnsiColor) -> TerminalRole| {
+            let mut section = format!("[colors.{table}]\n");
+
+            for ansi in AnsiColor::ALL {
+                section += &format!(
+                    "{} = \"{}\"\n",
+                    ansi.to_string().to_lowercase(),
+                    self.hex(role(ansi))
+                );
+            }
+
+            section
+        };
+
+        let mut toml = format!("# {}\n\n", self.name);
+        toml += &format!(
+            "[colors.primary]\nbackground = \"{}\"\nforeground = \"{}\"\n\n",
+            self.hex(TerminalRole::Background),
+            self.hex(TerminalRole::Foreground)
+        );
+        toml += &format!(
+            "[colors.cursor]\ntext = \"{}\"\ncursor = \"{}\"\n\n",
+            self.hex(TerminalRole::Background),
+            self.hex(TerminalRole::Cursor)
+        );
+        toml += &format!(
+            "[colors.selection]\ntext = \"{}\"\nbackground = \"{}\"\n\n",
+            self.hex(TerminalRole::Foreground),
+            self.hex(TerminalRole::Selection)
+        );
+        toml += &ansi_table("normal", TerminalRole::Normal);
+        toml += "\n";
+        toml += &ansi_table("bright", TerminalRole::Bright);
+
+        for (idx, color) in (16..).zip(extended) {
+            toml += &format!(
+                "\n[[colors.indexed_colors]]\nindex = {idx}\ncolor = \"{}\"\n",
+                color.to_hex()
+            );
+        }
+
+        toml
+    }
+
+    fn to_kitty(&self, extended: &[RGBA]) -> String {
+        let mut conf = format!("# {}\n\n", self.name);
+
+        for (key, role) in [
+            ("background", TerminalRole::Background),
+            ("foreground", TerminalRole::Foreground),
+            ("cursor", TerminalRole::Cursor),
+            ("cursor_text_color", TerminalRole::Background),
+            ("selection_background", TerminalRole::Selection),
+            ("selection_foreground", TerminalRole::Foreground),
+        ] {
+            conf += &format!("{key} {}\n", self.hex(role));
+        }
+
+        conf += "\n";
+
+        for (idx, color) in self
+            .ansi_colors()
+            .into_iter()
+            .chain(extended.to_vec())
+            .enumerate()
+        {
+            conf += &format!("color{idx} {}\n", color.to_hex());
+        }
+
+        conf
+    }
+
+    fn to_wezterm(&self, extended: &[RGBA]) -> String {
+        let list = |colors: &[RGBA]| {
+            colors
+                .iter()
+                .map(|color| format!("\"{}\"", color.to_hex()))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+
+        let ansi = self.ansi_colors();
+        let mut lua = format!(
+            "-- {}\n-- config.colors = require(\"palettepal\")\nreturn {{\n",
+            self.name
+        );
+
+        for (key, role) in [
+            ("background", TerminalRole::Background),
+            ("foreground", TerminalRole::Foreground),
+            ("cursor_bg", TerminalRole::Cursor),
+            ("cursor_fg", TerminalRole::Background),
+            ("cursor_border", TerminalRole::Cursor),
+            ("selection_bg", TerminalRole::Selection),
+            ("selection_fg", TerminalRole::Foreground),
+        ] {
+            lua += &format!("  {key} = \"{}\",\n", self.hex(role));
+        }
+
+        lua += &format!("  ansi = {{ {} }},\n", list(&ansi[..8]));
+        lua += &format!("  brights = {{ {} }},\n", list(&ansi[8..]));
+
+        if !extended.is_empty() {
+            lua += "  indexed = {\n";
+
+            for (idx, color) in (16..).zip(extended) {
+                lua += &format!("    [{idx}] = \"{}\",\n", color.to_hex());
+            }
+
+            lua += "  },\n";
+        }
+
+        lua += "}\n";
+        lua
+    }
+
+    fn to_windows_terminal(&self) -> String {
+        let mut scheme = Map::new();
+        scheme.insert("name".to_string(), self.name.clone().into());
+
+        for (key, role) in [
+            ("background", TerminalRole::Background),
+            ("foreground", TerminalRole::Foreground),
+            ("cursorColor", TerminalRole::Cursor),
+            ("selectionBackground", TerminalRole::Selection),
+        ] {
+            scheme.insert(key.to_string(), self.hex(role).into());
+        }
+
+        for (prefix, role) in [
+            ("", TerminalRole::Normal as fn(AnsiColor) -> TerminalRole),
+            ("bright", TerminalRole::Bright),
+        ] {
+            for ansi in AnsiColor::ALL {
+                // Windows Terminal calls magenta purple
+                let name = match ansi {
+                    AnsiColor::Magenta => "Purple".to_string(),
+                    ansi => ansi.to_string(),
+                };
+                let key = match prefix {
+                    "" => name.to_lowercase(),
+                    prefix => format!("{prefix}{name}"),
+                };
+
+                scheme.insert(key, self.hex(role(ansi)).into());
+            }
+        }
+
+        serde_json::to_string_pretty(&Value::Object(scheme)).unwrap_or_default()
+    }
+
+    fn to_iterm2(&self) -> String {
+        let mut entries = self
+            .ansi_colors()
+            .into_iter()
+            .enumerate()
+            .map(|(idx, color)| (format!("Ansi {idx} Color"), color))
+            .collect::<Vec<_>>();
+
+        for (key, role) in [
+            ("Background Color", TerminalRole::Background),
+            ("Foreground Color", TerminalRole::Foreground),
+            ("Bold Color", TerminalRole::Foreground),
+            ("Cursor Color", TerminalRole::Cursor),
+            ("Cursor Text Color", TerminalRole::Background),
+            ("Selection Color", TerminalRole::Selection),
+            ("Selected Text Color", TerminalRole::Foreground),
+        ] {
+            entries.push((key.to_string(), self.color(role)));
+        }
+
+        let mut plist = "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+            <!DOCTYPE plist PUBLIC \"-//Apple//DTD PLIST 1.0//EN\" \
+            \"http://www.apple.com/DTDs/PropertyList-1.0.dtd\">\n\
+            <plist version=\"1.0\">\n<dict>\n"
+            .to_string();
+
+        for (key, color) in entries {
+            let component = |value: u8| format!("{:.6}", value as f32 / 255.);
+
+            plist += &format!("\t<key>{key}</key>\n\t<dict>\n");
+            plist += "\t\t<key>Alpha Component</key>\n\t\t<real>1</real>\n";
+            plist += &format!(
+                "\t\t<key>Blue Component</key>\n\t\t<real>{}</real>\n",
+                component(color.b)
+            );
+            plist += "\t\t<key>Color Space</key>\n\t\t<string>sRGB</string>\n";
+            plist += &format!(
+                "\t\t<key>Green Component</key>\n\t\t<real>{}</real>\n",
+                component(color.g)
+            );
+            plist += &format!(
+                "\t\t<key>Red Component</key>\n\t\t<real>{}</real>\n",
+                component(color.r)
+            );
+            plist += "\t</dict>\n";
+        }
+
+        plist += "</dict>\n</plist>\n";
+        plist
+    }
+
+    fn to_xresources(&self, extended: &[RGBA]) -> String {
+        let mut resources = format!("! {}\n\n", self.name);
+
+        for (key, role) in [
+            ("background", TerminalRole::Background),
+            ("foreground", TerminalRole::Foreground),
+            ("cursorColor", TerminalRole::Cursor),
+            ("highlightColor", TerminalRole::Selection),
+        ] {
+            resources += &format!("*.{key}: {}\n", self.hex(role));
+        }
+
+        resources += "\n";
+
+        for (idx, color) in self
+            .ansi_colors()
+            .into_iter()
+            .chain(extended.to_vec())
+            .enumerate()
+        {
+            resources += &format!("*.color{idx}: {}\n", color.to_hex());
+        }
+
+        resources
+    }
+}
+
+pub(crate) fn mix(a: Oklab, b: Oklab, t: f32) -> Oklab {
+    Oklab {
+        l: a.l + (b.l - a.l) * t,
+        a: a.a + (b.a - a.a) * t,
+        b: a.b + (b.b - a.b) * t,
+    }
+}
+
+pub(crate) fn oklab_to_rgba(color: Oklab) -> RGBA {
+    Srgb8::from(color).into()
+}
+
+impl Palette {
+    /// A terminal color scheme with the default mapping of the palette's shades, see
+    /// [default_mapping]. Wide gamut shades are replaced by their sRGB fallbacks
+    pub fn to_terminal_scheme(&self, theme: TerminalTheme) -> TerminalScheme {
+        let swatches = self.to_sheet_swatches();
+        let mapping = default_mapping(&swatches, theme);
+
+        TerminalScheme::from_mapping("palettepal", &swatches, &mapping)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// 11 shades of a hue from light to dark
+    fn ramp(name: &str, h: f32, c: f32) -> (String, Vec<RGBA>) {
+        let shades = (0..11)
+            .map(|idx| {
+                Srgb8::from(Oklch {
+                    l: 0.97 - idx as f32 * 0.08,
+                    c,
+                    h,
+                })
+                .into()
+            })
+            .collect();
+
+        (name.to_string(), shades)
+    }
+
+    fn swatches() -> Vec<(String, Vec<RGBA>)> {
+        vec![
+            ramp("teal", 185., 0.1),
+            ramp("red", 25., 0.15),
+            ramp("blue", 260., 0.12),
+            ramp("grey", 260., 0.01),
+        ]
+    }
+
+    #[test]
+    fn test_default_mapping() {
+        let swatches = swatches();
+        let mapping = default_mapping(&swatches, TerminalTheme::Dark);
+        let shade = |role: TerminalRole| mapping.iter().find(|(r, _)| *r == role).unwrap().1;
+
+        assert_eq!(mapping.len(), TerminalRole::ALL.len());
+        assert_eq!(
+            shade(TerminalRole::Background),
+            ShadeRef {
+                color: 3,
+                shade: 10
+            }
+        );
+        assert_eq!(
+            shade(TerminalRole::Foreground),
+            ShadeRef { color: 3, shade: 2 }
+        );
+        assert_eq!(shade(TerminalRole::Normal(AnsiColor::Red)).color, 1);
+        assert_eq!(shade(TerminalRole::Normal(AnsiColor::Magenta)).color, 1);
+        assert_eq!(
+            shade(TerminalRole::Bright(AnsiColor::Blue)),
+            ShadeRef { color: 2, shade: 3 }
+        );
+        assert_eq!(shade(TerminalRole::Normal(AnsiColor::Cyan)).color, 0);
+
+        let light = default_mapping(&swatches, TerminalTheme::Light);
+
+        assert_eq!(
+            light[0],
+            (TerminalRole::Background, ShadeRef { color: 3, shade: 0 })
+        );
+    }
+
+    #[test]
+    fn test_extended_colors() {
+        let swatches = swatches();
+        let scheme = TerminalScheme::from_mapping(
+            "test",
+            &swatches,
+            &default_mapping(&swatches, TerminalTheme::Dark),
+        );
+
+        let extended = scheme.extended_colors();
+
+        assert_eq!(extended.len(), 240);
+        // The corners of the cube are the background, the normal colors and the foreground
+        assert_eq!(extended[0], scheme.color(TerminalRole::Background));
+        assert_eq!(
+            extended[5 * 36],
+            scheme.color(TerminalRole::Normal(AnsiColor::Red))
+        );
+        assert_eq!(extended[215], scheme.color(TerminalRole::Foreground));
+        assert_ne!(extended[216], scheme.color(TerminalRole::Background));
+    }
+
+    #[test]
+    fn test_exports() {
+        let palette = Palette::new();
+        let scheme = palette.to_terminal_scheme(TerminalTheme::Dark);
+        let background = scheme.hex(TerminalRole::Background);
+
+        let alacritty = scheme.export(TerminalFormat::Alacritty, true);
+
+        assert!(alacritty.contains(&format!("[colors.primary]\nbackground = \"{background}\"")));
+        assert!(alacritty.contains("[colors.bright]\nblack = \""));
+        assert!(alacritty.contains("index = 255\n"));
+
+        let kitty = scheme.export(TerminalFormat::Kitty, false);
+
+        assert!(kitty.contains(&format!("background {background}\n")));
+        assert!(kitty.contains("color15 #"));
+        assert!(!kitty.contains("color16 "));
+
+        let wezterm = scheme.export(TerminalFormat::WezTerm, true);
+
+        assert_eq!(wezterm.matches("ansi = { \"#").count(), 1);
+        assert!(wezterm.contains("    [16] = \"#"));
+
+        let windows_terminal: Value =
+            serde_json::from_str(&scheme.export(TerminalFormat::WindowsTerminal, true)).unwrap();
+
+        assert_eq!(windows_terminal["name"], "palettepal");
+        assert_eq!(windows_terminal["background"], background.as_str());
+        assert!(windows_terminal["brightPurple"].is_string());
+        assert_eq!(windows_terminal.as_object().unwrap().len(), 21);
+
+        let iterm2 = scheme.export(TerminalFormat::ITerm2, true);
+
+        assert!(iterm2.contains("<key>Ansi 15 Color</key>"));
+        assert!(!iterm2.contains("Ansi 16"));
+        assert_eq!(iterm2.matches("<key>Red Component</key>").count(), 23);
+
+        let xresources = scheme.export(TerminalFormat::Xresources, true);
+
+        assert!(xresources.contains(&format!("*.background: {background}\n")));
+        assert!(xresources.contains("*.color255: #"));
+    }
+}
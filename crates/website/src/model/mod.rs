@@ -1,4 +1 @@
-pub mod palette;
-pub mod palette_color;
-pub mod sampling;
-pub mod sampling_curve;
+pub mod palette_file;
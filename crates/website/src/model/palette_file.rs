@@ -0,0 +1,13 @@
+use crate::views::tools::ToolsViewState;
+use palette::palette::Palette;
+use serde::{Deserialize, Serialize};
+
+/// The contents of a `.palettepal` file. The open tools are saved along with the palette, in
+/// the same object
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PaletteFile {
+    #[serde(flatten)]
+    pub palette: Palette,
+    #[serde(default)]
+    pub tools_view_state: ToolsViewState,
+}
@@ -1,10 +1,11 @@
 use crate::mixins::panel::panel_mixin;
-use crate::model::palette_color::PaletteColor;
-use crate::model::sampling::static_sample_signal;
-use crate::model::sampling_curve::SamplingCurve;
 use crate::views::geometry::color_cake;
 use crate::views::svg_icons::{svg_button, Icons};
 use crate::views::tools::curve_editor::curve_editor_inner;
+use crate::views::vision_simulation::{simulated_colors_signal, simulation_signal};
+use color::contrast::RGBA;
+use color::conversion::{Hsl, Srgb8};
+use color::gamut::Gamut;
 use dominator::Dom;
 use dwind::prelude::*;
 use dwui::prelude::*;
@@ -14,6 +15,9 @@ use futures_signals::signal::{option, LocalBoxSignal, Mutable, Signal, SignalExt
 use futures_signals::signal_map::{MutableBTreeMap, SignalMapExt};
 use futures_signals::signal_vec::SignalVecExt;
 use once_cell::sync::Lazy;
+use palette::palette_color::PaletteColor;
+use palette::sampling::static_sample_signal;
+use palette::sampling_curve::SamplingCurve;
 use std::f32::consts::PI;
 use uuid::Uuid;
 
@@ -22,11 +26,15 @@ static COPIED_COLOR: Lazy<Mutable<Option<PaletteColor>>> = Lazy::new(|| Mutable:
 pub fn color_panel(
     color: PaletteColor,
     sampling_curves: MutableBTreeMap<Uuid, SamplingCurve>,
+    gamut: Mutable<Gamut>,
+    alpha_background: Mutable<Srgb8>,
 ) -> Dom {
     let hue: Mutable<f32> = color.hue.clone();
     let hue2: Mutable<f32> = color.hue.clone();
 
     let shades_signal = color.colors_u8_signal(&sampling_curves);
+    let alpha_shades_signal =
+        color.alpha_colors_signal(&sampling_curves, gamut.signal(), alpha_background.signal());
     let show_advanced = Mutable::new(false);
 
     let advanced_settings = map_ref! {
@@ -130,6 +138,9 @@ pub fn color_panel(
                             .options(vec![
                                 ("HSV".to_string(), "HSV".to_string()),
                                 ("HSL".to_string(), "HSL".to_string()),
+                                ("OKLCH".to_string(), "OKLCH".to_string()),
+                                ("LCH".to_string(), "LCH".to_string()),
+                                ("HSLuv".to_string(), "HSLuv".to_string()),
                             ])
                         }),
                         slider!({
@@ -173,6 +184,7 @@ pub fn color_panel(
                 }))
             }))
             .child(horizontal_color_bar(shades_signal))
+            .child(alpha_color_bar(alpha_shades_signal, alpha_background))
             .child_signal(advanced_settings)
         }))
     })
@@ -181,11 +193,13 @@ pub fn color_panel(
 fn color_edit(color: PaletteColor, sampling_curves: MutableBTreeMap<Uuid, SamplingCurve>) -> Dom {
     let curves = sampling_curves.clone();
     let curve_id = color.sampling_curve_id.clone();
+    let color_space = color.color_space.read_only();
 
     let sampled_colors_signal = map_ref! {
         let curve_id = color.sampling_curve_id.signal(),
         let _angle = color.color_plane_angle.signal_cloned(),
         let _hue = color.hue.signal_cloned(),
+        let _space = color.color_space.signal(),
         let sampling_rect = color.sampling_rect.signal_cloned() => move {
             sampling_curves
                 .signal_map_cloned()
@@ -203,9 +217,9 @@ fn color_edit(color: PaletteColor, sampling_curves: MutableBTreeMap<Uuid, Sampli
     }
     .flatten()
     .to_signal_vec()
-    .map(|(r, g, b)| {
+    .map(|rgb| {
         html!("div", {
-            .text(&format!("{r:X}{g:X}{b:X}"))
+            .text(&rgb.to_hex())
         })
     });
 
@@ -215,10 +229,12 @@ fn color_edit(color: PaletteColor, sampling_curves: MutableBTreeMap<Uuid, Sampli
         .flatten();
 
     let editor = sampling_curve_signal.map(move |curve| {
+        let color_space = color_space.clone();
+
         curve.map(move |curve| {
             html!("div", {
                 .dwclass!("w-64 h-64")
-                .child(curve_editor_inner(curve, false))
+                .child(curve_editor_inner(curve, false, Some(color_space)))
             })
         })
     });
@@ -235,33 +251,59 @@ fn color_edit(color: PaletteColor, sampling_curves: MutableBTreeMap<Uuid, Sampli
     })
 }
 
-fn horizontal_color_bar(shades_signal: impl Signal<Item = Vec<(u8, u8, u8)>> + 'static) -> Dom {
+fn horizontal_color_bar(shades_signal: impl Signal<Item = Vec<RGBA>> + 'static) -> Dom {
     html!("div", {
         .dwclass!("flex flex-row w-full justify-center m-t-4 p-l-16 p-r-16")
-        .children_signal_vec(shades_signal.to_signal_vec().map(|shade| {
-            let color = format!("rgb({}, {}, {})", shade.0, shade.1, shade.2);
-
+        .children_signal_vec(simulated_colors_signal(shades_signal).to_signal_vec().map(|shade| {
             html!("div", {
                 .dwclass!("@sm:aspect-video @<sm:aspect-square flex-1 max-h-12")
-                .style("background-color", color)
+                .style("background-color", shade.to_css())
             })
         }))
     })
 }
 
+/// The alpha scale, drawn on top of the background it was computed against
+fn alpha_color_bar(
+    shades_signal: impl Signal<Item = Vec<RGBA>> + 'static,
+    background: Mutable<Srgb8>,
+) -> Dom {
+    let background_signal = map_ref! {
+        let background = background.signal(),
+        let simulation = simulation_signal() => {
+            let (simulation, severity) = *simulation;
+
+            simulation.simulate((*background).into(), severity).to_css()
+        }
+    };
+
+    html!("div", {
+        .dwclass!("flex flex-row w-full justify-center p-l-16 p-r-16")
+        .child(html!("div", {
+            .dwclass!("flex flex-row flex-1 p-t-2 p-b-2")
+            .style_signal("background-color", background_signal)
+            .children_signal_vec(simulated_colors_signal(shades_signal).to_signal_vec().map(|shade| {
+                html!("div", {
+                    .dwclass!("@sm:aspect-video @<sm:aspect-square flex-1 max-h-8")
+                    .attr("title", &shade.to_hex())
+                    .style("background-color", shade.to_css())
+                })
+            }))
+        }))
+    })
+}
+
 struct HueHexWrapper(Mutable<f32>);
 
 impl InputValueWrapper for HueHexWrapper {
     fn set(&self, value: String) -> ValidationResult {
-        let Ok(hex) = hex_color::HexColor::parse(&value) else {
+        let Ok(rgb) = value.parse::<Srgb8>() else {
             return ValidationResult::Invalid {
                 message: "Invalid hex color".to_string(),
             };
         };
 
-        let hsl = hsl::HSL::from_rgb(&[hex.r, hex.g, hex.b]);
-
-        self.0.set(hsl.h as f32);
+        self.0.set(Hsl::from(rgb).h);
 
         ValidationResult::Valid
     }
@@ -269,15 +311,7 @@ impl InputValueWrapper for HueHexWrapper {
     fn value_signal_cloned(&self) -> LocalBoxSignal<'static, String> {
         self.0
             .signal_cloned()
-            .map(|h| {
-                let hsl = hsl::HSL {
-                    h: h as f64,
-                    s: 1.,
-                    l: 0.5,
-                };
-                let rgb = hsl.to_rgb();
-                format!("#{:02x}{:02x}{:02x}", rgb.0, rgb.1, rgb.2)
-            })
+            .map(|h| Srgb8::from(Hsl { h, s: 1., l: 0.5 }).to_hex())
             .boxed_local()
     }
 }
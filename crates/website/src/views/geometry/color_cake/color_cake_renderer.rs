@@ -1,12 +1,12 @@
-use crate::model::palette_color::{CakeType, ColorSpace};
 use crate::views::geometry::color_cake::brick_geometry::brick_triangles;
 use crate::views::geometry::cylinder_geometry;
 use crate::views::geometry::cylinder_geometry::make_cylinder;
-use crate::views::geometry::shader_program::ShaderProgram;
+use crate::views::geometry::shader_program::{include_color_spaces, ShaderProgram};
 use crate::views::geometry::transform::Transform;
 use anyhow::anyhow;
 use futures_signals::signal::Mutable;
 use glam::{Mat4, Vec2};
+use palette::palette_color::{CakeType, ColorSpace};
 use web_sys::WebGl2RenderingContext;
 
 pub struct ColorCake {
@@ -30,7 +30,7 @@ impl ColorCake {
         let shader_program = ShaderProgram::new(
             context,
             include_str!("shaders/cake_vertex.glsl"),
-            include_str!("shaders/cake_fragment.glsl"),
+            &include_color_spaces(include_str!("shaders/cake_fragment.glsl")),
             vertices,
         )?;
 
@@ -112,11 +112,6 @@ impl ColorCake {
         context.enable_vertex_attrib_array(position_location as u32);
         context.enable_vertex_attrib_array(color_location as u32);
 
-        let color_space = match color_space {
-            ColorSpace::HSV => 0,
-            ColorSpace::HSL => 1,
-        };
-
         let scale = self.transform.scale;
         let view_matrix = self.transform.projection;
 
@@ -127,7 +122,11 @@ impl ColorCake {
         }
 
         WebGl2RenderingContext::uniform1f(context, hue_location.as_ref(), hue);
-        WebGl2RenderingContext::uniform1i(context, space_location.as_ref(), color_space);
+        WebGl2RenderingContext::uniform1i(
+            context,
+            space_location.as_ref(),
+            color_space.shader_id(),
+        );
         WebGl2RenderingContext::uniform_matrix4fv_with_f32_array(
             context,
             matrix_location.as_ref(),
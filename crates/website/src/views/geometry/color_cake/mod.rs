@@ -3,8 +3,6 @@ pub mod color_cake_renderer;
 pub mod cylinder_geometry;
 pub mod transform;
 
-use crate::model::palette_color::{CakeType, PaletteColor};
-use crate::model::sampling_curve::SamplingCurve;
 use crate::views::geometry::color_cake_renderer::ColorCake;
 use crate::views::geometry::transform::Plane;
 use crate::widgets::shader_canvas::*;
@@ -14,6 +12,8 @@ use futures_signals::map_ref;
 use futures_signals::signal::{Mutable, SignalExt};
 use futures_signals::signal_map::MutableBTreeMap;
 use glam::{Mat4, Vec2, Vec3};
+use palette::palette_color::{CakeType, PaletteColor};
+use palette::sampling_curve::SamplingCurve;
 use std::rc::Rc;
 use transform::{Transform, AABB};
 use uuid::Uuid;
@@ -41,7 +41,7 @@ pub fn color_cake(
 ) -> Dom {
     let sample_points = color.samples_signal(sampling_curves.clone());
 
-    fn get_curve_aabb(curve: &Vec<Vec2>) -> AABB {
+    fn get_curve_aabb(curve: &[Vec2]) -> AABB {
         let mut min = Vec2::splat(f32::INFINITY);
         let mut max = Vec2::splat(f32::NEG_INFINITY);
 
@@ -273,9 +273,7 @@ pub fn color_cake(
 
                 b.future(async move {
                     render_data_signal.for_each(|(pos, curve)| {
-                        if pos.is_some() {
-
-                            let mut pos = pos.unwrap();
+                        if let Some(mut pos) = pos {
                             // transform the curve to world space
                             pos.corner.y = 2. * (pos.corner.y - 0.5);
                             pos.dimension.y *= 2.;
@@ -1,8 +1,12 @@
-use crate::views::geometry::shader_program::{ColorSpaceVertex, ShaderProgram};
+use crate::views::geometry::shader_program::{
+    include_color_spaces, ColorSpaceVertex, ShaderProgram,
+};
 use crate::widgets::shader_canvas::*;
 use anyhow::anyhow;
 use dominator::Dom;
+use futures_signals::map_ref;
 use futures_signals::signal::{ReadOnlyMutable, SignalExt};
+use palette::palette_color::ColorSpace;
 use wasm_bindgen::UnwrapThrowExt;
 use web_sys::WebGl2RenderingContext;
 
@@ -44,7 +48,7 @@ impl ColorQuad {
         let shader_program = ShaderProgram::new(
             context,
             include_str!("vertex.glsl"),
-            include_str!("fragment.glsl"),
+            &include_color_spaces(include_str!("fragment.glsl")),
             vertices,
         )?;
 
@@ -55,6 +59,8 @@ impl ColorQuad {
         &mut self,
         context: &WebGl2RenderingContext,
         hue: f32,
+        angle: f32,
+        color_space: ColorSpace,
         resolution: (f32, f32),
     ) -> anyhow::Result<()> {
         let program = &self.shader_program.program;
@@ -64,6 +70,8 @@ impl ColorQuad {
         let color_location = context.get_attrib_location(program, "a_color");
         let resolution_location = context.get_uniform_location(program, "u_resolution");
         let hue_location = context.get_uniform_location(program, "u_hue");
+        let angle_location = context.get_uniform_location(program, "u_angle");
+        let space_location = context.get_uniform_location(program, "u_space");
         let buffer = context
             .create_buffer()
             .ok_or(anyhow!("failed to create buffer"))?;
@@ -119,6 +127,12 @@ impl ColorQuad {
         let vertex_count = self.shader_program.vertices.len() as i32;
 
         WebGl2RenderingContext::uniform1f(context, hue_location.as_ref(), hue);
+        WebGl2RenderingContext::uniform1f(context, angle_location.as_ref(), angle);
+        WebGl2RenderingContext::uniform1i(
+            context,
+            space_location.as_ref(),
+            color_space.shader_id(),
+        );
 
         context.clear_color(0.0, 0.0, 0.0, 1.0);
         context.clear(WebGl2RenderingContext::COLOR_BUFFER_BIT);
@@ -128,15 +142,26 @@ impl ColorQuad {
     }
 }
 
-pub fn color_plane(hue: ReadOnlyMutable<f32>) -> Dom {
+pub fn color_plane(
+    hue: ReadOnlyMutable<f32>,
+    angle: ReadOnlyMutable<f32>,
+    color_space: ReadOnlyMutable<ColorSpace>,
+) -> Dom {
     shader_canvas!({
         .ctor(move|context, b| {
             let mut quad = ColorQuad::new(&context).unwrap_throw();
 
+            let plane_signal = map_ref! {
+                let hue = hue.signal(),
+                let angle = angle.signal(),
+                let color_space = color_space.signal() => (*hue, *angle, *color_space)
+            };
+
             b.future(async move {
-                hue.signal().for_each(move |hue| {
+                plane_signal.for_each(move |(hue, angle, color_space)| {
                     let hue = hue /360.;
-                    let _ = quad.draw(&context, hue, (100., 100.)).inspect_err(|e| {
+                    let angle = angle / 360.;
+                    let _ = quad.draw(&context, hue, angle, color_space, (100., 100.)).inspect_err(|e| {
                         log::error!("error: {:?}", e);
                     });
 
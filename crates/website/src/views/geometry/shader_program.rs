@@ -1,6 +1,14 @@
 use crate::views::geometry::gl_utils::{compile_shader, link_program};
 use web_sys::{WebGl2RenderingContext, WebGlProgram};
 
+/// Color space conversions shared between fragment shaders, see [include_color_spaces]
+const COLOR_SPACES_GLSL: &str = include_str!("shaders/color_spaces.glsl");
+
+/// Replaces the `#include <color_spaces>` line of a shader with the shared color space conversions
+pub fn include_color_spaces(shader: &str) -> String {
+    shader.replace("#include <color_spaces>", COLOR_SPACES_GLSL)
+}
+
 #[derive(Clone, Copy, Debug)]
 #[repr(C)]
 pub struct ColorSpaceVertex {
@@ -0,0 +1,281 @@
+// Color space conversions shared by the color space shaders.
+// Every conversion takes (hue in turns, x, y) where x and y are the 0..1 coordinates of the
+// sampling plane, matching ColorSpace::colors_u8
+
+// Chroma at the edge of the sampling plane, see OKLCH_MAX_CHROMA
+const float OKLCH_MAX_CHROMA = 0.4;
+// Chroma at the edge of the sampling plane, see LCH_MAX_CHROMA
+const float LCH_MAX_CHROMA = 150.0;
+
+const vec3 D65_WHITE = vec3(0.9504559, 1.0, 1.089058);
+const float LAB_EPSILON = 216.0 / 24389.0;
+const float LAB_KAPPA = 24389.0 / 27.0;
+const vec2 LUV_WHITE = vec2(0.19783, 0.46832);
+
+// Just noticeable difference in OKLab, see JND in the gamut module of the color crate
+const float JND = 0.02;
+const float GAMUT_EPSILON = 0.0001;
+
+// Columns of the XYZ to linear sRGB matrix
+const mat3 XYZ_TO_LINEAR = mat3(
+    3.24097, -0.9692436, 0.05563008,
+    -1.537383, 1.875968, -0.203977,
+    -0.4986108, 0.04155506, 1.056972
+);
+
+vec3 hsl2rgb( in vec3 c )
+{
+    vec3 rgb = clamp( abs(mod(c.x*6.0+vec3(0.0,4.0,2.0),6.0)-3.0)-1.0, 0.0, 1.0 );
+
+    return c.z + c.y * (rgb-0.5)*(1.0-abs(2.0*c.z-1.0));
+}
+
+vec3 hsv2rgb(vec3 c)
+{
+    vec4 K = vec4(1.0, 2.0 / 3.0, 1.0 / 3.0, 3.0);
+    vec3 p = abs(fract(c.xxx + K.xyz) * 6.0 - K.www);
+    return c.z * mix(K.xxx, clamp(p - K.xxx, 0.0, 1.0), c.y);
+}
+
+vec3 linear2srgb(vec3 c)
+{
+    vec3 a = 1.055 * pow(abs(c), vec3(1.0 / 2.4)) - 0.055;
+    vec3 b = 12.92 * c;
+    return mix(a, b, lessThanEqual(c, vec3(0.0031308)));
+}
+
+bool in_gamut(vec3 c)
+{
+    return all(greaterThanEqual(c, vec3(-0.0001))) && all(lessThanEqual(c, vec3(1.0001)));
+}
+
+vec3 oklab2linear(vec3 c)
+{
+    float l = c.x + 0.3963377774 * c.y + 0.2158037573 * c.z;
+    float m = c.x - 0.1055613458 * c.y - 0.0638541728 * c.z;
+    float s = c.x - 0.0894841775 * c.y - 1.2914855480 * c.z;
+
+    l = l * l * l;
+    m = m * m * m;
+    s = s * s * s;
+
+    return vec3(
+        4.0767416621 * l - 3.3077115913 * m + 0.2309699292 * s,
+        -1.2684380046 * l + 2.6097574011 * m - 0.3413193965 * s,
+        -0.0041960863 * l - 0.7034186147 * m + 1.7076147010 * s
+    );
+}
+
+vec3 oklch2oklab(float l, float c, float h)
+{
+    float angle = h * 6.28318530718;
+    return vec3(l, c * cos(angle), c * sin(angle));
+}
+
+vec3 oklch2linear(float l, float c, float h)
+{
+    return oklab2linear(oklch2oklab(l, c, h));
+}
+
+// Cube root that keeps the sign, for colors outside of the gamut
+vec3 signed_cbrt(vec3 c)
+{
+    return sign(c) * pow(abs(c), vec3(1.0 / 3.0));
+}
+
+vec3 linear2oklab(vec3 c)
+{
+    vec3 lms = signed_cbrt(vec3(
+        0.4122214708 * c.r + 0.5363325363 * c.g + 0.0514459929 * c.b,
+        0.2119034982 * c.r + 0.6806995451 * c.g + 0.1073969566 * c.b,
+        0.0883024619 * c.r + 0.2817188376 * c.g + 0.6299787005 * c.b
+    ));
+
+    return vec3(
+        0.2104542553 * lms.x + 0.7936177850 * lms.y - 0.0040720468 * lms.z,
+        1.9779984951 * lms.x - 2.4285922050 * lms.y + 0.4505937099 * lms.z,
+        0.0259040371 * lms.x + 0.7827717662 * lms.y - 0.8086757660 * lms.z
+    );
+}
+
+// Reduce the chroma until the color is within a just noticeable difference of its clipped
+// form, and return it in linear sRGB. This mirrors gamut_map_oklch in the color crate, so the
+// previews show the colors the palette exports
+vec3 gamut_map_oklch(float l, float c, float h)
+{
+    if (l >= 1.0) {
+        return vec3(1.0);
+    }
+
+    if (l <= 0.0) {
+        return vec3(0.0);
+    }
+
+    vec3 rgb = oklch2linear(l, c, h);
+
+    if (in_gamut(rgb)) {
+        return clamp(rgb, 0.0, 1.0);
+    }
+
+    vec3 clipped = clamp(rgb, 0.0, 1.0);
+
+    if (distance(linear2oklab(clipped), oklch2oklab(l, c, h)) < JND) {
+        return clipped;
+    }
+
+    float low = 0.0;
+    float high = c;
+    bool low_in_gamut = true;
+
+    for (int i = 0; i < 24 && high - low > GAMUT_EPSILON; i++) {
+        float mid = (low + high) / 2.0;
+        vec3 current = oklch2linear(l, mid, h);
+
+        if (low_in_gamut && in_gamut(current)) {
+            low = mid;
+            continue;
+        }
+
+        clipped = clamp(current, 0.0, 1.0);
+        float delta = distance(linear2oklab(clipped), oklch2oklab(l, mid, h));
+
+        if (delta < JND) {
+            if (JND - delta < GAMUT_EPSILON) {
+                return clipped;
+            }
+
+            low_in_gamut = false;
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+
+    return clipped;
+}
+
+// Lightness in 0..100
+float lab_lightness2y(float l)
+{
+    return l > LAB_KAPPA * LAB_EPSILON ? pow((l + 16.0) / 116.0, 3.0) : l / LAB_KAPPA;
+}
+
+float lab_f_inverse(float t)
+{
+    float t3 = t * t * t;
+    return t3 > LAB_EPSILON ? t3 : (116.0 * t - 16.0) / LAB_KAPPA;
+}
+
+vec3 lch2linear(float l, float c, float h)
+{
+    float angle = h * 6.28318530718;
+    float fy = (l + 16.0) / 116.0;
+    float fx = fy + c * cos(angle) / 500.0;
+    float fz = fy - c * sin(angle) / 200.0;
+
+    vec3 xyz = vec3(lab_f_inverse(fx), lab_lightness2y(l), lab_f_inverse(fz)) * D65_WHITE;
+
+    return XYZ_TO_LINEAR * xyz;
+}
+
+vec3 luv2linear(vec3 c)
+{
+    if (c.x <= 0.0) {
+        return vec3(0.0);
+    }
+
+    float u = c.y / (13.0 * c.x) + LUV_WHITE.x;
+    float v = c.z / (13.0 * c.x) + LUV_WHITE.y;
+    float y = lab_lightness2y(c.x) * D65_WHITE.y;
+    float x = y * 9.0 * u / (4.0 * v);
+    float z = y * (12.0 - 3.0 * u - 20.0 * v) / (4.0 * v);
+
+    return XYZ_TO_LINEAR * vec3(x, y, z);
+}
+
+// The largest chroma in the sRGB gamut for the lightness (0..100) and hue (turns),
+// see hsluv_max_chroma in the color crate
+float hsluv_max_chroma(float l, float h)
+{
+    float sub1 = pow(l + 16.0, 3.0) / 1560896.0;
+    float sub2 = sub1 > LAB_EPSILON ? sub1 : l / LAB_KAPPA;
+    float angle = h * 6.28318530718;
+    float max_chroma = 1.0e10;
+
+    for (int channel = 0; channel < 3; channel++) {
+        float m1 = XYZ_TO_LINEAR[0][channel];
+        float m2 = XYZ_TO_LINEAR[1][channel];
+        float m3 = XYZ_TO_LINEAR[2][channel];
+
+        for (int t = 0; t < 2; t++) {
+            float top1 = (284517.0 * m1 - 94839.0 * m3) * sub2;
+            float top2 = (838422.0 * m3 + 769860.0 * m2 + 731718.0 * m1) * l * sub2 - 769860.0 * float(t) * l;
+            float bottom = (632260.0 * m3 - 126452.0 * m2) * sub2 + 126452.0 * float(t);
+            float length = (top2 / bottom) / (sin(angle) - (top1 / bottom) * cos(angle));
+
+            if (length >= 0.0) {
+                max_chroma = min(max_chroma, length);
+            }
+        }
+    }
+
+    return max_chroma;
+}
+
+vec3 oklch2rgb(vec3 c)
+{
+    float l = clamp(c.z, 0.0, 1.0);
+
+    return linear2srgb(gamut_map_oklch(l, c.y * OKLCH_MAX_CHROMA, c.x));
+}
+
+// Colors outside of sRGB are gamut mapped in OKLCH, like gamut_map in the color crate
+vec3 lch2rgb(vec3 c)
+{
+    float l = clamp(c.z, 0.0, 1.0) * 100.0;
+    vec3 rgb = lch2linear(l, c.y * LCH_MAX_CHROMA, c.x);
+
+    if (in_gamut(rgb)) {
+        return linear2srgb(clamp(rgb, 0.0, 1.0));
+    }
+
+    vec3 oklab = linear2oklab(rgb);
+    float hue = atan(oklab.z, oklab.y) / 6.28318530718;
+
+    return linear2srgb(gamut_map_oklch(oklab.x, length(oklab.yz), hue));
+}
+
+vec3 hsluv2rgb(vec3 c)
+{
+    float l = clamp(c.z, 0.0, 1.0) * 100.0;
+
+    if (l > 99.9999) {
+        return vec3(1.0);
+    }
+
+    if (l < 0.0001) {
+        return vec3(0.0);
+    }
+
+    float chroma = hsluv_max_chroma(l, c.x) * clamp(c.y, 0.0, 1.0);
+    float angle = c.x * 6.28318530718;
+    vec3 rgb = luv2linear(vec3(l, chroma * cos(angle), chroma * sin(angle)));
+
+    return linear2srgb(clamp(rgb, 0.0, 1.0));
+}
+
+// The space ids match the u_space uniform set by the renderers
+vec3 color_space2rgb(int space, vec3 c)
+{
+    if (space == 0) {
+        return hsv2rgb(c);
+    } else if (space == 1) {
+        return hsl2rgb(c);
+    } else if (space == 2) {
+        return oklch2rgb(c);
+    } else if (space == 3) {
+        return lch2rgb(c);
+    } else {
+        return hsluv2rgb(c);
+    }
+}
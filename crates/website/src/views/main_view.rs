@@ -1,31 +1,44 @@
 use crate::mixins::panel::panel_mixin;
-use crate::model::palette::Palette;
 use crate::views::color_panel::color_panel;
-use crate::views::palette_controls::palette_controls;
+use crate::views::palette_controls::{download_file, palette_controls};
+use crate::views::tools::ToolsViewState;
 use crate::widgets::menu_overlay::menu_overlay;
+use color::contrast::RGBA;
 use dominator::Dom;
 use dwind::prelude::*;
+use dwui::prelude::*;
+use futures_signals::map_ref;
 use futures_signals::signal::{always, Mutable, Signal, SignalExt};
 use futures_signals::signal_vec::SignalVecExt;
+use palette::palette::Palette;
+use palette::swatch_sheet::{
+    encode_indexed_png, SheetOrientation, SwatchLabels, SwatchSheet, SwatchSheetError,
+    SwatchSheetOptions,
+};
 use serde::{Deserialize, Serialize};
-use wasm_bindgen::JsCast;
-use web_sys::{CanvasRenderingContext2d, HtmlCanvasElement};
+use wasm_bindgen::{Clamped, JsCast, UnwrapThrowExt};
+use web_sys::{window, CanvasRenderingContext2d, HtmlCanvasElement, ImageData};
+
+/// The shades of every palette color by name, to draw on a swatch sheet
+pub type ExportImage = Vec<(String, Vec<RGBA>)>;
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct PalettePalViewModel {
     pub palette: Mutable<Palette>,
+    pub tools_view_state: ToolsViewState,
     pub export_file_content: Mutable<Option<String>>,
-    pub export_image_content: Mutable<Option<Vec<Vec<(u8, u8, u8)>>>>,
+    pub export_image_content: Mutable<Option<ExportImage>>,
 }
 
 pub fn main_view() -> Dom {
     let palette = Mutable::new(Palette::new());
 
     let export_file_content: Mutable<Option<String>> = Mutable::new(None);
-    let export_image_content: Mutable<Option<Vec<Vec<(u8, u8, u8)>>>> = Mutable::new(None);
+    let export_image_content: Mutable<Option<ExportImage>> = Mutable::new(None);
 
     let vm = PalettePalViewModel {
         palette,
+        tools_view_state: ToolsViewState::default(),
         export_file_content,
         export_image_content,
     };
@@ -59,14 +72,13 @@ pub fn palette_view(vm: PalettePalViewModel) -> Dom {
                         }))
                     })})
                 }))
-                // Renders the palette as a PNG export
-                // 1 row per color
+                // Renders the palette as a PNG swatch sheet
                 .child_signal(export_png_view(&vm))
-                .children_signal_vec(vm.palette.get_cloned().tools_view_state.tools_children_signal(vm.clone()))
+                .children_signal_vec(vm.tools_view_state.tools_children_signal(vm.clone()))
                 .child(html!("div", {
                     .dwclass!("flex flex-wrap flex-row w-full")
                     .children_signal_vec(palette.colors.signal_vec_cloned().map(clone!(palette => move |color| {
-                        color_panel(color, palette.sampling_curves.clone())
+                        color_panel(color, palette.sampling_curves.clone(), palette.gamut.clone(), palette.alpha_background.clone())
                     })))
                 }))
             }))
@@ -76,41 +88,139 @@ pub fn palette_view(vm: PalettePalViewModel) -> Dom {
 
 fn export_png_view(vm: &PalettePalViewModel) -> impl Signal<Item = Option<Dom>> {
     vm.export_image_content.signal_cloned().map(move |content| {
-        content.map(|content| {
-            if content.is_empty() || content[0].is_empty() {
-                return html!("div", {})
-            }
+        content.map(|swatches| {
+            let defaults = SwatchSheetOptions::default();
+            let swatch_size = Mutable::new(defaults.swatch_size as f32);
+            let gap = Mutable::new(defaults.gap as f32);
+            let labels = Mutable::new(defaults.labels);
+            let orientation = Mutable::new(defaults.orientation);
 
-            let width = content[0].len();
-            let height = content.len();
+            let options_signal = map_ref! {
+                let swatch_size = swatch_size.signal(),
+                let gap = gap.signal(),
+                let labels = labels.signal(),
+                let orientation = orientation.signal() => {
+                    SwatchSheetOptions {
+                        swatch_size: *swatch_size as u32,
+                        gap: *gap as u32,
+                        labels: *labels,
+                        orientation: *orientation,
+                        ..defaults
+                    }
+                }
+            };
 
             html!("div", {
                 .apply(panel_mixin)
-                .dwclass!("p-4 @>sm:w-full @<sm:w-sm")
-                .child(html!("canvas" => HtmlCanvasElement, {
-                    .dwclass!("w-full h-12")
-                    .style("image-rendering", "pixelated")
-                    .attr("width", &width.to_string())
-                    .attr("height", &height.to_string())
-                    .after_inserted(move |node| {
-                        let context = node.get_context("2d").unwrap().unwrap().dyn_into::<CanvasRenderingContext2d>().unwrap();
-                        let mut x = 0;
-                        let mut y = 0;
-
-                        for shade in content {
-                            for (r, g, b) in shade {
-                                context.set_fill_style_str(&format!("rgb({r} {g} {b})"));
-                                context.fill_rect(x as f64, y as f64, 1., 1.);
-
-                                x += 1;
-                            }
-
-                            x = 0;
-                            y += 1;
-                        }
-                    })
+                .dwclass!("p-4 flex flex-col gap-4 @>sm:w-full @<sm:w-sm")
+                .child(html!("div", {
+                    .dwclass!("flex flex-row flex-wrap gap-2")
+                    .children([
+                        slider!({
+                            .label("Swatch size".to_string())
+                            .value(swatch_size.clone())
+                            .min(8.)
+                            .max(128.)
+                            .step(4.)
+                        }),
+                        slider!({
+                            .label("Gap".to_string())
+                            .value(gap.clone())
+                            .min(0.)
+                            .max(32.)
+                            .step(1.)
+                        }),
+                        select!({
+                            .label("Labels".to_string())
+                            .value(labels.clone())
+                            .options(SwatchLabels::ALL.map(|v| (v.to_string(), v.to_string())).to_vec())
+                        }),
+                        select!({
+                            .label("Orientation".to_string())
+                            .value(orientation.clone())
+                            .options(SheetOrientation::ALL.map(|v| (v.to_string(), v.to_string())).to_vec())
+                        }),
+                    ])
+                }))
+                .child(html!("div", {
+                    .dwclass!("flex flex-row gap-2")
+                    .children([
+                        button!({
+                            .content(Some(html!("div", {
+                                .dwclass!("p-l-2 p-r-2")
+                                .text("Download PNG")
+                            })))
+                            .on_click(clone!(swatches, swatch_size, gap, labels, orientation => move |_| {
+                                let options = SwatchSheetOptions {
+                                    swatch_size: swatch_size.get() as u32,
+                                    gap: gap.get() as u32,
+                                    labels: labels.get(),
+                                    orientation: orientation.get(),
+                                    ..defaults
+                                };
+
+                                download_png(
+                                    "palette.png",
+                                    SwatchSheet::render(&swatches, &options).and_then(|sheet| sheet.to_png()),
+                                );
+                            }))
+                        }),
+                        button!({
+                            .content(Some(html!("div", {
+                                .dwclass!("p-l-2 p-r-2")
+                                .text("Download indexed PNG")
+                            })))
+                            .on_click(clone!(swatches, swatch_size => move |_| {
+                                download_png(
+                                    "palette-indexed.png",
+                                    encode_indexed_png(&swatches, swatch_size.get() as u32),
+                                );
+                            }))
+                        }),
+                    ])
+                }))
+                .child(html!("div", {
+                    .dwclass!("overflow-auto max-h-96")
+                    .child_signal(options_signal.map(move |options| {
+                        Some(match SwatchSheet::render(&swatches, &options) {
+                            Ok(sheet) => swatch_sheet_canvas(sheet),
+                            Err(error) => html!("div", {
+                                .text(&error.to_string())
+                            }),
+                        })
+                    }))
                 }))
             })
         })
     })
 }
+
+/// Draws the rendered sheet pixel for pixel
+fn swatch_sheet_canvas(sheet: SwatchSheet) -> Dom {
+    html!("canvas" => HtmlCanvasElement, {
+        .attr("width", &sheet.width.to_string())
+        .attr("height", &sheet.height.to_string())
+        .after_inserted(move |node| {
+            if sheet.width == 0 {
+                return;
+            }
+
+            let context = node.get_context("2d").unwrap().unwrap().dyn_into::<CanvasRenderingContext2d>().unwrap();
+            let image = ImageData::new_with_u8_clamped_array_and_sh(Clamped(&sheet.pixels), sheet.width, sheet.height).unwrap_throw();
+
+            context.put_image_data(&image, 0., 0.).unwrap_throw();
+        })
+    })
+}
+
+fn download_png(filename: &str, png: Result<Vec<u8>, SwatchSheetError>) {
+    match png {
+        Ok(png) => download_file(filename, png),
+        Err(error) => {
+            window()
+                .unwrap()
+                .alert_with_message(&error.to_string())
+                .unwrap_throw();
+        }
+    }
+}
@@ -4,3 +4,4 @@ pub mod main_view;
 pub mod palette_controls;
 pub mod svg_icons;
 pub mod tools;
+pub mod vision_simulation;
@@ -1,15 +1,28 @@
 use crate::mixins::click_outside_collapse::click_outside_collapse_mixin;
 use crate::mixins::panel::panel_mixin;
-use crate::model::palette::Palette;
+use crate::model::palette_file::PaletteFile;
 use crate::views::main_view::PalettePalViewModel;
-use crate::views::tools::Tool;
+use crate::views::tools::{Tool, ToolsViewState};
+use crate::views::vision_simulation::{VisionSimulation, SIMULATION_SEVERITY, VISION_SIMULATION};
+use color::conversion::Srgb8;
+use color::css::CssColorSyntax;
+use color::cvd::ColorVisionDeficiency;
+use color::gamut::Gamut;
 use dominator::{events, Dom};
 use dwind::prelude::*;
 use dwui::prelude::*;
-use futures_signals::signal::SignalExt;
 use futures_signals::signal::{not, Mutable};
-use gloo_file::futures::read_as_text;
+use futures_signals::signal::{LocalBoxSignal, SignalExt};
+use gloo_file::futures::{read_as_bytes, read_as_text};
 use gloo_file::Blob;
+use palette::aco::AcoFile;
+use palette::ase::AseFile;
+use palette::base16::{Base16Options, Base16Scheme, Base16System};
+use palette::gimp_palette::GimpPalette;
+use palette::lospec::parse_color_list;
+use palette::palette::{shade_name, Palette, TAILWIND_NUMBERS};
+use palette::swatch_book::{PageSize, SwatchBookOptions};
+use palette::terminal_scheme::TerminalTheme;
 use std::iter::once;
 use wasm_bindgen::{JsCast, JsValue, UnwrapThrowExt};
 use wasm_bindgen_futures::spawn_local;
@@ -25,7 +38,8 @@ pub fn palette_controls(vm: &PalettePalViewModel) -> Dom {
                 .children([
                     tools_menu(vm.clone()),
                     export_menu(vm.clone()),
-                    save_menu(vm.palette.clone())
+                    simulate_menu(),
+                    save_menu(vm.palette.clone(), vm.tools_view_state.clone())
                 ])
             })
         ])
@@ -40,6 +54,9 @@ fn tools_menu(vm: PalettePalViewModel) -> Dom {
                 tool_menu_entry(&vm, Tool::PaletteOverview),
                 tool_menu_entry(&vm, Tool::CurveEditor),
                 tool_menu_entry(&vm, Tool::WcagContrast),
+                tool_menu_entry(&vm, Tool::ColorblindCheck),
+                tool_menu_entry(&vm, Tool::TerminalScheme),
+                tool_menu_entry(&vm, Tool::EditorTheme),
                 tool_menu_entry(&vm, Tool::DwuiExample),
                 tool_menu_entry(&vm, Tool::PixelArt),
                 tool_menu_entry(&vm, Tool::ColorImport),
@@ -51,10 +68,10 @@ fn tools_menu(vm: PalettePalViewModel) -> Dom {
 fn tool_menu_entry(vm: &PalettePalViewModel, tool: Tool) -> Dom {
     html!("div", {
         .dwclass!("font-bold text-base text-woodsmoke-300 hover:text-picton-blue-500 cursor-pointer w-full h-full text-center")
-        .dwclass_signal!("text-picton-blue-400", vm.palette.get_cloned().tools_view_state.tool_state_signal(tool))
+        .dwclass_signal!("text-picton-blue-400", vm.tools_view_state.tool_state_signal(tool))
         .text(&format!("{tool}"))
         .event(clone!(vm => move |_: events::Click| {
-            vm.palette.get_cloned().tools_view_state.toggle(tool)
+            vm.tools_view_state.toggle(tool)
         }))
     })
 }
@@ -92,23 +109,66 @@ fn export_menu(vm: PalettePalViewModel) -> Dom {
         ..
     } = vm;
 
+    let page_size = Mutable::new(PageSize::default());
+    let contrast_badges = Mutable::new(false);
+    let base16_variant = Mutable::new(TerminalTheme::default());
+    // The name of the neutral color of base16 schemes, empty for the least colorful color
+    let base16_neutral = Mutable::new(String::new());
+    let base16_accent_shade = Mutable::new(Base16Options::default().accent_shade);
+
     application_menu("Export", move || {
+        let base16_export = |system: Base16System| {
+            clone!(palette, base16_variant, base16_neutral, base16_accent_shade, export_file_content => move |_| {
+                let neutral = base16_neutral.get_cloned();
+                let options = Base16Options {
+                    system,
+                    variant: base16_variant.get(),
+                    neutral: (!neutral.is_empty()).then_some(neutral),
+                    accent_shade: base16_accent_shade.get(),
+                };
+                let scheme = palette.lock_ref().to_base16_scheme(&options).to_string();
+                download_file(&format!("palettepal.{system}.yaml"), scheme.clone());
+
+                export_file_content.set(Some(scheme));
+            })
+        };
+
+        let color_names = palette
+            .lock_ref()
+            .colors
+            .lock_ref()
+            .iter()
+            .map(|color| color.name.get_cloned())
+            .collect::<Vec<_>>();
+
         html!("div", {
             .dwclass!("flex flex-col gap-2 justify-start")
             .children([
+                select!({
+                    .label("Target gamut".to_string())
+                    .value(palette.lock_ref().gamut.clone())
+                    .options(Gamut::ALL.into_iter().map(|gamut| {
+                        (gamut.to_string(), gamut.to_string())
+                    }).collect::<Vec<_>>())
+                }),
+                select!({
+                    .label("Value syntax".to_string())
+                    .value(palette.lock_ref().css_syntax.clone())
+                    .options(CssColorSyntax::ALL.into_iter().map(|syntax| {
+                        (syntax.to_string(), syntax.to_string())
+                    }).collect::<Vec<_>>())
+                }),
+                text_input!({
+                    .label("Alpha background".to_string())
+                    .value(HexColorWrapper(palette.lock_ref().alpha_background.clone()))
+                }),
                 button!({
                     .content(Some(html!("div", {
                         .dwclass!("p-l-2 p-r-2")
                         .text("Export to DWIND")
                     })))
                     .on_click(clone!(palette, export_file_content => move |_| {
-                        let mut color_file = dwind_build::colors::ColorFile {colors: vec![]};
-                        let palette = palette.lock_mut();
-
-                        for color in palette.colors.lock_mut().iter() {
-                            color_file.colors.push(color.clone().into_dwind_color(&palette.sampling_curves).unwrap_throw())
-                        }
-
+                        let color_file = palette.lock_ref().to_dwind_color_file();
                         let color_file_string = serde_json::to_string_pretty(&color_file).unwrap();
 
                         export_file_content.set(Some(color_file_string));
@@ -117,28 +177,47 @@ fn export_menu(vm: PalettePalViewModel) -> Dom {
                 button!({
                     .content(Some(html!("div", {
                         .dwclass!("p-l-2 p-r-2")
-                        .text("Export to TAILWIND")
+                        .text("Export to Design Tokens")
                     })))
-                    .on_click(|_| {
-                        window().unwrap().alert_with_message("TODO").unwrap()
-                    })
+                    .on_click(clone!(palette, export_file_content => move |_| {
+                        let tokens = palette.lock_ref().to_design_tokens();
+                        let tokens_string = serde_json::to_string_pretty(&tokens).unwrap();
+                        download_file("palette.tokens.json", tokens_string.clone());
+
+                        export_file_content.set(Some(tokens_string));
+                    }))
                 }),
                 button!({
                     .content(Some(html!("div", {
                         .dwclass!("p-l-2 p-r-2")
-                        .text("Export to PNG")
+                        .text("Export to TAILWIND v3")
                     })))
-                    .on_click(clone!(palette, export_image_content => move |_| {
-                        let palette = palette.lock_mut();
-                        let samples = palette.sampling_curves.clone();
-                        let mut colors = vec![];
+                    .on_click(clone!(palette, export_file_content => move |_| {
+                        let config = palette.lock_ref().to_tailwind_config();
+                        download_file("tailwind.config.js", config.clone());
 
-                        for color in palette.colors.lock_mut().iter() {
-                            let curve = color.samples(&samples);
-                            colors.push(color.colors_u8(&curve));
-                        }
+                        export_file_content.set(Some(config));
+                    }))
+                }),
+                button!({
+                    .content(Some(html!("div", {
+                        .dwclass!("p-l-2 p-r-2")
+                        .text("Export to TAILWIND v4")
+                    })))
+                    .on_click(clone!(palette, export_file_content => move |_| {
+                        let theme = palette.lock_ref().to_tailwind_theme();
+                        download_file("theme.css", theme.clone());
 
-                        export_image_content.set(Some(colors));
+                        export_file_content.set(Some(theme));
+                    }))
+                }),
+                button!({
+                    .content(Some(html!("div", {
+                        .dwclass!("p-l-2 p-r-2")
+                        .text("Export to PNG")
+                    })))
+                    .on_click(clone!(palette, export_image_content => move |_| {
+                        export_image_content.set(Some(palette.lock_ref().to_sheet_swatches()));
                     }))
                 }),
                 button!({
@@ -152,13 +231,208 @@ fn export_menu(vm: PalettePalViewModel) -> Dom {
 
                         export_file_content.set(Some(jasc_palette));
                     }))
+                }),
+                button!({
+                    .content(Some(html!("div", {
+                        .dwclass!("p-l-2 p-r-2")
+                        .text("Export to GPL(GIMP)")
+                    })))
+                    .on_click(clone!(palette, export_file_content => move |_| {
+                        let gimp_palette = palette.lock_ref().to_gimp_palette();
+                        download_file("palette.gpl", gimp_palette.clone());
+
+                        export_file_content.set(Some(gimp_palette));
+                    }))
+                }),
+                button!({
+                    .content(Some(html!("div", {
+                        .dwclass!("p-l-2 p-r-2")
+                        .text("Export to HEX(Lospec)")
+                    })))
+                    .on_click(clone!(palette, export_file_content => move |_| {
+                        let hex_list = palette.lock_ref().to_hex_list();
+                        download_file("palette.hex", hex_list.clone());
+
+                        export_file_content.set(Some(hex_list));
+                    }))
+                }),
+                button!({
+                    .content(Some(html!("div", {
+                        .dwclass!("p-l-2 p-r-2")
+                        .text("Export to TXT(paint.net)")
+                    })))
+                    .on_click(clone!(palette, export_file_content => move |_| {
+                        let paint_net_palette = palette.lock_ref().to_paint_net_palette();
+                        download_file("palette.txt", paint_net_palette.clone());
+
+                        export_file_content.set(Some(paint_net_palette));
+                    }))
+                }),
+                button!({
+                    .content(Some(html!("div", {
+                        .dwclass!("p-l-2 p-r-2")
+                        .text("Export to ASE(Adobe)")
+                    })))
+                    .on_click(clone!(palette => move |_| {
+                        download_file("palette.ase", palette.lock_ref().to_ase());
+                    }))
+                }),
+                button!({
+                    .content(Some(html!("div", {
+                        .dwclass!("p-l-2 p-r-2")
+                        .text("Export to ACO(Photoshop)")
+                    })))
+                    .on_click(clone!(palette => move |_| {
+                        download_file("palette.aco", palette.lock_ref().to_aco());
+                    }))
+                }),
+                select!({
+                    .label("Swatch book paper".to_string())
+                    .value(page_size.clone())
+                    .options(PageSize::ALL.into_iter().map(|size| {
+                        (size.to_string(), size.to_string())
+                    }).collect::<Vec<_>>())
+                }),
+                select!({
+                    .label("Contrast badges".to_string())
+                    .value(contrast_badges.clone())
+                    .options(vec![
+                        ("false".to_string(), "Hide".to_string()),
+                        ("true".to_string(), "Show".to_string()),
+                    ])
+                }),
+                button!({
+                    .content(Some(html!("div", {
+                        .dwclass!("p-l-2 p-r-2")
+                        .text("Export to SVG(swatch book)")
+                    })))
+                    .on_click(clone!(palette, page_size, contrast_badges, export_file_content => move |_| {
+                        let options = SwatchBookOptions {
+                            page_size: page_size.get(),
+                            contrast_badges: contrast_badges.get(),
+                        };
+                        let svg = palette.lock_ref().to_swatch_book_svg(&options);
+                        download_file("palette.svg", svg.clone());
+
+                        export_file_content.set(Some(svg));
+                    }))
+                }),
+                button!({
+                    .content(Some(html!("div", {
+                        .dwclass!("p-l-2 p-r-2")
+                        .text("Export to PDF(swatch book)")
+                    })))
+                    .on_click(clone!(palette, page_size, contrast_badges => move |_| {
+                        let options = SwatchBookOptions {
+                            page_size: page_size.get(),
+                            contrast_badges: contrast_badges.get(),
+                        };
+
+                        download_file("palette.pdf", palette.lock_ref().to_swatch_book_pdf(&options));
+                    }))
+                }),
+                select!({
+                    .label("Base16 variant".to_string())
+                    .value(base16_variant.clone())
+                    .options(TerminalTheme::ALL.into_iter().map(|variant| {
+                        (variant.to_string(), variant.to_string())
+                    }).collect::<Vec<_>>())
+                }),
+                select!({
+                    .label("Base16 neutral".to_string())
+                    .value(base16_neutral.clone())
+                    .options(once((String::new(), "Least colorful".to_string()))
+                        .chain(color_names.into_iter().map(|name| (name.clone(), name)))
+                        .collect::<Vec<_>>())
+                }),
+                select!({
+                    .label("Base16 accent shade".to_string())
+                    .value(base16_accent_shade.clone())
+                    .options((0..TAILWIND_NUMBERS.len()).map(|shade| {
+                        (shade.to_string(), shade_name(shade))
+                    }).collect::<Vec<_>>())
+                }),
+                button!({
+                    .content(Some(html!("div", {
+                        .dwclass!("p-l-2 p-r-2")
+                        .text("Export to YAML(base16)")
+                    })))
+                    .on_click(base16_export(Base16System::Base16))
+                }),
+                button!({
+                    .content(Some(html!("div", {
+                        .dwclass!("p-l-2 p-r-2")
+                        .text("Export to YAML(base24)")
+                    })))
+                    .on_click(base16_export(Base16System::Base24))
+                }),
+                button!({
+                    .content(Some(html!("div", {
+                        .dwclass!("p-l-2 p-r-2")
+                        .text("Export to CSS")
+                    })))
+                    .on_click(clone!(palette, export_file_content => move |_| {
+                        let css = palette.lock_ref().to_css_variables();
+                        download_file("palette.css", css.clone());
+
+                        export_file_content.set(Some(css));
+                    }))
+                }),
+                button!({
+                    .content(Some(html!("div", {
+                        .dwclass!("p-l-2 p-r-2")
+                        .text("Export to SCSS")
+                    })))
+                    .on_click(clone!(palette, export_file_content => move |_| {
+                        let scss = palette.lock_ref().to_scss_map();
+                        download_file("_palette.scss", scss.clone());
+
+                        export_file_content.set(Some(scss));
+                    }))
+                }),
+                button!({
+                    .content(Some(html!("div", {
+                        .dwclass!("p-l-2 p-r-2")
+                        .text("Export to LESS")
+                    })))
+                    .on_click(clone!(palette, export_file_content => move |_| {
+                        let less = palette.lock_ref().to_less_variables();
+                        download_file("palette.less", less.clone());
+
+                        export_file_content.set(Some(less));
+                    }))
                 })
             ])
         })
     })
 }
 
-fn save_menu(palette: Mutable<Palette>) -> Dom {
+fn simulate_menu() -> Dom {
+    application_menu("Simulate", move || {
+        html!("div", {
+            .dwclass!("flex flex-col gap-2 justify-start")
+            .children([
+                select!({
+                    .label("Vision".to_string())
+                    .value(VISION_SIMULATION.clone())
+                    .options(once(VisionSimulation::Off)
+                        .chain(ColorVisionDeficiency::ALL.into_iter().map(VisionSimulation::Simulate))
+                        .map(|simulation| (simulation.to_string(), simulation.to_string()))
+                        .collect::<Vec<_>>())
+                }),
+                slider!({
+                    .label("Severity".to_string())
+                    .value(SIMULATION_SEVERITY.clone())
+                    .min(0.)
+                    .max(1.)
+                    .step(0.1)
+                }),
+            ])
+        })
+    })
+}
+
+fn save_menu(palette: Mutable<Palette>, tools_view_state: ToolsViewState) -> Dom {
     let expanded = Mutable::new(false);
 
     html!("div", {
@@ -187,8 +461,12 @@ fn save_menu(palette: Mutable<Palette>) -> Dom {
                             .dwclass!("p-l-2 p-r-2")
                             .text("Save file")
                         })))
-                        .on_click(clone!(palette => move |_| {
-                            let palette_json = serde_json::to_string(&*palette.lock_ref()).unwrap();
+                        .on_click(clone!(palette, tools_view_state => move |_| {
+                            let file = PaletteFile {
+                                palette: palette.get_cloned(),
+                                tools_view_state: tools_view_state.clone(),
+                            };
+                            let palette_json = serde_json::to_string(&file).unwrap();
                             download_file("palette.palettepal", palette_json);
                         }))
                     }),
@@ -197,13 +475,20 @@ fn save_menu(palette: Mutable<Palette>) -> Dom {
                             .attr("id", "uploadpalettefile")
                             .attr("type", "file")
                             .with_node!(file => {
-                                .event(clone!(palette => move |_: events::Change| {
+                                .event(clone!(palette, tools_view_state => move |_: events::Change| {
                                     let file = file.files().unwrap().get(0).unwrap();
 
-                                    spawn_local(clone!(palette => async move {
+                                    spawn_local(clone!(palette, tools_view_state => async move {
                                         let content = read_as_text(&Blob::from(file)).await.unwrap_throw();
-                                        let loaded_palette: Palette = serde_json::from_str(&content).unwrap_throw();
-                                        palette.set(loaded_palette);
+                                        let loaded_file: PaletteFile = serde_json::from_str(&content).unwrap_throw();
+
+                                        if let Err(error) = loaded_file.palette.validate() {
+                                            window().unwrap().alert_with_message(&error.to_string()).unwrap_throw();
+                                            return;
+                                        }
+
+                                        tools_view_state.set_from(&loaded_file.tools_view_state);
+                                        palette.set(loaded_file.palette);
                                     }));
                                 }))
                             })
@@ -220,6 +505,54 @@ fn save_menu(palette: Mutable<Palette>) -> Dom {
                             })
                         }))
                     }),
+                    html!("div", {
+                        .child(html!("input" => HtmlInputElement, {
+                            .attr("id", "uploadswatchfile")
+                            .attr("type", "file")
+                            .attr("accept", ".ase,.aco,.gpl,.hex,.txt,.yaml,.yml")
+                            .with_node!(file => {
+                                .event(clone!(palette => move |_: events::Change| {
+                                    let file = file.files().unwrap().get(0).unwrap();
+
+                                    spawn_local(clone!(palette => async move {
+                                        let content = read_as_bytes(&Blob::from(file)).await.unwrap_throw();
+                                        let palette = palette.lock_ref();
+
+                                        let text = String::from_utf8(content.clone()).unwrap_or_default();
+
+                                        let imported = if let Some(ase) = AseFile::from_bytes(&content) {
+                                            palette.import_ase(&ase)
+                                        } else if let Some(aco) = AcoFile::from_bytes(&content) {
+                                            palette.import_aco(&aco)
+                                        } else if let Ok(gimp_palette) = text.parse::<GimpPalette>() {
+                                            palette.import_gimp_palette(&gimp_palette)
+                                        } else if let Ok(scheme) = text.parse::<Base16Scheme>() {
+                                            palette.import_base16_scheme(&scheme)
+                                        } else if let Some(colors) = parse_color_list(&text) {
+                                            palette.import_color_list(&colors)
+                                        } else {
+                                            0
+                                        };
+
+                                        if imported == 0 {
+                                            window().unwrap().alert_with_message("No colors found in the swatch file").unwrap_throw();
+                                        }
+                                    }));
+                                }))
+                            })
+                            .attr("hidden", "hidden")
+                        }))
+                        .child(button!({
+                            .content(Some(html!("div", {
+                                .dwclass!("p-l-2 p-r-2")
+                                .text("Import swatches")
+                            })))
+                            .on_click(move |_| {
+                                let input = window().unwrap().document().unwrap().query_selector("#uploadswatchfile").unwrap().unwrap().dyn_into::<HtmlInputElement>().unwrap();
+                                input.click();
+                            })
+                        }))
+                    }),
                     button!({
                         .content(Some(html!("div", {
                             .dwclass!("p-l-2 p-r-2")
@@ -243,11 +576,12 @@ fn save_menu(palette: Mutable<Palette>) -> Dom {
     })
 }
 
-fn download_file(filename: &str, content: String) {
-    let string = JsValue::from_str(content.as_str());
+/// Download text or binary content as a file
+pub fn download_file(filename: &str, content: impl AsRef<[u8]>) {
+    let bytes = js_sys::Uint8Array::from(content.as_ref());
 
-    let sequence = js_sys::Array::from_iter(once(string));
-    let blob = web_sys::Blob::new_with_str_sequence(&sequence).unwrap_throw();
+    let sequence = js_sys::Array::from_iter(once(JsValue::from(bytes)));
+    let blob = web_sys::Blob::new_with_u8_array_sequence(&sequence).unwrap_throw();
 
     let file_url = Url::create_object_url_with_blob(&blob).unwrap_throw();
     let dl_link = window()
@@ -283,3 +617,23 @@ fn download_file(filename: &str, content: String) {
         .unwrap_throw();
     Url::revoke_object_url(&file_url).unwrap_throw();
 }
+
+struct HexColorWrapper(Mutable<Srgb8>);
+
+impl InputValueWrapper for HexColorWrapper {
+    fn set(&self, value: String) -> ValidationResult {
+        let Ok(color) = value.parse::<Srgb8>() else {
+            return ValidationResult::Invalid {
+                message: "Invalid hex color".to_string(),
+            };
+        };
+
+        self.0.set(color);
+
+        ValidationResult::Valid
+    }
+
+    fn value_signal_cloned(&self) -> LocalBoxSignal<'static, String> {
+        self.0.signal().map(|color| color.to_hex()).boxed_local()
+    }
+}
@@ -1,36 +1,61 @@
 use crate::mixins::panel::widget_panel_mixin;
 use crate::views::main_view::PalettePalViewModel;
 use crate::views::tools::Tool;
+use color::conversion::Srgb8;
 use dominator::{events, Dom};
 use dwind::prelude::*;
 use dwind_build::colors::Color;
 use dwui::prelude::*;
 use futures_signals::signal::{always, Mutable, SignalExt};
-use web_sys::{HtmlElement, HtmlTextAreaElement};
+use palette::design_tokens::color_groups;
+use palette::gimp_palette::GimpPalette;
+use palette::lospec::parse_color_list;
+use wasm_bindgen::UnwrapThrowExt;
+use web_sys::{window, HtmlTextAreaElement};
 
 pub fn color_import_tool(vm: &PalettePalViewModel) -> Dom {
-    let palette = vm.palette.get_cloned();
-
     html!("div", {
         .dwclass!("p-2")
-        .apply(widget_panel_mixin(always("Import Color".to_string()), Some(palette.tools_view_state.create_close_tool_handler(Tool::ColorImport))))
-        .child(color_import_tool_body(&vm))
+        .apply(widget_panel_mixin(always("Import Color".to_string()), Some(vm.tools_view_state.create_close_tool_handler(Tool::ColorImport))))
+        .child(color_import_tool_body(vm))
     })
 }
 
+/// The colors a pasted file contains
+enum ImportedColors {
+    Dwind(Color),
+    DesignTokens(serde_json::Value),
+    Gimp(GimpPalette),
+    ColorList(Vec<Srgb8>),
+}
+
+fn parse_imported_colors(text: &str) -> Option<ImportedColors> {
+    if let Ok(gimp_palette) = text.parse::<GimpPalette>() {
+        return Some(ImportedColors::Gimp(gimp_palette));
+    }
+
+    if let Ok(color) = serde_json::from_str::<Color>(text) {
+        return Some(ImportedColors::Dwind(color));
+    }
+
+    if let Some(colors) = parse_color_list(text) {
+        return Some(ImportedColors::ColorList(colors));
+    }
+
+    let tokens = serde_json::from_str::<serde_json::Value>(text).ok()?;
+
+    (!color_groups(&tokens).is_empty()).then_some(ImportedColors::DesignTokens(tokens))
+}
+
 fn color_import_tool_body(vm: &PalettePalViewModel) -> Dom {
     let json_text = Mutable::new("".to_string());
-    let color_signal = json_text.signal_ref(|text| {
-        serde_json::from_str::<Color>(&text)
-            .map(Some)
-            .unwrap_or(None)
-    });
+    let color_signal = json_text.signal_ref(|text| parse_imported_colors(text).is_some());
 
     let palette = vm.palette.clone();
 
     html!("div", {
         .dwclass!("flex flex-col")
-        .text("Import DWIND swatch json")
+        .text("Import DWIND swatch, design tokens json, GIMP or Lospec palette")
         .child(html!("textarea" => HtmlTextAreaElement, {
             .attr("cols", "40")
             .attr("rows", "20")
@@ -45,10 +70,25 @@ fn color_import_tool_body(vm: &PalettePalViewModel) -> Dom {
         }))
         .child(button!({
             .content(Some(html!("span", { .text("Import")})))
-            .disabled_signal(color_signal.map(|v| v.is_none()))
+            .disabled_signal(color_signal.map(|is_valid| !is_valid))
             .on_click(move |_| {
-                let color = serde_json::from_str::<Color>(&json_text.get_cloned()).map(Some).unwrap_or(None).unwrap();
-                palette.lock_mut().import_dwind_color(color);
+                match parse_imported_colors(&json_text.get_cloned()) {
+                    Some(ImportedColors::Dwind(color)) => {
+                        if let Err(error) = palette.lock_mut().import_dwind_color(color) {
+                            window().unwrap().alert_with_message(&error.to_string()).unwrap_throw();
+                        }
+                    }
+                    Some(ImportedColors::DesignTokens(tokens)) => {
+                        palette.lock_mut().import_design_tokens(&tokens);
+                    }
+                    Some(ImportedColors::Gimp(gimp_palette)) => {
+                        palette.lock_mut().import_gimp_palette(&gimp_palette);
+                    }
+                    Some(ImportedColors::ColorList(colors)) => {
+                        palette.lock_mut().import_color_list(&colors);
+                    }
+                    None => {}
+                }
             })
         }))
     })
@@ -0,0 +1,200 @@
+use crate::mixins::panel::widget_panel_mixin;
+use crate::views::main_view::PalettePalViewModel;
+use crate::views::tools::Tool;
+use color::contrast::RGBA;
+use color::cvd::ColorVisionDeficiency;
+use color::difference::DifferenceMetric;
+use color::distinguishability::{
+    indistinguishable_pairs, IndistinguishablePair, CATEGORICAL_DELTA_E_2000,
+};
+use dominator::Dom;
+use dwind::prelude::*;
+use dwui::prelude::*;
+use dwui::select;
+use futures_signals::map_ref;
+use futures_signals::signal::{always, Broadcaster, Mutable, Signal, SignalExt};
+use futures_signals::signal_vec::SignalVecExt;
+use palette::palette::{Palette, TAILWIND_NUMBERS};
+
+/// A failing pair, along with the names of its palette colors
+#[derive(Clone)]
+struct NamedPair {
+    pair: IndistinguishablePair,
+    name_a: String,
+    name_b: String,
+}
+
+pub fn colorblind_check_tool(vm: &PalettePalViewModel, palette: &Palette) -> Dom {
+    let shade_idx = Mutable::new(5usize);
+    let severity = Mutable::new(1.);
+    let threshold = Mutable::new(CATEGORICAL_DELTA_E_2000);
+
+    let curves = palette.sampling_curves.clone();
+
+    let named_shades_signal = palette
+        .colors
+        .signal_vec_cloned()
+        .map_signal(move |color| {
+            map_ref! {
+                let name = color.name.signal_cloned(),
+                let shades = color.colors_u8_signal(&curves) => (name.clone(), shades.clone())
+            }
+        })
+        .to_signal_cloned();
+
+    let pairs_signal = map_ref! {
+        let named_shades = named_shades_signal,
+        let shade_idx = shade_idx.signal(),
+        let severity = severity.signal(),
+        let threshold = threshold.signal() => {
+            let named_colors: Vec<(String, RGBA)> = named_shades
+                .iter()
+                .filter_map(|(name, shades)| shades.get(*shade_idx).map(|color| (name.clone(), *color)))
+                .collect();
+
+            indistinguishable_pairs(
+                named_colors.iter().map(|(_, color)| *color),
+                *severity,
+                DifferenceMetric::Ciede2000,
+                *threshold,
+            )
+            .into_iter()
+            .map(|pair| NamedPair {
+                name_a: named_colors[pair.color_a_idx].0.clone(),
+                name_b: named_colors[pair.color_b_idx].0.clone(),
+                pair,
+            })
+            .collect::<Vec<_>>()
+        }
+    }
+    .broadcast();
+
+    let rows: Vec<Dom> = ColorVisionDeficiency::ALL
+        .into_iter()
+        .map(|deficiency| deficiency_row(deficiency, &pairs_signal))
+        .collect();
+
+    let body = html!("div", {
+        .dwclass!("flex-1 flex flex-col gap-4 p-4")
+        .child(html!("div", {
+            .dwclass!("flex flex-row flex-1 gap-2")
+            .children([
+                select!({
+                    .label("Shade".to_string())
+                    .value(shade_idx.clone())
+                    .options(TAILWIND_NUMBERS.iter().enumerate().map(|(idx, shade)| {
+                        (idx.to_string(), shade.to_string())
+                    }).collect::<Vec<_>>())
+                }),
+                slider!({
+                    .label("Severity".to_string())
+                    .value(severity.clone())
+                    .min(0.)
+                    .max(1.)
+                    .step(0.1)
+                }),
+                slider!({
+                    .label("Minimum ΔE2000".to_string())
+                    .value(threshold.clone())
+                    .min(1.)
+                    .max(30.)
+                    .step(0.5)
+                }),
+            ])
+        }))
+        .child(html!("div", {
+            .dwclass!("overflow-y-scroll flex h-80")
+            .child(html!("table", {
+                .dwclass!("divide-y border-woodsmoke-500 border-collapse")
+                .child(html!("tr", {
+                    .dwclass!("border-woodsmoke-500")
+                    .children([
+                        html!("th", {
+                            .text("Deficiency")
+                        }),
+                        html!("th", {
+                            .text("Indistinguishable pairs, normal | simulated")
+                        })
+                    ])
+                }))
+                .children(rows)
+            }))
+        }))
+    });
+
+    let close_cb = vm
+        .tools_view_state
+        .create_close_tool_handler(Tool::ColorblindCheck);
+
+    html!("div", {
+        .dwclass!("flex-1 p-2 relative")
+        .apply(widget_panel_mixin(always("Colorblind Distinguishability".to_string()), Some(close_cb)))
+        .child(body)
+    })
+}
+
+fn deficiency_row(
+    deficiency: ColorVisionDeficiency,
+    pairs_signal: &Broadcaster<impl Signal<Item = Vec<NamedPair>> + 'static>,
+) -> Dom {
+    let pairs = pairs_signal
+        .signal_cloned()
+        .to_signal_vec()
+        .filter(move |pair| pair.pair.deficiency == deficiency)
+        .map(pair_display);
+
+    html!("tr", {
+        .dwclass!("border-woodsmoke-500")
+        .children([
+            html!("td", {
+                .text(&deficiency.to_string())
+            }),
+            html!("td", {
+                .dwclass!("flex flex-wrap flex-row gap-2")
+                .children_signal_vec(pairs)
+            })
+        ])
+    })
+}
+
+/// Shows the pair side by side with normal vision, then as simulated
+fn pair_display(pair: NamedPair) -> Dom {
+    let NamedPair {
+        pair,
+        name_a,
+        name_b,
+    } = pair;
+
+    html!("div", {
+        .dwclass!("flex flex-col gap-1 bg-woodsmoke-600 rounded-sm p-1")
+        .attr("title", &format!(
+            "ΔE2000 {:.1} with normal vision, {:.1} simulated",
+            pair.normal_difference, pair.simulated_difference
+        ))
+        .child(html!("div", {
+            .dwclass!("text-xs text-center")
+            .text(&format!("{name_a} / {name_b}"))
+        }))
+        .child(html!("div", {
+            .dwclass!("flex flex-row gap-2")
+            .children([
+                swatch_pair(pair.color_a, pair.color_b),
+                swatch_pair(pair.simulated_a, pair.simulated_b),
+            ])
+        }))
+    })
+}
+
+fn swatch_pair(color_a: RGBA, color_b: RGBA) -> Dom {
+    let swatch = |color: RGBA| {
+        html!("div", {
+            .dwclass!("w-10 h-10")
+            .style("background", &color.to_css())
+        })
+    };
+
+    html!("div", {
+        .dwclass!("flex flex-row")
+        .children([swatch(color_a), swatch(color_b)])
+    })
+}
@@ -1,7 +1,5 @@
 use crate::mixins::observe_size::observe_size_mixin;
 use crate::mixins::panel::{panel_mixin, widget_panel_mixin};
-use crate::model::palette::Palette;
-use crate::model::sampling_curve::{Modifiers, SamplingCurve};
 use crate::views::main_view::PalettePalViewModel;
 use crate::views::tools::Tool;
 use dominator::events::MouseButton;
@@ -12,6 +10,9 @@ use futures_signals::signal::{always, Mutable, ReadOnlyMutable, Signal, SignalEx
 use futures_signals::signal_map::SignalMapExt;
 use futures_signals::signal_vec::SignalVecExt;
 use glam::Vec2;
+use palette::palette::Palette;
+use palette::palette_color::ColorSpace;
+use palette::sampling_curve::{Modifiers, SamplingCurve};
 use uuid::Uuid;
 
 pub fn sampling_curve_editor(vm: &PalettePalViewModel) -> Dom {
@@ -60,7 +61,7 @@ pub fn sampling_curve_editor(vm: &PalettePalViewModel) -> Dom {
 
     html!("div", {
         .dwclass!("p-2")
-        .apply(widget_panel_mixin(always("Curve Editor".to_string()), Some(palette.tools_view_state.create_close_tool_handler(Tool::CurveEditor))))
+        .apply(widget_panel_mixin(always("Curve Editor".to_string()), Some(vm.tools_view_state.create_close_tool_handler(Tool::CurveEditor))))
         .child(body)
     })
 }
@@ -76,21 +77,88 @@ fn curve_editor(
         }))
         .flatten();
 
-    curve_signal.map(move |curve| {
-        let Some(curve) = curve else {
-            return None;
-        };
-
-        Some(curve_editor_inner(curve, true))
-    })
+    curve_signal.map(move |curve| curve.map(|curve| curve_editor_inner(curve, true, None)))
 }
 
-pub fn curve_editor_inner(curve: SamplingCurve, meta_info: bool) -> Dom {
+/// Editor for the points of a sampling curve. If a color space is given, the axis of the
+/// sampling plane are labeled accordingly
+pub fn curve_editor_inner(
+    curve: SamplingCurve,
+    meta_info: bool,
+    color_space: Option<ReadOnlyMutable<ColorSpace>>,
+) -> Dom {
     let rect_sample_space_curve = curve.curve.signal_cloned();
 
     let rect_size = Mutable::new((0., 0.));
     let dragging_idx: Mutable<Option<usize>> = Mutable::new(None);
 
+    let plane = html!("div", {
+        .dwclass!("bg-picton-blue-500 aspect-square max-w-80")
+        .apply(observe_size_mixin(rect_size.clone()))
+        .child(svg!("svg", {
+            .attr("viewBox", "0 0 512, 512")
+            .attr("width", "100%")
+            .attr("height", "100%")
+            .event(clone!(curve, rect_size => move |event: events::DoubleClick| {
+                let x = event.offset_x();
+                let y = event.offset_y();
+
+                let x = x as f32 / rect_size.get().0 as f32;
+                let y = 1. - y as f32 / rect_size.get().1 as f32;
+
+                curve.add_new_point(Vec2::new(x, y));
+            }))
+            .event(clone!(curve, dragging_idx, rect_size => move |event: events::MouseMove| {
+                let x = event.offset_x();
+                let y = event.offset_y();
+                let x = x as f32 / rect_size.get().0 as f32;
+                let y = 1. - y as f32 / rect_size.get().1 as f32;
+
+                if event.shift_key() {
+                    curve.try_y_align_at_x(Vec2::new(x, y));
+
+                    return;
+                }
+
+                let idx = dragging_idx.get();
+
+                if idx.is_none() {
+                    return;
+                }
+
+                let idx = idx.unwrap();
+
+                let idx = curve.replace_point(idx, Vec2::new(x, y), Modifiers {alt: event.alt_key(), ctrl: event.ctrl_key(), shift: event.shift_key(),});
+                dragging_idx.set(Some(idx));
+            }))
+            .children_signal_vec(rect_sample_space_curve.map(clone!(curve => move |curve_data| {
+                curve_data.into_iter().enumerate().map(clone!(curve, dragging_idx => move |(idx, point)| {
+                    svg!("circle", {
+                        .dwclass!("cursor-pointer")
+                        .attr("r", "10px")
+                        .attr("cx", &(point.x * 512.).to_string())
+                        .attr("cy", &(512. - point.y * 512.).to_string())
+                        .event(clone!(curve, dragging_idx => move |event: events::MouseDown| {
+                            if event.button() == MouseButton::Left {
+                                dragging_idx.set(Some(idx));
+                            } else if event.button() == MouseButton::Right {
+                                event.stop_propagation();
+                                curve.curve.lock_mut().remove(idx);
+                            }
+                        }))
+                        .event_with_options(&EventOptions {bubbles: true, preventable: true}, |event: events::ContextMenu| {
+                            event.prevent_default();
+                            event.stop_propagation();
+                        })
+                        .global_event(clone!(dragging_idx => move |_: events::MouseUp| {
+                            dragging_idx.set(None);
+                        }))
+                    })
+                })).collect()
+            })).to_signal_vec())
+        }))
+    });
+
     html!("div", {
         .dwclass!("flex gap-4 w-full")
         .apply_if(meta_info, |b| b.child(html!("div", {
@@ -110,71 +178,29 @@ pub fn curve_editor_inner(curve: SamplingCurve, meta_info: bool) -> Dom {
                 }))
             }))
         })))
-        .child(html!("div", {
-            .dwclass!("bg-picton-blue-500 aspect-square max-w-80")
-            .apply(observe_size_mixin(rect_size.clone()))
-            .child(svg!("svg", {
-                .attr("viewBox", "0 0 512, 512")
-                .attr("width", "100%")
-                .attr("height", "100%")
-                .event(clone!(curve, rect_size => move |event: events::DoubleClick| {
-                    let x = event.offset_x();
-                    let y = event.offset_y();
-
-                    let x = x as f32 / rect_size.get().0 as f32;
-                    let y = 1. - y as f32 / rect_size.get().1 as f32;
-
-                    curve.add_new_point(Vec2::new(x, y));
-                }))
-                .event(clone!(curve, dragging_idx, rect_size => move |event: events::MouseMove| {
-                    let x = event.offset_x();
-                    let y = event.offset_y();
-                    let x = x as f32 / rect_size.get().0 as f32;
-                    let y = 1. - y as f32 / rect_size.get().1 as f32;
-
-                    if event.shift_key() {
-                        curve.try_y_align_at_x(Vec2::new(x, y));
-
-                        return;
-                    }
-
-                    let idx = dragging_idx.get();
-
-                    if idx.is_none() {
-                        return;
-                    }
-
-                    let idx = idx.unwrap();
+        .child(match color_space {
+            Some(color_space) => labeled_plane(plane, color_space),
+            None => plane,
+        })
+    })
+}
 
-                    let idx = curve.replace_point(idx, Vec2::new(x, y), Modifiers {alt: event.alt_key(), ctrl: event.ctrl_key(), shift: event.shift_key(),});
-                    dragging_idx.set(Some(idx));
-                }))
-                .children_signal_vec(rect_sample_space_curve.map(clone!(curve => move |curve_data| {
-                    curve_data.into_iter().enumerate().map(clone!(curve, dragging_idx => move |(idx, point)| {
-                        svg!("circle", {
-                            .dwclass!("cursor-pointer")
-                            .attr("r", "10px")
-                            .attr("cx", &(point.x * 512.).to_string())
-                            .attr("cy", &(512. - point.y * 512.).to_string())
-                            .event(clone!(curve, dragging_idx => move |event: events::MouseDown| {
-                                if event.button() == MouseButton::Left {
-                                    dragging_idx.set(Some(idx));
-                                } else if event.button() == MouseButton::Right {
-                                    event.stop_propagation();
-                                    curve.curve.lock_mut().remove(idx);
-                                }
-                            }))
-                            .event_with_options(&EventOptions {bubbles: true, preventable: true}, |event: events::ContextMenu| {
-                                event.prevent_default();
-                                event.stop_propagation();
-                            })
-                            .global_event(clone!(dragging_idx => move |_: events::MouseUp| {
-                                dragging_idx.set(None);
-                            }))
-                        })
-                    })).collect()
-                })).to_signal_vec())
+fn labeled_plane(plane: Dom, color_space: ReadOnlyMutable<ColorSpace>) -> Dom {
+    html!("div", {
+        .dwclass!("flex flex-col gap-1 text-xs text-woodsmoke-300")
+        .child(html!("div", {
+            .dwclass!("flex flex-row gap-1")
+            .child(html!("div", {
+                .dwclass!("text-center")
+                .style("writing-mode", "vertical-rl")
+                .style("transform", "rotate(180deg)")
+                .text_signal(color_space.signal_ref(|space| space.axis_labels().1))
             }))
+            .child(plane)
+        }))
+        .child(html!("div", {
+            .dwclass!("text-center")
+            .text_signal(color_space.signal_ref(|space| space.axis_labels().0))
         }))
     })
 }
@@ -0,0 +1,233 @@
+use crate::mixins::panel::widget_panel_mixin;
+use crate::views::main_view::PalettePalViewModel;
+use crate::views::palette_controls::download_file;
+use crate::views::tools::role_shade::{shade_options, swatches_signal, Overrides, RoleShade};
+use crate::views::tools::Tool;
+use dominator::Dom;
+use dwind::prelude::*;
+use dwui::prelude::*;
+use dwui::select;
+use futures_signals::map_ref;
+use futures_signals::signal::{always, Mutable, SignalExt};
+use palette::editor_theme::{default_mapping, EditorFormat, EditorScope, EditorTheme};
+use palette::palette::Palette;
+use palette::terminal_scheme::TerminalTheme;
+use std::collections::BTreeMap;
+
+pub fn editor_theme_tool(vm: &PalettePalViewModel, palette: &Palette) -> Dom {
+    let theme = Mutable::new(TerminalTheme::Dark);
+    let format = Mutable::new(EditorFormat::VsCode);
+    let overrides: Overrides<EditorScope> = Mutable::new(BTreeMap::new());
+
+    let swatches_signal = swatches_signal(palette).broadcast();
+
+    // The default mapping of the theme, with the scopes the user picked a shade for replaced
+    let mapping_signal = map_ref! {
+        let swatches = swatches_signal.signal_cloned(),
+        let theme = theme.signal(),
+        let overrides = overrides.signal_cloned() => {
+            let mapping = default_mapping(swatches, *theme)
+                .into_iter()
+                .map(|(scope, shade)| (scope, overrides.get(&scope).copied().unwrap_or(shade)))
+                .collect::<Vec<_>>();
+
+            let editor_theme = EditorTheme::from_mapping("palettepal", *theme, swatches, &mapping);
+
+            (mapping, editor_theme)
+        }
+    }
+    .broadcast();
+
+    let export_signal = map_ref! {
+        let (_, editor_theme) = mapping_signal.signal_cloned(),
+        let format = format.signal() => {
+            editor_theme.export(*format)
+        }
+    };
+
+    // The export of the current theme, for the download button to read
+    let export_text = Mutable::new(String::new());
+
+    let shade_options = swatches_signal
+        .signal_ref(|swatches| shade_options(swatches))
+        .broadcast();
+
+    let scope_selects = EditorScope::ALL.map(|scope| {
+        select!({
+            .label(scope.to_string())
+            .value(RoleShade {
+                role: scope,
+                overrides: overrides.clone(),
+                mapping: mapping_signal.clone(),
+            })
+            .options_signal_vec(shade_options.signal_cloned().to_signal_vec())
+        })
+    });
+
+    let body = html!("div", {
+        .dwclass!("flex-1 flex flex-col gap-4 p-4")
+        .child(html!("div", {
+            .dwclass!("flex flex-row flex-wrap gap-2")
+            .children([
+                select!({
+                    .label("Theme".to_string())
+                    .value(theme.clone())
+                    .options(TerminalTheme::ALL.map(|v| (v.to_string(), v.to_string())).to_vec())
+                }),
+                select!({
+                    .label("Format".to_string())
+                    .value(format.clone())
+                    .options(EditorFormat::ALL.map(|v| (v.to_string(), v.to_string())).to_vec())
+                }),
+                button!({
+                    .content(Some(html!("div", {
+                        .dwclass!("p-l-2 p-r-2")
+                        .text("Reset to defaults")
+                    })))
+                    .on_click(clone!(overrides => move |_| {
+                        overrides.set(BTreeMap::new());
+                    }))
+                }),
+                button!({
+                    .content(Some(html!("div", {
+                        .dwclass!("p-l-2 p-r-2")
+                        .text("Download")
+                    })))
+                    .on_click(clone!(export_text, format => move |_| {
+                        download_file(format.get().file_name(), export_text.get_cloned());
+                    }))
+                }),
+            ])
+        }))
+        .child_signal(mapping_signal.signal_cloned().map(|(_, editor_theme)| Some(code_preview(&editor_theme))))
+        .child(html!("div", {
+            .dwclass!("grid grid-cols-4 gap-2")
+            .children(scope_selects)
+        }))
+        .child(html!("div", {
+            .dwclass!("overflow-y-scroll h-64")
+            .child(html!("pre", {
+                .future(export_signal.for_each(clone!(export_text => move |export| {
+                    export_text.set(export);
+                    async {}
+                })))
+                .text_signal(export_text.signal_cloned())
+            }))
+        }))
+    });
+
+    let close_cb = vm
+        .tools_view_state
+        .create_close_tool_handler(Tool::EditorTheme);
+
+    html!("div", {
+        .dwclass!("flex-1 p-2 relative")
+        .apply(widget_panel_mixin(always("Editor Theme".to_string()), Some(close_cb)))
+        .child(body)
+    })
+}
+
+/// The line of [snippet] shown as selected
+const SELECTED_LINE: usize = 7;
+
+/// A short Rust function split into highlighted tokens, with a warning and an error
+fn snippet() -> Vec<Vec<(&'static str, EditorScope)>> {
+    use EditorScope::{Comment, Error, Function, Keyword, Number, Type, Warning};
+    use EditorScope::{Foreground as Text, String as Str};
+
+    vec![
+        vec![("// Blend two colors in OKLab", Comment)],
+        vec![
+            ("fn ", Keyword),
+            ("mix", Function),
+            ("(a: ", Text),
+            ("Oklab", Type),
+            (", b: ", Text),
+            ("Oklab", Type),
+            (", t: ", Text),
+            ("f32", Type),
+            (") -> ", Text),
+            ("Oklab", Type),
+            (" {", Text),
+        ],
+        vec![
+            ("    let ", Keyword),
+            ("label", Text),
+            (" = ", Text),
+            ("\"palettepal\"", Str),
+            (";", Text),
+        ],
+        vec![
+            ("    let ", Keyword),
+            ("unused", Warning),
+            (" = ", Text),
+            ("42", Number),
+            (";", Text),
+        ],
+        vec![
+            ("    if ", Keyword),
+            ("t > ", Text),
+            ("1.0", Number),
+            (" {", Text),
+        ],
+        vec![
+            ("        return ", Keyword),
+            ("a + ", Text),
+            ("missing", Error),
+            (";", Text),
+        ],
+        vec![("    }", Text)],
+        vec![
+            ("    Oklab", Type),
+            ("::", Text),
+            ("lerp", Function),
+            ("(a, b, t)", Text),
+        ],
+        vec![("}", Text)],
+    ]
+}
+
+/// The snippet highlighted in the colors of the theme, with line numbers and a selected line
+fn code_preview(editor_theme: &EditorTheme) -> Dom {
+    let color = |scope: EditorScope| editor_theme.color(scope).to_css();
+
+    let token = |text: &str, scope: EditorScope| match scope {
+        EditorScope::Error | EditorScope::Warning | EditorScope::Info | EditorScope::Hint => {
+            html!("span", {
+                .style("color", &color(EditorScope::Foreground))
+                .style("text-decoration", &format!("underline wavy {}", color(scope)))
+                .text(text)
+            })
+        }
+        scope => html!("span", {
+            .style("color", &color(scope))
+            .apply(|b| match scope {
+                EditorScope::Comment => b.style("font-style", "italic"),
+                _ => b,
+            })
+            .text(text)
+        }),
+    };
+
+    html!("div", {
+        .dwclass!("flex flex-col p-4 rounded-sm")
+        .style("background", &color(EditorScope::Background))
+        .style("font-family", "monospace")
+        .style("white-space", "pre")
+        .children(snippet().into_iter().enumerate().map(|(idx, tokens)| {
+            html!("div", {
+                .dwclass!("flex flex-row")
+                .apply(|b| match idx == SELECTED_LINE {
+                    true => b.style("background", color(EditorScope::Selection)),
+                    false => b,
+                })
+                .child(html!("span", {
+                    .dwclass!("w-10")
+                    .style("color", &color(EditorScope::LineNumber))
+                    .text(&format!("{:>3} ", idx + 1))
+                }))
+                .children(tokens.into_iter().map(|(text, scope)| token(text, scope)))
+            })
+        }))
+    })
+}
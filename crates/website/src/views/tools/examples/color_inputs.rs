@@ -1,9 +1,9 @@
-use crate::model::palette::Palette;
-use crate::model::palette_color::PaletteColor;
 use dominator::Dom;
 use dwui::prelude::*;
-use futures_signals::signal::{always, LocalBoxSignal, Mutable, Signal, SignalExt};
+use futures_signals::signal::{always, LocalBoxSignal, Mutable, SignalExt};
 use futures_signals::signal_vec::SignalVec;
+use palette::palette::Palette;
+use palette::palette_color::PaletteColor;
 use std::rc::Rc;
 
 pub struct ColorAdapter {
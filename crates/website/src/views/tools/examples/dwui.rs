@@ -1,9 +1,8 @@
 use crate::mixins::panel::widget_panel_mixin;
-use crate::model::palette::Palette;
-use crate::model::palette_color::PaletteColor;
 use crate::views::main_view::PalettePalViewModel;
 use crate::views::tools::examples::color_inputs::color_input;
 use crate::views::tools::Tool;
+use crate::views::vision_simulation::simulated_colors_signal;
 use dominator::text;
 use dominator::Dom;
 use dominator::DomBuilder;
@@ -15,10 +14,12 @@ use futures_signals::signal::SignalExt;
 use futures_signals::signal::{always, not};
 use futures_signals::signal_vec::SignalVecExt;
 use once_cell::sync::Lazy;
+use palette::palette::{Palette, TAILWIND_NUMBERS};
+use palette::palette_color::PaletteColor;
 use std::rc::Rc;
 use web_sys::HtmlElement;
 
-pub fn dwui_example_container(_vm: &PalettePalViewModel, palette: Palette) -> Dom {
+pub fn dwui_example_container(vm: &PalettePalViewModel, palette: Palette) -> Dom {
     let curves = palette.sampling_curves.clone();
     let sampling_curves = palette.sampling_curves.clone();
 
@@ -58,20 +59,13 @@ pub fn dwui_example_container(_vm: &PalettePalViewModel, palette: Palette) -> Do
 
         Box::new(
             clone!(color, sampling_curves => move |b: DomBuilder<HtmlElement>| {
-                let colors = color.colors_u8_signal(&sampling_curves).broadcast();
+                let colors = simulated_colors_signal(color.colors_u8_signal(&sampling_curves)).broadcast();
 
-                b
-                .style_signal(format!("--dwui-{color_name}-50"), colors.signal_ref(|c| format!("{}", hex_color::HexColor::rgb(c.first().map(|c| c.0).unwrap_or(0), c.first().map(|c| c.1).unwrap_or(0), c.first().map(|c| c.2).unwrap_or(0)).display_rgba())))
-                .style_signal(format!("--dwui-{color_name}-100"), colors.signal_ref(|c| format!("{}", hex_color::HexColor::rgb(c.get(1).map(|c| c.0).unwrap_or(0), c.get(1).map(|c| c.1).unwrap_or(0), c.get(1).map(|c| c.2).unwrap_or(0)).display_rgba())))
-                .style_signal(format!("--dwui-{color_name}-200"), colors.signal_ref(|c| format!("{}", hex_color::HexColor::rgb(c.get(2).map(|c| c.0).unwrap_or(0), c.get(2).map(|c| c.1).unwrap_or(0), c.get(2).map(|c| c.2).unwrap_or(0)).display_rgba())))
-                .style_signal(format!("--dwui-{color_name}-300"), colors.signal_ref(|c| format!("{}", hex_color::HexColor::rgb(c.get(3).map(|c| c.0).unwrap_or(0), c.get(3).map(|c| c.1).unwrap_or(0), c.get(3).map(|c| c.2).unwrap_or(0)).display_rgba())))
-                .style_signal(format!("--dwui-{color_name}-400"), colors.signal_ref(|c| format!("{}", hex_color::HexColor::rgb(c.get(4).map(|c| c.0).unwrap_or(0), c.get(4).map(|c| c.1).unwrap_or(0), c.get(4).map(|c| c.2).unwrap_or(0)).display_rgba())))
-                .style_signal(format!("--dwui-{color_name}-500"), colors.signal_ref(|c| format!("{}", hex_color::HexColor::rgb(c.get(5).map(|c| c.0).unwrap_or(0), c.get(5).map(|c| c.1).unwrap_or(0), c.get(5).map(|c| c.2).unwrap_or(0)).display_rgba())))
-                .style_signal(format!("--dwui-{color_name}-600"), colors.signal_ref(|c| format!("{}", hex_color::HexColor::rgb(c.get(6).map(|c| c.0).unwrap_or(0), c.get(6).map(|c| c.1).unwrap_or(0), c.get(6).map(|c| c.2).unwrap_or(0)).display_rgba())))
-                .style_signal(format!("--dwui-{color_name}-700"), colors.signal_ref(|c| format!("{}", hex_color::HexColor::rgb(c.get(7).map(|c| c.0).unwrap_or(0), c.get(7).map(|c| c.1).unwrap_or(0), c.get(7).map(|c| c.2).unwrap_or(0)).display_rgba())))
-                .style_signal(format!("--dwui-{color_name}-800"), colors.signal_ref(|c| format!("{}", hex_color::HexColor::rgb(c.get(8).map(|c| c.0).unwrap_or(0), c.get(8).map(|c| c.1).unwrap_or(0), c.get(8).map(|c| c.2).unwrap_or(0)).display_rgba())))
-                .style_signal(format!("--dwui-{color_name}-900"), colors.signal_ref(|c| format!("{}", hex_color::HexColor::rgb(c.get(9).map(|c| c.0).unwrap_or(0), c.get(9).map(|c| c.1).unwrap_or(0), c.get(9).map(|c| c.2).unwrap_or(0)).display_rgba())))
-                .style_signal(format!("--dwui-{color_name}-950"), colors.signal_ref(|c| format!("{}", hex_color::HexColor::rgb(c.get(10).map(|c| c.0).unwrap_or(0), c.get(10).map(|c| c.1).unwrap_or(0), c.get(10).map(|c| c.2).unwrap_or(0)).display_rgba())))
+                TAILWIND_NUMBERS.into_iter().enumerate().fold(b, |b, (idx, shade)| {
+                    b.style_signal(format!("--dwui-{color_name}-{shade}"), colors.signal_ref(move |c| {
+                        c.get(idx).copied().unwrap_or_default().to_hex()
+                    }))
+                })
             }),
         )
     };
@@ -80,7 +74,7 @@ pub fn dwui_example_container(_vm: &PalettePalViewModel, palette: Palette) -> Do
 
     html!("div", {
         .dwclass!("flex-1 p-2")
-        .apply(widget_panel_mixin(always("DWUI Preview".to_string()), Some(palette.tools_view_state.create_close_tool_handler(Tool::DwuiExample))))
+        .apply(widget_panel_mixin(always("DWUI Preview".to_string()), Some(vm.tools_view_state.create_close_tool_handler(Tool::DwuiExample))))
         .children([
             // All colors, with type association
             html!("div", {
@@ -1,8 +1,11 @@
 use crate::views::main_view::PalettePalViewModel;
 use crate::views::tools::color_import::color_import_tool;
+use crate::views::tools::colorblind_check_tool::colorblind_check_tool;
 use crate::views::tools::curve_editor::sampling_curve_editor;
+use crate::views::tools::editor_theme_tool::editor_theme_tool;
 use crate::views::tools::examples::dwui::dwui_example_container;
 use crate::views::tools::pixel_art_tool::pixel_art_tool;
+use crate::views::tools::terminal_scheme_tool::terminal_scheme_tool;
 use crate::views::tools::wcag_contrast_tool::wcag_tool;
 use dominator::Dom;
 use futures_signals::signal::SignalExt;
@@ -14,10 +17,14 @@ use serde::{Deserialize, Serialize};
 use std::fmt::{Display, Formatter};
 
 pub mod color_import;
+pub mod colorblind_check_tool;
 pub mod curve_editor;
+pub mod editor_theme_tool;
 pub mod examples;
 pub mod palette_overview;
 pub mod pixel_art_tool;
+pub mod role_shade;
+pub mod terminal_scheme_tool;
 pub mod wcag_contrast_tool;
 
 #[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Ord, PartialOrd, Clone, Copy)]
@@ -28,6 +35,9 @@ pub enum Tool {
     CurveEditor,
     PixelArt,
     ColorImport,
+    ColorblindCheck,
+    TerminalScheme,
+    EditorTheme,
 }
 
 impl Display for Tool {
@@ -39,6 +49,9 @@ impl Display for Tool {
             Tool::CurveEditor => write!(f, "Curve Editor"),
             Tool::PixelArt => write!(f, "Pixel Art"),
             Tool::ColorImport => write!(f, "Color Import"),
+            Tool::ColorblindCheck => write!(f, "Colorblind Check"),
+            Tool::TerminalScheme => write!(f, "Terminal Colors"),
+            Tool::EditorTheme => write!(f, "Editor Theme"),
         }
     }
 }
@@ -91,6 +104,21 @@ impl ToolsViewState {
                     .signal_cloned()
                     .map(clone!(vm => move |palette| wcag_tool(&vm, &palette)))
                     .boxed_local(),
+                Tool::ColorblindCheck => vm
+                    .palette
+                    .signal_cloned()
+                    .map(clone!(vm => move |palette| colorblind_check_tool(&vm, &palette)))
+                    .boxed_local(),
+                Tool::TerminalScheme => vm
+                    .palette
+                    .signal_cloned()
+                    .map(clone!(vm => move |palette| terminal_scheme_tool(&vm, &palette)))
+                    .boxed_local(),
+                Tool::EditorTheme => vm
+                    .palette
+                    .signal_cloned()
+                    .map(clone!(vm => move |palette| editor_theme_tool(&vm, &palette)))
+                    .boxed_local(),
                 Tool::CurveEditor => always(sampling_curve_editor(&vm)).boxed_local(),
                 Tool::PixelArt => pixel_art_tool(&vm).boxed_local(),
                 Tool::ColorImport => always(color_import_tool(&vm)).boxed_local(),
@@ -98,11 +126,18 @@ impl ToolsViewState {
     }
 
     /// Returns a function that toggles the visibility of a tool
-    pub fn create_close_tool_handler(&self, tool: Tool) -> Box<dyn Fn() -> () + 'static> {
+    pub fn create_close_tool_handler(&self, tool: Tool) -> Box<dyn Fn() + 'static> {
         let show_tools = self.show_tools.clone();
 
         Box::new(move || {
             show_tools.lock_mut().insert(tool, false);
         })
     }
+
+    /// Show the same tools as `other`, e.g. when a palette file is loaded
+    pub fn set_from(&self, other: &ToolsViewState) {
+        self.show_tools
+            .lock_mut()
+            .replace_cloned(other.show_tools.lock_ref().clone());
+    }
 }
@@ -1,22 +1,28 @@
 use crate::mixins::panel::widget_panel_mixin;
-use crate::model::palette::Palette;
 use crate::views::main_view::PalettePalViewModel;
 use crate::views::tools::Tool;
+use crate::views::vision_simulation::{simulated_colors_signal, simulation_signal};
+use color::conversion::{Hsv, Srgb8};
 use dominator::{events, Dom, EventOptions};
 use dwind::prelude::*;
 use futures_signals::signal::{always, Mutable, SignalExt};
 use futures_signals::signal_vec::SignalVecExt;
+use palette::palette::Palette;
 use std::rc::Rc;
 use std::time::Duration;
 use wasm_bindgen::{JsCast, UnwrapThrowExt};
 use web_sys::{CanvasRenderingContext2d, HtmlCanvasElement};
 
 pub fn palette_overview(vm: PalettePalViewModel) -> Dom {
-    let PalettePalViewModel { palette, .. } = vm;
+    let PalettePalViewModel {
+        palette,
+        tools_view_state,
+        ..
+    } = vm;
 
     html!("div", {
         .dwclass!("p-2")
-        .apply(widget_panel_mixin(always("Color Wheel".to_string()), Some(palette.get_cloned().tools_view_state.create_close_tool_handler(Tool::PaletteOverview))))
+        .apply(widget_panel_mixin(always("Color Wheel".to_string()), Some(tools_view_state.create_close_tool_handler(Tool::PaletteOverview))))
         .child(html!("div", {
             .dwclass!("p-4 @md:flex-col @<md:flex-row flex justify-center align-items-center gap-4 @md:w-64 @<md:w-full")
             .child(preview_palette(palette.get_cloned()))
@@ -32,7 +38,7 @@ fn preview_palette(palette: Palette) -> Dom {
         .colors
         .signal_vec_cloned()
         .map_signal(clone!(sampling_curves => move |color| {
-            color.colors_u8_signal(&sampling_curves)
+            simulated_colors_signal(color.colors_u8_signal(&sampling_curves))
         }))
         .to_signal_cloned();
 
@@ -52,8 +58,8 @@ fn preview_palette(palette: Palette) -> Dom {
                         ctx.clear_rect(0., 0., 512., 512.);
 
                         for (y, color) in colors.into_iter().enumerate() {
-                            for (x, (r, g, b)) in color.iter().enumerate() {
-                                ctx.set_fill_style_str(&format!("rgb({}, {}, {})", r, g, b));
+                            for (x, color) in color.iter().enumerate() {
+                                ctx.set_fill_style_str(&color.to_css());
                                 ctx.fill_rect(x as f64 * PIXEL_SIZE, y as f64 * PIXEL_SIZE, PIXEL_SIZE, PIXEL_SIZE);
                             }
                         }
@@ -102,24 +108,29 @@ fn color_circle_preview(palette: Palette) -> Dom {
             .attr("width", "512")
             .attr("height", "512")
             .dwclass!("w-32 h-32 grid-col-1 grid-row-1")
-            .after_inserted(|node| {
-                let ctx = node.get_context("2d").unwrap_throw().unwrap_throw().dyn_into::<CanvasRenderingContext2d>().unwrap_throw();
-                let mut angle = 0.;
-                let num_sectors = 64;
-
-                for _ in 0..num_sectors {
-                    let hsv = hsv::hsv_to_rgb(angle, 1., 1.);
-                    let next_angle = angle + 360. / num_sectors as f64;
-                    ctx.set_fill_style_str(&format!("rgb({}, {}, {})", hsv.0, hsv.1, hsv.2));
-
-                    ctx.begin_path();
-                    ctx.move_to(256., 256.);
-                    ctx.arc(256., 256., 256., angle.to_radians(), next_angle.to_radians()).unwrap_throw();
-                    ctx.fill();
-                    ctx.close_path();
-
-                    angle = next_angle;
-                }
+            .with_node!(canvas => {
+                .future(simulation_signal().for_each(move |(simulation, severity)| {
+                    let ctx = canvas.get_context("2d").unwrap_throw().unwrap_throw().dyn_into::<CanvasRenderingContext2d>().unwrap_throw();
+                    let mut angle = 0.;
+                    let num_sectors = 64;
+
+                    for _ in 0..num_sectors {
+                        let rgb = Srgb8::from(Hsv { h: angle as f32, s: 1., v: 1. });
+                        let rgb = simulation.simulate(rgb.into(), severity);
+                        let next_angle = angle + 360. / num_sectors as f64;
+                        ctx.set_fill_style_str(&rgb.to_hex());
+
+                        ctx.begin_path();
+                        ctx.move_to(256., 256.);
+                        ctx.arc(256., 256., 256., angle.to_radians(), next_angle.to_radians()).unwrap_throw();
+                        ctx.fill();
+                        ctx.close_path();
+
+                        angle = next_angle;
+                    }
+
+                    async {}
+                }))
             })
         }))
         .child(svg!("svg", {
@@ -1,25 +1,25 @@
 use crate::mixins::panel::widget_panel_mixin;
-use crate::model::palette::Palette;
 use crate::views::main_view::PalettePalViewModel;
 use crate::views::tools::Tool;
+use crate::views::vision_simulation::{simulated_colors_signal, simulation_signal};
+use color::contrast::RGBA;
 use dominator::events::MouseButton;
 use dominator::{events, Dom};
 use dwind::prelude::*;
 use futures::{FutureExt, StreamExt};
 use futures_signals::signal::SignalExt;
 use futures_signals::signal::{always, Mutable, Signal};
-use futures_signals::signal_vec::{MutableVec, SignalVecExt, VecDiff};
+use futures_signals::signal_vec::{SignalVec, SignalVecExt};
+use palette::palette::Palette;
 use std::time::Duration;
 use wasm_bindgen::{JsCast, UnwrapThrowExt};
-use wasm_bindgen_futures::spawn_local;
-use web_sys::console::info;
 use web_sys::{CanvasRenderingContext2d, HtmlCanvasElement};
 
 pub fn pixel_art_tool(vm: &PalettePalViewModel) -> impl Signal<Item = Dom> {
-    vm.palette.signal_ref(|palette| {
-        let close_cb = palette
-            .tools_view_state
-            .create_close_tool_handler(Tool::PixelArt);
+    let tools_view_state = vm.tools_view_state.clone();
+
+    vm.palette.signal_ref(move |palette| {
+        let close_cb = tools_view_state.create_close_tool_handler(Tool::PixelArt);
         html!("div", {
         .dwclass!("flex-1 p-2 relative")
         .apply(widget_panel_mixin(always("Pixel Art Preview".to_string()), Some(close_cb)))
@@ -28,6 +28,15 @@ pub fn pixel_art_tool(vm: &PalettePalViewModel) -> impl Signal<Item = Dom> {
     })
 }
 
+/// All shades of the palette, throttled while the palette is being edited
+fn palette_colors_signal(palette: &Palette) -> impl SignalVec<Item = RGBA> {
+    let curves = palette.sampling_curves.clone();
+
+    palette.colors.signal_vec_cloned().map(clone!(curves => move |color| {
+        color.colors_u8_signal(&curves).throttle(|| gloo_timers::future::sleep(Duration::from_millis(500))).to_signal_vec()
+    })).flatten()
+}
+
 fn pixel_art_impl(palette: &Palette) -> Dom {
     let selected_color = Mutable::new(0);
 
@@ -41,11 +50,14 @@ fn pixel_art_impl(palette: &Palette) -> Dom {
 fn pixel_art_palette(palette: &Palette, selected_color: Mutable<usize>) -> Dom {
     html!("div", {
         .dwclass!("flex flex-row flex-wrap w-32")
-        .children_signal_vec(palette.palette_colors_signal().enumerate().map(move |(color_idx, (r, g, b))| {
-            let rgba = format!("rgba({}, {}, {}, 1)", r, g, b);
+        .children_signal_vec(palette_colors_signal(palette).enumerate().map(move |(color_idx, color)| {
+            let rgba_signal = simulation_signal().map(move |(simulation, severity)| {
+                simulation.simulate(color, severity).to_css()
+            });
+
             html!("div", {
                 .dwclass!("w-8 h-8 cursor-pointer")
-                .style("background-color", rgba)
+                .style_signal("background-color", rgba_signal)
                 .dwclass_signal!("border border-w-5px border-red-500", selected_color.signal().map(clone!(color_idx => move |s_idx| {
                     color_idx.signal().map(move |color_idx| {
                         color_idx == Some(s_idx)
@@ -59,16 +71,8 @@ fn pixel_art_palette(palette: &Palette, selected_color: Mutable<usize>) -> Dom {
     })
 }
 
-#[derive(Debug, Copy, Clone, Eq, PartialEq)]
-enum ToolMode {
-    Draw,
-    Erase,
-}
-
 fn pixel_editor(palette: &Palette, selected_color_index: Mutable<usize>) -> Dom {
     let pixels = Mutable::new([u16::MAX; 80 * 4 * 80 * 4].into_iter().collect::<Vec<_>>());
-    let colors = palette.palette_colors_signal().to_signal_cloned();
-    let tool_mode = Mutable::new(ToolMode::Draw);
 
     html!("div", {
         .child(html!("canvas" => HtmlCanvasElement, {
@@ -89,7 +93,7 @@ fn pixel_editor(palette: &Palette, selected_color_index: Mutable<usize>) -> Dom
                             let x = event.offset_x();
                             let y = event.offset_y();
 
-                            if x < 0 || x >= 320 || y < 0 || y >= 320 {
+                            if !(0..320).contains(&x) || !(0..320).contains(&y) {
                                 return;
                             }
 
@@ -169,7 +173,6 @@ fn pixel_editor(palette: &Palette, selected_color_index: Mutable<usize>) -> Dom
                                 let mut new_pixels = pixels.get_cloned();
 
                                 cmds.iter().for_each(|(x, y, color_index)| {
-                                    let pixel_index = y * 320 + x;
                                     // draw a 5x5 circle
                                     for dx in 0..13 {
                                         for dy in 0..13 {
@@ -184,7 +187,6 @@ fn pixel_editor(palette: &Palette, selected_color_index: Mutable<usize>) -> Dom
 
                                 pixels.set(new_pixels);
                                 draw_commands.set(None);
-                            } else {
                             }
                             async {}
                         })).await;
@@ -196,7 +198,7 @@ fn pixel_editor(palette: &Palette, selected_color_index: Mutable<usize>) -> Dom
                         let mut colors = vec![];
                         let mut pixel_values = vec![];
 
-                        let colors_signal = palette.palette_colors_signal().to_signal_cloned().throttle(||gloo_timers::future::sleep(Duration::from_millis(1000)));
+                        let colors_signal = simulated_colors_signal(palette_colors_signal(&palette).to_signal_cloned()).throttle(||gloo_timers::future::sleep(Duration::from_millis(1000)));
                         let mut pixels_stream = pixels.signal_cloned().to_stream();
                         let mut colors_stream = colors_signal.to_stream();
 
@@ -213,16 +215,16 @@ fn pixel_editor(palette: &Palette, selected_color_index: Mutable<usize>) -> Dom
                                 }
                             }
 
-                            let mut px_color = (0,0,0);
+                            let mut px_color = None;
 
                             for x in 0usize..320 {
                                 for y in 0usize..320 {
                                     let pixel_index = pixel_values[y * 320 + x];
-                                    let (r, g, b) = colors.get(pixel_index as usize).unwrap_or(&(0,0,0));
+                                    let color = colors.get(pixel_index as usize).copied().unwrap_or(RGBA::opaque(0, 0, 0));
 
-                                    if (*r, *g, *b) != px_color {
-                                        px_color = (*r, *g, *b);
-                                        ctx.set_fill_style_str(&format!("rgb({}, {}, {})", r, g, b));
+                                    if px_color != Some(color) {
+                                        px_color = Some(color);
+                                        ctx.set_fill_style_str(&color.to_css());
                                     }
 
                                     ctx.fill_rect(x as f64, y as f64, 1., 1.);
@@ -0,0 +1,85 @@
+use color::contrast::RGBA;
+use dwui::prelude::*;
+use futures_signals::map_ref;
+use futures_signals::signal::{Broadcaster, LocalBoxSignal, Mutable, Signal, SignalExt};
+use futures_signals::signal_vec::SignalVecExt;
+use palette::palette::{shade_name, Palette};
+use palette::terminal_scheme::ShadeRef;
+use std::collections::BTreeMap;
+
+/// The shades the user picked for roles, replacing their default shade
+pub type Overrides<R> = Mutable<BTreeMap<R, ShadeRef>>;
+
+/// The name and shades of every color of a palette, updated as the palette changes
+pub fn swatches_signal(palette: &Palette) -> impl Signal<Item = Vec<(String, Vec<RGBA>)>> {
+    let curves = palette.sampling_curves.clone();
+
+    palette
+        .colors
+        .signal_vec_cloned()
+        .map_signal(move |color| {
+            map_ref! {
+                let name = color.name.signal_cloned(),
+                let shades = color.colors_u8_signal(&curves) => (name.clone(), shades.clone())
+            }
+        })
+        .to_signal_cloned()
+}
+
+/// Select options of every shade, keyed by their [ShadeRef] and labelled `<color>-<shade>`
+pub fn shade_options(swatches: &[(String, Vec<RGBA>)]) -> Vec<(String, String)> {
+    swatches
+        .iter()
+        .enumerate()
+        .flat_map(|(color, (name, shades))| {
+            (0..shades.len()).map(move |shade| {
+                (
+                    ShadeRef { color, shade }.to_string(),
+                    format!("{name}-{}", shade_name(shade)),
+                )
+            })
+        })
+        .collect()
+}
+
+/// The shade of a role in a mapping, which is overridden when the user picks another shade
+pub struct RoleShade<R, T, S>
+where
+    S: Signal<Item = (Vec<(R, ShadeRef)>, T)> + 'static,
+{
+    pub role: R,
+    pub overrides: Overrides<R>,
+    pub mapping: Broadcaster<S>,
+}
+
+impl<R, T, S> InputValueWrapper for RoleShade<R, T, S>
+where
+    R: Copy + Ord + 'static,
+    S: Signal<Item = (Vec<(R, ShadeRef)>, T)> + 'static,
+{
+    fn set(&self, value: String) -> ValidationResult {
+        let Ok(shade) = value.parse::<ShadeRef>() else {
+            return ValidationResult::Invalid {
+                message: "Invalid shade".to_string(),
+            };
+        };
+
+        self.overrides.lock_mut().insert(self.role, shade);
+
+        ValidationResult::Valid
+    }
+
+    fn value_signal_cloned(&self) -> LocalBoxSignal<'static, String> {
+        let role = self.role;
+
+        self.mapping
+            .signal_ref(move |(mapping, _)| {
+                mapping
+                    .iter()
+                    .find(|(r, _)| *r == role)
+                    .map(|(_, shade)| shade.to_string())
+                    .unwrap_or_default()
+            })
+            .boxed_local()
+    }
+}
@@ -0,0 +1,219 @@
+use crate::mixins::panel::widget_panel_mixin;
+use crate::views::main_view::PalettePalViewModel;
+use crate::views::palette_controls::download_file;
+use crate::views::tools::role_shade::{shade_options, swatches_signal, Overrides, RoleShade};
+use crate::views::tools::Tool;
+use dominator::Dom;
+use dwind::prelude::*;
+use dwui::prelude::*;
+use dwui::select;
+use futures_signals::map_ref;
+use futures_signals::signal::{always, Mutable, SignalExt};
+use palette::palette::Palette;
+use palette::terminal_scheme::{
+    default_mapping, AnsiColor, TerminalFormat, TerminalRole, TerminalScheme, TerminalTheme,
+};
+use std::collections::BTreeMap;
+
+pub fn terminal_scheme_tool(vm: &PalettePalViewModel, palette: &Palette) -> Dom {
+    let theme = Mutable::new(TerminalTheme::Dark);
+    let format = Mutable::new(TerminalFormat::Alacritty);
+    let colors_256 = Mutable::new(false);
+    let overrides: Overrides<TerminalRole> = Mutable::new(BTreeMap::new());
+
+    let swatches_signal = swatches_signal(palette).broadcast();
+
+    // The default mapping of the theme, with the roles the user picked a shade for replaced
+    let mapping_signal = map_ref! {
+        let swatches = swatches_signal.signal_cloned(),
+        let theme = theme.signal(),
+        let overrides = overrides.signal_cloned() => {
+            let mapping = default_mapping(swatches, *theme)
+                .into_iter()
+                .map(|(role, shade)| (role, overrides.get(&role).copied().unwrap_or(shade)))
+                .collect::<Vec<_>>();
+
+            let scheme = TerminalScheme::from_mapping("palettepal", swatches, &mapping);
+
+            (mapping, scheme)
+        }
+    }
+    .broadcast();
+
+    let export_signal = map_ref! {
+        let (_, scheme) = mapping_signal.signal_cloned(),
+        let format = format.signal(),
+        let colors_256 = colors_256.signal() => {
+            scheme.export(*format, *colors_256)
+        }
+    };
+
+    // The export of the current scheme, for the download button to read
+    let export_text = Mutable::new(String::new());
+
+    let shade_options = swatches_signal
+        .signal_ref(|swatches| shade_options(swatches))
+        .broadcast();
+
+    let role_selects = TerminalRole::ALL.map(|role| {
+        select!({
+            .label(role.to_string())
+            .value(RoleShade {
+                role,
+                overrides: overrides.clone(),
+                mapping: mapping_signal.clone(),
+            })
+            .options_signal_vec(shade_options.signal_cloned().to_signal_vec())
+        })
+    });
+
+    let body = html!("div", {
+        .dwclass!("flex-1 flex flex-col gap-4 p-4")
+        .child(html!("div", {
+            .dwclass!("flex flex-row flex-wrap gap-2")
+            .children([
+                select!({
+                    .label("Theme".to_string())
+                    .value(theme.clone())
+                    .options(TerminalTheme::ALL.map(|v| (v.to_string(), v.to_string())).to_vec())
+                }),
+                select!({
+                    .label("Format".to_string())
+                    .value(format.clone())
+                    .options(TerminalFormat::ALL.map(|v| (v.to_string(), v.to_string())).to_vec())
+                }),
+                select!({
+                    .label("256 colors".to_string())
+                    .value(colors_256.clone())
+                    .options(vec![
+                        ("false".to_string(), "16 colors only".to_string()),
+                        ("true".to_string(), "Blend colors 16-255".to_string()),
+                    ])
+                }),
+                button!({
+                    .content(Some(html!("div", {
+                        .dwclass!("p-l-2 p-r-2")
+                        .text("Reset to defaults")
+                    })))
+                    .on_click(clone!(overrides => move |_| {
+                        overrides.set(BTreeMap::new());
+                    }))
+                }),
+                button!({
+                    .content(Some(html!("div", {
+                        .dwclass!("p-l-2 p-r-2")
+                        .text("Download")
+                    })))
+                    .on_click(clone!(export_text, format => move |_| {
+                        download_file(format.get().file_name(), export_text.get_cloned());
+                    }))
+                }),
+            ])
+        }))
+        .child_signal(mapping_signal.signal_cloned().map(|(_, scheme)| Some(terminal_preview(&scheme))))
+        .child(html!("div", {
+            .dwclass!("grid grid-cols-4 gap-2")
+            .children(role_selects)
+        }))
+        .child(html!("div", {
+            .dwclass!("overflow-y-scroll h-64")
+            .child(html!("pre", {
+                .future(export_signal.for_each(clone!(export_text => move |export| {
+                    export_text.set(export);
+                    async {}
+                })))
+                .text_signal(export_text.signal_cloned())
+            }))
+        }))
+    });
+
+    let close_cb = vm
+        .tools_view_state
+        .create_close_tool_handler(Tool::TerminalScheme);
+
+    html!("div", {
+        .dwclass!("flex-1 p-2 relative")
+        .apply(widget_panel_mixin(always("Terminal Colors".to_string()), Some(close_cb)))
+        .child(body)
+    })
+}
+
+/// A mock terminal session in the colors of the scheme, with the 16 ANSI colors below it
+fn terminal_preview(scheme: &TerminalScheme) -> Dom {
+    let color = |role: TerminalRole| scheme.color(role).to_css();
+
+    let span = |text: &str, role: TerminalRole| {
+        html!("span", {
+            .style("color", &color(role))
+            .text(text)
+        })
+    };
+
+    let line = |children: Vec<Dom>| {
+        html!("div", {
+            .children(children)
+        })
+    };
+
+    let prompt = || span("$ ", TerminalRole::Normal(AnsiColor::Green));
+    let text = |text: &str| span(text, TerminalRole::Foreground);
+
+    let ansi_row = |role: fn(AnsiColor) -> TerminalRole| {
+        html!("div", {
+            .dwclass!("flex flex-row")
+            .children(AnsiColor::ALL.map(|ansi| {
+                html!("div", {
+                    .dwclass!("w-10 h-6")
+                    .attr("title", &role(ansi).to_string())
+                    .style("background", &color(role(ansi)))
+                })
+            }))
+        })
+    };
+
+    html!("div", {
+        .dwclass!("flex flex-col gap-2 p-4 rounded-sm")
+        .style("background", &color(TerminalRole::Background))
+        .style("font-family", "monospace")
+        .children([
+            line(vec![prompt(), text("ls")]),
+            line(vec![
+                span("src  ", TerminalRole::Normal(AnsiColor::Blue)),
+                span("build.sh  ", TerminalRole::Normal(AnsiColor::Green)),
+                span("archive.tar  ", TerminalRole::Normal(AnsiColor::Red)),
+                span("link  ", TerminalRole::Normal(AnsiColor::Cyan)),
+                text("README.md"),
+            ]),
+            line(vec![prompt(), text("git status")]),
+            line(vec![
+                span("  modified:   ", TerminalRole::Normal(AnsiColor::Red)),
+                span("src/main.rs", TerminalRole::Bright(AnsiColor::Red)),
+            ]),
+            line(vec![
+                span("  new file:   ", TerminalRole::Normal(AnsiColor::Green)),
+                span("src/lib.rs", TerminalRole::Bright(AnsiColor::Green)),
+            ]),
+            line(vec![
+                span("warning: ", TerminalRole::Normal(AnsiColor::Yellow)),
+                span("unused import ", TerminalRole::Bright(AnsiColor::Yellow)),
+                span("# comment", TerminalRole::Bright(AnsiColor::Black)),
+            ]),
+            line(vec![
+                prompt(),
+                html!("span", {
+                    .style("background", &color(TerminalRole::Selection))
+                    .style("color", &color(TerminalRole::Foreground))
+                    .text("selected text")
+                }),
+                text(" "),
+                html!("span", {
+                    .style("background", &color(TerminalRole::Cursor))
+                    .style("color", &color(TerminalRole::Background))
+                    .text(" ")
+                }),
+            ]),
+            ansi_row(TerminalRole::Normal),
+            ansi_row(TerminalRole::Bright),
+        ])
+    })
+}
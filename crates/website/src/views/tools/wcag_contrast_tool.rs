@@ -1,48 +1,40 @@
 use crate::mixins::panel::widget_panel_mixin;
-use crate::model::palette::Palette;
-use crate::model::palette_color::PaletteColor;
 use crate::views::main_view::PalettePalViewModel;
 use crate::views::tools::examples::color_inputs::color_input;
 use crate::views::tools::Tool;
+use color::apca::ApcaUsage;
 use color::contrast::{
-    is_enhanced_text_contrast, is_minimum_text_contrast, SwatchColorContrast, RGBA,
+    is_enhanced_text_contrast, is_minimum_text_contrast, ContrastAlgorithm, SwatchColorContrast,
+    RGBA,
 };
 use dominator::Dom;
 use dwind::prelude::*;
+use dwui::prelude::*;
+use dwui::select;
 use futures_signals::map_ref;
-use futures_signals::signal::{always, Mutable, Signal, SignalExt};
+use futures_signals::signal::{always, Broadcaster, Mutable, Signal, SignalExt};
 use futures_signals::signal_vec::{SignalVec, SignalVecExt};
+use palette::palette::Palette;
+use palette::palette_color::PaletteColor;
 use std::rc::Rc;
 
-pub fn wcag_tool(_vm: &PalettePalViewModel, palette: &Palette) -> Dom {
-    let color_a = Mutable::new(palette.colors.lock_ref().get(0).cloned());
-    let color_b = Mutable::new(palette.colors.lock_ref().get(0).cloned());
+pub fn wcag_tool(vm: &PalettePalViewModel, palette: &Palette) -> Dom {
+    let color_a = Mutable::new(palette.colors.lock_ref().first().cloned());
+    let color_b = Mutable::new(palette.colors.lock_ref().first().cloned());
+    let algorithm = Mutable::new(ContrastAlgorithm::Wcag2);
 
     let color_a_shades_signal = color_shades_signal(color_a.clone(), palette);
     let color_b_shades_signal = color_shades_signal(color_b.clone(), palette);
 
     let contrasts_signal = map_ref! {
         let color_a_shades = color_a_shades_signal,
-        let color_b_shades = color_b_shades_signal => {
-            color::contrast::swatch_color_contrast(color_a_shades.clone(), color_b_shades.clone())
+        let color_b_shades = color_b_shades_signal,
+        let algorithm = algorithm.signal() => {
+            color::contrast::swatch_color_contrast(color_a_shades.clone(), color_b_shades.clone(), *algorithm)
         }
     }
     .broadcast();
 
-    let text_minimum_contrasts_signal =
-        contrasts_signal
-            .signal_cloned()
-            .to_signal_vec()
-            .filter(|contrast| {
-                is_minimum_text_contrast(contrast.color_a, contrast.color_b)
-                    && !is_enhanced_text_contrast(contrast.color_a, contrast.color_b)
-            });
-
-    let text_enhanced_contrast_signal = contrasts_signal
-        .signal_cloned()
-        .to_signal_vec()
-        .filter(|contrast| is_enhanced_text_contrast(contrast.color_a, contrast.color_b));
-
     let color_names_signal_vec = palette
         .colors
         .signal_vec_cloned()
@@ -59,14 +51,27 @@ pub fn wcag_tool(_vm: &PalettePalViewModel, palette: &Palette) -> Dom {
             .children([
                 color_input("Color A", &palette, color_a.clone(), color_names_signal_vec.signal_cloned().to_signal_vec()),
                 color_input("Color B", &palette, color_b.clone(), color_names_signal_vec.signal_cloned().to_signal_vec()),
+                select!({
+                    .label("Algorithm".to_string())
+                    .value(algorithm.clone())
+                    .options(vec![
+                        ("WCAG 2".to_string(), "WCAG 2".to_string()),
+                        ("APCA".to_string(), "APCA".to_string()),
+                    ])
+                }),
             ])
         }))
         .child(html!("div", {
             .dwclass!("overflow-y-scroll flex h-80")
-            .child(html!("table", {
-                .dwclass!("divide-y border-woodsmoke-500 border-collapse")
-                .children([
-                    html!("tr", {
+            .child_signal(algorithm.signal().map(move |algorithm| {
+                let rows = match algorithm {
+                    ContrastAlgorithm::Wcag2 => wcag2_rows(&contrasts_signal),
+                    ContrastAlgorithm::Apca => apca_rows(&contrasts_signal),
+                };
+
+                Some(html!("table", {
+                    .dwclass!("divide-y border-woodsmoke-500 border-collapse")
+                    .child(html!("tr", {
                         .dwclass!("border-woodsmoke-500")
                         .children([
                             html!("th", {
@@ -76,37 +81,14 @@ pub fn wcag_tool(_vm: &PalettePalViewModel, palette: &Palette) -> Dom {
                                 .text("Color pairs")
                             })
                         ])
-                    }),
-                    html!("tr", {
-                        .dwclass!("border-woodsmoke-500")
-                        .children([
-                            html!("td", {
-                                .text("Text, enhanced")
-                            }),
-                            html!("td", {
-                                .dwclass!("flex flex-wrap flex-row gap-2")
-                                .children_signal_vec(contrasts_display(text_enhanced_contrast_signal))
-                            })
-                        ])
-                    }),
-                    html!("tr", {
-                        .dwclass!("border-woodsmoke-500")
-                        .children([
-                            html!("td", {
-                                .text("Text, minimum")
-                            }),
-                            html!("td", {
-                                .dwclass!("flex flex-wrap flex-row gap-2")
-                                .children_signal_vec(contrasts_display(text_minimum_contrasts_signal))
-                            })
-                        ])
-                    })
-                ])
+                    }))
+                    .children(rows)
+                }))
             }))
         }))
     });
 
-    let close_cb = palette
+    let close_cb = vm
         .tools_view_state
         .create_close_tool_handler(Tool::WcagContrast);
     html!("div", {
@@ -116,32 +98,85 @@ pub fn wcag_tool(_vm: &PalettePalViewModel, palette: &Palette) -> Dom {
     })
 }
 
+fn contrasts_row(label: &str, contrasts: impl SignalVec<Item = Dom> + 'static) -> Dom {
+    html!("tr", {
+        .dwclass!("border-woodsmoke-500")
+        .children([
+            html!("td", {
+                .text(label)
+            }),
+            html!("td", {
+                .dwclass!("flex flex-wrap flex-row gap-2")
+                .children_signal_vec(contrasts)
+            })
+        ])
+    })
+}
+
+fn wcag2_rows(
+    contrasts_signal: &Broadcaster<impl Signal<Item = Vec<SwatchColorContrast>> + 'static>,
+) -> Vec<Dom> {
+    let text_minimum_contrasts_signal =
+        contrasts_signal
+            .signal_cloned()
+            .to_signal_vec()
+            .filter(|contrast| {
+                is_minimum_text_contrast(contrast.color_a, contrast.color_b)
+                    && !is_enhanced_text_contrast(contrast.color_a, contrast.color_b)
+            });
+
+    let text_enhanced_contrast_signal = contrasts_signal
+        .signal_cloned()
+        .to_signal_vec()
+        .filter(|contrast| is_enhanced_text_contrast(contrast.color_a, contrast.color_b));
+
+    vec![
+        contrasts_row(
+            "Text, enhanced",
+            contrasts_display(text_enhanced_contrast_signal),
+        ),
+        contrasts_row(
+            "Text, minimum",
+            contrasts_display(text_minimum_contrasts_signal),
+        ),
+    ]
+}
+
+/// One row per APCA usage level, each pair is listed under the most demanding level it fulfills
+fn apca_rows(
+    contrasts_signal: &Broadcaster<impl Signal<Item = Vec<SwatchColorContrast>> + 'static>,
+) -> Vec<Dom> {
+    ApcaUsage::ALL
+        .into_iter()
+        .map(|usage| {
+            let usage_contrasts_signal = contrasts_signal
+                .signal_cloned()
+                .to_signal_vec()
+                .filter(move |contrast| ApcaUsage::from_lc(contrast.contrast) == Some(usage));
+
+            contrasts_row(
+                &format!("{usage}, {}", usage.font_requirement()),
+                apca_contrasts_display(usage_contrasts_signal),
+            )
+        })
+        .collect()
+}
+
 fn contrasts_display(
     contrasts: impl SignalVec<Item = SwatchColorContrast>,
 ) -> impl SignalVec<Item = Dom> {
     contrasts.map(|contrast| {
-        let first_color = format!(
-            "rgb({}, {}, {})",
-            contrast.color_a.0, contrast.color_a.1, contrast.color_a.2
-        );
-        let first_hex = format!(
-            "#{:02x}{:02x}{:02x}",
-            contrast.color_a.0, contrast.color_a.1, contrast.color_a.2
-        );
+        let first_color = contrast.color_a.to_css();
+        let first_hex = contrast.color_a.to_hex();
         let first_tailwind = index_to_tailwind_number(contrast.swatch_a_idx);
 
-        let second_color = format!(
-            "rgb({}, {}, {})",
-            contrast.color_b.0, contrast.color_b.1, contrast.color_b.2
-        );
-        let second_hex = format!(
-            "#{:02x}{:02x}{:02x}",
-            contrast.color_b.0, contrast.color_b.1, contrast.color_b.2
-        );
+        let second_color = contrast.color_b.to_css();
+        let second_hex = contrast.color_b.to_hex();
         let second_tailwind = index_to_tailwind_number(contrast.swatch_b_idx);
 
         html!("div", {
             .dwclass!("flex w-40 flex-row gap-2 bg-woodsmoke-600 rounded-sm p-1")
+            .attr("title", &format!("{:.2}:1", contrast.contrast))
             .children([
                 html!("div", {
                     .dwclass!("w-20 h-10 flex justify-center align-items-center")
@@ -166,6 +201,29 @@ fn contrasts_display(
     })
 }
 
+/// Displays color a as text on color b, along with the Lc value
+fn apca_contrasts_display(
+    contrasts: impl SignalVec<Item = SwatchColorContrast>,
+) -> impl SignalVec<Item = Dom> {
+    contrasts.map(|contrast| {
+        let text_color = contrast.color_a.to_css();
+        let background_color = contrast.color_b.to_css();
+        let text_tailwind = index_to_tailwind_number(contrast.swatch_a_idx);
+        let background_tailwind = index_to_tailwind_number(contrast.swatch_b_idx);
+
+        html!("div", {
+            .dwclass!("flex w-40 flex-row gap-2 bg-woodsmoke-600 rounded-sm p-1")
+            .attr("title", &format!("a-{text_tailwind} on b-{background_tailwind}"))
+            .child(html!("div", {
+                .dwclass!("w-full h-10 flex justify-center align-items-center")
+                .style("background", &background_color)
+                .style("color", &text_color)
+                .text(&format!("Lc {:.1}", contrast.contrast))
+            }))
+        })
+    })
+}
+
 fn color_shades_signal(
     color: Mutable<Option<PaletteColor>>,
     palette: &Palette,
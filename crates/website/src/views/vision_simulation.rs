@@ -0,0 +1,78 @@
+//!
+//!  # Vision simulation
+//!
+//! Global color vision deficiency simulation. When enabled, every rendered swatch is shown as it
+//! would appear with the selected deficiency.
+//!
+
+use color::contrast::RGBA;
+use color::cvd::ColorVisionDeficiency;
+use futures_signals::map_ref;
+use futures_signals::signal::{Mutable, Signal};
+use once_cell::sync::Lazy;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+pub static VISION_SIMULATION: Lazy<Mutable<VisionSimulation>> =
+    Lazy::new(|| Mutable::new(VisionSimulation::Off));
+
+/// Severity of the simulated deficiency, in the range 0..1
+pub static SIMULATION_SEVERITY: Lazy<Mutable<f32>> = Lazy::new(|| Mutable::new(1.));
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum VisionSimulation {
+    #[default]
+    Off,
+    Simulate(ColorVisionDeficiency),
+}
+
+impl VisionSimulation {
+    pub fn simulate(&self, color: RGBA, severity: f32) -> RGBA {
+        match self {
+            VisionSimulation::Off => color,
+            VisionSimulation::Simulate(deficiency) => deficiency.simulate(color, severity),
+        }
+    }
+}
+
+impl Display for VisionSimulation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VisionSimulation::Off => write!(f, "Normal vision"),
+            VisionSimulation::Simulate(deficiency) => write!(f, "{deficiency}"),
+        }
+    }
+}
+
+impl FromStr for VisionSimulation {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Normal vision" => Ok(Self::Off),
+            _ => s.parse().map(Self::Simulate),
+        }
+    }
+}
+
+/// The current simulation and its severity
+pub fn simulation_signal() -> impl Signal<Item = (VisionSimulation, f32)> {
+    map_ref! {
+        let simulation = VISION_SIMULATION.signal(),
+        let severity = SIMULATION_SEVERITY.signal() => (*simulation, *severity)
+    }
+}
+
+/// Runs the colors through the current simulation
+pub fn simulated_colors_signal(
+    colors_signal: impl Signal<Item = Vec<RGBA>>,
+) -> impl Signal<Item = Vec<RGBA>> {
+    map_ref! {
+        let colors = colors_signal,
+        let simulation = simulation_signal() => {
+            let (simulation, severity) = *simulation;
+
+            colors.iter().map(|color| simulation.simulate(*color, severity)).collect()
+        }
+    }
+}